@@ -0,0 +1,47 @@
+//! Compares the batched MDS multiplication against the scalar one.
+
+#[macro_use]
+extern crate bencher;
+
+use bencher::{black_box, Bencher};
+use ff::Field;
+use halo2curves::bn256::Fr;
+use poseidon2::base::primitives::{mat_mul, mat_mul_batched, Mds};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn inputs<const T: usize>() -> (Mds<Fr, T>, [Fr; T]) {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mat = [(); T].map(|_| [(); T].map(|_| Fr::random(&mut rng)));
+    let state = [(); T].map(|_| Fr::random(&mut rng));
+    (mat, state)
+}
+
+fn mat_mul_scalar_t8(b: &mut Bencher) {
+    let (mat, mut state) = inputs::<8>();
+    b.iter(|| mat_mul(black_box(&mut state), &mat));
+}
+
+fn mat_mul_batched_t8(b: &mut Bencher) {
+    let (mat, mut state) = inputs::<8>();
+    b.iter(|| mat_mul_batched(black_box(&mut state), &mat));
+}
+
+fn mat_mul_scalar_t16(b: &mut Bencher) {
+    let (mat, mut state) = inputs::<16>();
+    b.iter(|| mat_mul(black_box(&mut state), &mat));
+}
+
+fn mat_mul_batched_t16(b: &mut Bencher) {
+    let (mat, mut state) = inputs::<16>();
+    b.iter(|| mat_mul_batched(black_box(&mut state), &mat));
+}
+
+benchmark_group!(
+    benches,
+    mat_mul_scalar_t8,
+    mat_mul_batched_t8,
+    mat_mul_scalar_t16,
+    mat_mul_batched_t16
+);
+benchmark_main!(benches);
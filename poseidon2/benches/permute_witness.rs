@@ -0,0 +1,84 @@
+//! Measures the witness generation of a `Pow5Chip` permutation under `MockProver`.
+
+#[macro_use]
+extern crate bencher;
+
+use bencher::Bencher;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+use halo2curves::bn256::Fr;
+use poseidon2::base::P128Pow5T3;
+use poseidon2::circuit::poseidon::PoseidonInstructions;
+use poseidon2::circuit::pow5::{Pow5Chip, Pow5Config, StateWord};
+
+/// Permutes `[0, 1, 2]` a number of times.
+struct PermuteCircuit {
+    count: usize,
+}
+
+impl Circuit<Fr> for PermuteCircuit {
+    type Config = Pow5Config<Fr, 3, 2>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        PermuteCircuit { count: self.count }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let state = [0; 3].map(|_| meta.advice_column());
+        let partial_sbox = meta.advice_column();
+        let rc_a = [0; 3].map(|_| meta.fixed_column());
+        let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+        Pow5Chip::configure::<P128Pow5T3<Fr>>(meta, state, partial_sbox, rc_a, pad_fixed)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let mut state = layouter.assign_region(
+            || "initial state",
+            |mut region| {
+                let mut load = |i: usize| {
+                    region
+                        .assign_advice(
+                            || format!("state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(Fr::from(i as u64)),
+                        )
+                        .map(StateWord::from)
+                };
+                Ok([load(0)?, load(1)?, load(2)?])
+            },
+        )?;
+
+        let chip = Pow5Chip::construct(config);
+        for _ in 0..self.count {
+            state = <Pow5Chip<Fr, 3, 2> as PoseidonInstructions<Fr, P128Pow5T3<Fr>, 3, 2>>::permute(
+                &chip,
+                &mut layouter,
+                &state,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn permute_witness_x16(b: &mut Bencher) {
+    let circuit = PermuteCircuit { count: 16 };
+    b.iter(|| MockProver::run(11, &circuit, vec![]).unwrap());
+}
+
+fn permute_witness_x100(b: &mut Bencher) {
+    let circuit = PermuteCircuit { count: 100 };
+    b.iter(|| MockProver::run(13, &circuit, vec![]).unwrap());
+}
+
+benchmark_group!(benches, permute_witness_x16, permute_witness_x100);
+benchmark_main!(benches);
@@ -0,0 +1,22 @@
+//! Compares the S-box multiplication chains against `pow_vartime`.
+
+#[macro_use]
+extern crate bencher;
+
+use bencher::{black_box, Bencher};
+use ff::Field;
+use halo2curves::bn256::Fr;
+use poseidon2::base::primitives::sbox;
+
+fn sbox_chain(b: &mut Bencher) {
+    let val = Fr::from(0x1234_5678);
+    b.iter(|| sbox(black_box(val), 5));
+}
+
+fn sbox_pow_vartime(b: &mut Bencher) {
+    let val = Fr::from(0x1234_5678);
+    b.iter(|| black_box(val).pow_vartime([5]));
+}
+
+benchmark_group!(benches, sbox_chain, sbox_pow_vartime);
+benchmark_main!(benches);
@@ -0,0 +1,273 @@
+//! Compares the two S-box layouts of a `Pow5Chip` permutation, at widths 3 and 8:
+//! the degree-5 gates of `Pow5Chip::configure`, and the intermediate S-box powers of
+//! `Pow5Chip::configure_low_degree`, which keep every gate at degree 3.
+//!
+//! Both assign one round per row, so they take the same number of rows; the low-degree
+//! layout pays `2 * WIDTH` advice columns for its lower degree instead. The rows,
+//! advice columns and degree of each configuration, to read next to the timings:
+//!
+//! | width | layout     | rows per permutation | advice columns | degree |
+//! |-------|------------|----------------------|----------------|--------|
+//! | 3     | degree-5   | 66                   | 4              | 6      |
+//! | 3     | low-degree | 66                   | 10             | 3      |
+//! | 8     | degree-5   | 67                   | 9              | 6      |
+//! | 8     | low-degree | 67                   | 25             | 3      |
+//!
+//! Four permutations fit in `k = 9` at both widths.
+
+#[macro_use]
+extern crate bencher;
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bencher::Bencher;
+use ff::Field;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    halo2curves::bn256::{Bn256, G1Affine},
+    plonk::{create_proof, keygen_pk2, Circuit, ConstraintSystem, Error},
+    poly::kzg::{
+        commitment::{KZGCommitmentScheme, ParamsKZG},
+        multiopen::ProverSHPLONK,
+    },
+    transcript::{Blake2bWrite, Challenge255, TranscriptWriterBuffer},
+};
+use halo2curves::bn256::Fr;
+use lazy_static::lazy_static;
+use poseidon2::base::primitives::{state_from_iter, Mds, Spec};
+use poseidon2::base::P128Pow5T3;
+use poseidon2::circuit::poseidon::PoseidonInstructions;
+use poseidon2::circuit::pow5::{Pow5Chip, Pow5Config, StateWord};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+/// The number of permutations each circuit proves.
+const PERMUTATIONS: usize = 4;
+
+/// A width-8 Poseidon2 spec with 8 full rounds, and arbitrary constants: the benchmarks
+/// only time the layout, so the constants need not be secure. It has the 57 partial
+/// rounds width 8 needs over BN254.
+#[derive(Debug)]
+struct Width8Spec;
+
+lazy_static! {
+    static ref WIDTH8_ROUND_CONSTANTS: Vec<[Fr; 8]> = (0..65u64)
+        .map(|round| std::array::from_fn(|i| Fr::from(round * 8 + i as u64 + 1).square()))
+        .collect();
+    /// The external matrix of Poseidon2 at width 8, `[[2 M4, M4], [M4, 2 M4]]`.
+    static ref WIDTH8_EXTERNAL: Mds<Fr, 8> = {
+        let m4 = [[5u64, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let scale = if i / 4 == j / 4 { 2 } else { 1 };
+                Fr::from(scale * m4[i % 4][j % 4])
+            })
+        })
+    };
+    static ref WIDTH8_INTERNAL: Mds<Fr, 8> = std::array::from_fn(|i| {
+        std::array::from_fn(|j| Fr::from(if i == j { i as u64 + 2 } else { 1 }))
+    });
+}
+
+impl Spec<Fr, 8, 7> for Width8Spec {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        57
+    }
+
+    fn alpha() -> u64 {
+        5
+    }
+
+    fn secure_mds() -> usize {
+        0
+    }
+
+    fn round_constants() -> Cow<'static, [[Fr; 8]]> {
+        Cow::Borrowed(&WIDTH8_ROUND_CONSTANTS[..])
+    }
+
+    fn internal_matrix() -> Cow<'static, Mds<Fr, 8>> {
+        Cow::Borrowed(&WIDTH8_INTERNAL)
+    }
+
+    fn external_matrix() -> Cow<'static, Mds<Fr, 8>> {
+        Cow::Borrowed(&WIDTH8_EXTERNAL)
+    }
+}
+
+/// Permutes `[0, 1, ..]` [`PERMUTATIONS`] times, with the low-degree layout if
+/// `LOW_DEGREE` is set.
+struct PermuteCircuit<S, const WIDTH: usize, const RATE: usize, const LOW_DEGREE: bool>(
+    PhantomData<S>,
+);
+
+impl<S: Spec<Fr, WIDTH, RATE>, const WIDTH: usize, const RATE: usize, const LOW_DEGREE: bool>
+    Circuit<Fr> for PermuteCircuit<S, WIDTH, RATE, LOW_DEGREE>
+{
+    type Config = Pow5Config<Fr, WIDTH, RATE>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        PermuteCircuit(PhantomData)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let state = [(); WIDTH].map(|_| meta.advice_column());
+        let partial_sbox = meta.advice_column();
+        let rc_a = [(); WIDTH].map(|_| meta.fixed_column());
+        let pad_fixed = [(); WIDTH].map(|_| meta.fixed_column());
+
+        if LOW_DEGREE {
+            let squares = [(); WIDTH].map(|_| meta.advice_column());
+            let fourths = [(); WIDTH].map(|_| meta.advice_column());
+            Pow5Chip::configure_low_degree::<S>(
+                meta,
+                state,
+                partial_sbox,
+                rc_a,
+                pad_fixed,
+                squares,
+                fourths,
+            )
+        } else {
+            Pow5Chip::configure::<S>(meta, state, partial_sbox, rc_a, pad_fixed)
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let mut state = layouter.assign_region(
+            || "initial state",
+            |mut region| {
+                let words: Result<Vec<_>, Error> = (0..WIDTH)
+                    .map(|i| {
+                        region
+                            .assign_advice(
+                                || format!("state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(Fr::from(i as u64)),
+                            )
+                            .map(StateWord::from)
+                    })
+                    .collect();
+                Ok(state_from_iter(words?))
+            },
+        )?;
+
+        let chip = Pow5Chip::construct(config);
+        for _ in 0..PERMUTATIONS {
+            state = <Pow5Chip<Fr, WIDTH, RATE> as PoseidonInstructions<
+                Fr,
+                S,
+                WIDTH,
+                RATE,
+            >>::permute(&chip, &mut layouter, &state)?;
+        }
+        Ok(())
+    }
+}
+
+/// The smallest `k` whose `2^k` rows hold the circuit, with room for blinding rows.
+fn circuit_k<S: Spec<Fr, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>() -> u32 {
+    let rows = 1 + PERMUTATIONS * Pow5Chip::<Fr, WIDTH, RATE>::permute_rows::<S>() + 16;
+    rows.next_power_of_two().trailing_zeros()
+}
+
+fn mock_prover<S, const WIDTH: usize, const RATE: usize, const LOW_DEGREE: bool>(b: &mut Bencher)
+where
+    S: Spec<Fr, WIDTH, RATE>,
+{
+    let k = circuit_k::<S, WIDTH, RATE>();
+    let circuit = PermuteCircuit::<S, WIDTH, RATE, LOW_DEGREE>(PhantomData);
+    b.iter(|| {
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    });
+}
+
+fn prove<S, const WIDTH: usize, const RATE: usize, const LOW_DEGREE: bool>(b: &mut Bencher)
+where
+    S: Spec<Fr, WIDTH, RATE>,
+{
+    let k = circuit_k::<S, WIDTH, RATE>();
+    let circuit = PermuteCircuit::<S, WIDTH, RATE, LOW_DEGREE>(PhantomData);
+    let mut rng = XorShiftRng::seed_from_u64(0);
+    let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+    let pk = keygen_pk2(&params, &circuit).expect("keygen_pk shouldn't fail");
+
+    b.iter(|| {
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            PermuteCircuit<S, WIDTH, RATE, LOW_DEGREE>,
+        >(
+            &params,
+            &pk,
+            &[PermuteCircuit(PhantomData)],
+            &[&[]],
+            XorShiftRng::seed_from_u64(1),
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        transcript.finalize()
+    });
+}
+
+fn mock_prover_width3_degree5(b: &mut Bencher) {
+    mock_prover::<P128Pow5T3<Fr>, 3, 2, false>(b);
+}
+
+fn mock_prover_width3_low_degree(b: &mut Bencher) {
+    mock_prover::<P128Pow5T3<Fr>, 3, 2, true>(b);
+}
+
+fn mock_prover_width8_degree5(b: &mut Bencher) {
+    mock_prover::<Width8Spec, 8, 7, false>(b);
+}
+
+fn mock_prover_width8_low_degree(b: &mut Bencher) {
+    mock_prover::<Width8Spec, 8, 7, true>(b);
+}
+
+fn prove_width3_degree5(b: &mut Bencher) {
+    prove::<P128Pow5T3<Fr>, 3, 2, false>(b);
+}
+
+fn prove_width3_low_degree(b: &mut Bencher) {
+    prove::<P128Pow5T3<Fr>, 3, 2, true>(b);
+}
+
+fn prove_width8_degree5(b: &mut Bencher) {
+    prove::<Width8Spec, 8, 7, false>(b);
+}
+
+fn prove_width8_low_degree(b: &mut Bencher) {
+    prove::<Width8Spec, 8, 7, true>(b);
+}
+
+benchmark_group!(
+    benches,
+    mock_prover_width3_degree5,
+    mock_prover_width3_low_degree,
+    mock_prover_width8_degree5,
+    mock_prover_width8_low_degree,
+    prove_width3_degree5,
+    prove_width3_low_degree,
+    prove_width8_degree5,
+    prove_width8_low_degree
+);
+benchmark_main!(benches);
@@ -14,17 +14,24 @@ use halo2curves::{
 use hex::FromHex;
 use lazy_static::lazy_static;
 
+use crate::base::primitives::RoundConstants;
+
 
 pub(crate) type Mat<Fr, const T: usize> = [[Fr; T]; T];
 
-fn from_hex(s: &str) -> Fr 
-{
+/// The little-endian representation of the big-endian hex string `s`.
+pub(super) fn repr_from_hex(s: &str) -> [u8; 32] {
     let bytes = Vec::from_hex(&s[2..]).expect("Invalid Hex String");
     let mut res = [0 as u8; 32];
     for i in 0..bytes.len() {
         res[i] = bytes[31 - i];
     }
-    Fr::from_bytes(&res).unwrap()
+    res
+}
+
+fn from_hex(s: &str) -> Fr 
+{
+    Fr::from_bytes(&repr_from_hex(s)).unwrap()
 }
 lazy_static! {
 
@@ -64,265 +71,1626 @@ lazy_static! {
         ],
     ];
         
-    pub static ref RC3: [[Fr; 3]; 64] = [
-        [from_hex("0x1d066a255517b7fd8bddd3a93f7804ef7f8fcde48bb4c37a59a09a1a97052816"),
-        from_hex("0x29daefb55f6f2dc6ac3f089cebcc6120b7c6fef31367b68eb7238547d32c1610"),
-        from_hex("0x1f2cb1624a78ee001ecbd88ad959d7012572d76f08ec5c4f9e8b7ad7b0b4e1d1"),
+    pub static ref RC3: [[Fr; 3]; 64] = RoundConstants::<Fr, 3>::from_reprs(&[
+        [repr_from_hex("0x1d066a255517b7fd8bddd3a93f7804ef7f8fcde48bb4c37a59a09a1a97052816"),
+        repr_from_hex("0x29daefb55f6f2dc6ac3f089cebcc6120b7c6fef31367b68eb7238547d32c1610"),
+        repr_from_hex("0x1f2cb1624a78ee001ecbd88ad959d7012572d76f08ec5c4f9e8b7ad7b0b4e1d1"),
+        ],
+        [repr_from_hex("0x0aad2e79f15735f2bd77c0ed3d14aa27b11f092a53bbc6e1db0672ded84f31e5"),
+        repr_from_hex("0x2252624f8617738cd6f661dd4094375f37028a98f1dece66091ccf1595b43f28"),
+        repr_from_hex("0x1a24913a928b38485a65a84a291da1ff91c20626524b2b87d49f4f2c9018d735"),
+        ],
+        [repr_from_hex("0x22fc468f1759b74d7bfc427b5f11ebb10a41515ddff497b14fd6dae1508fc47a"),
+        repr_from_hex("0x1059ca787f1f89ed9cd026e9c9ca107ae61956ff0b4121d5efd65515617f6e4d"),
+        repr_from_hex("0x02be9473358461d8f61f3536d877de982123011f0bf6f155a45cbbfae8b981ce"),
+        ],
+        [repr_from_hex("0x0ec96c8e32962d462778a749c82ed623aba9b669ac5b8736a1ff3a441a5084a4"),
+        repr_from_hex("0x292f906e073677405442d9553c45fa3f5a47a7cdb8c99f9648fb2e4d814df57e"),
+        repr_from_hex("0x274982444157b86726c11b9a0f5e39a5cc611160a394ea460c63f0b2ffe5657e"),
+        ],
+        [repr_from_hex("0x1a1d063e54b1e764b63e1855bff015b8cedd192f47308731499573f23597d4b5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x26abc66f3fdf8e68839d10956259063708235dccc1aa3793b91b002c5b257c37"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0c7c64a9d887385381a578cfed5aed370754427aabca92a70b3c2b12ff4d7be8"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1cf5998769e9fab79e17f0b6d08b2d1eba2ebac30dc386b0edd383831354b495"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0f5e3a8566be31b7564ca60461e9e08b19828764a9669bc17aba0b97e66b0109"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x18df6a9d19ea90d895e60e4db0794a01f359a53a180b7d4b42bf3d7a531c976e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x04f7bf2c5c0538ac6e4b782c3c6e601ad0ea1d3a3b9d25ef4e324055fa3123dc"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x29c76ce22255206e3c40058523748531e770c0584aa2328ce55d54628b89ebe6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x198d425a45b78e85c053659ab4347f5d65b1b8e9c6108dbe00e0e945dbc5ff15"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x25ee27ab6296cd5e6af3cc79c598a1daa7ff7f6878b3c49d49d3a9a90c3fdf74"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x138ea8e0af41a1e024561001c0b6eb1505845d7d0c55b1b2c0f88687a96d1381"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x306197fb3fab671ef6e7c2cba2eefd0e42851b5b9811f2ca4013370a01d95687"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1a0c7d52dc32a4432b66f0b4894d4f1a21db7565e5b4250486419eaf00e8f620"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2b46b418de80915f3ff86a8e5c8bdfccebfbe5f55163cd6caa52997da2c54a9f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x12d3e0dc0085873701f8b777b9673af9613a1af5db48e05bfb46e312b5829f64"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x263390cf74dc3a8870f5002ed21d089ffb2bf768230f648dba338a5cb19b3a1f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0a14f33a5fe668a60ac884b4ca607ad0f8abb5af40f96f1d7d543db52b003dcd"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x28ead9c586513eab1a5e86509d68b2da27be3a4f01171a1dd847df829bc683b9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c6ab1c328c3c6430972031f1bdb2ac9888f0ea1abe71cffea16cda6e1a7416c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1fc7e71bc0b819792b2500239f7f8de04f6decd608cb98a932346015c5b42c94"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x03e107eb3a42b2ece380e0d860298f17c0c1e197c952650ee6dd85b93a0ddaa8"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2d354a251f381a4669c0d52bf88b772c46452ca57c08697f454505f6941d78cd"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x094af88ab05d94baf687ef14bc566d1c522551d61606eda3d14b4606826f794b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x19705b783bf3d2dc19bcaeabf02f8ca5e1ab5b6f2e3195a9d52b2d249d1396f7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x09bf4acc3a8bce3f1fcc33fee54fc5b28723b16b7d740a3e60cef6852271200e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1803f8200db6013c50f83c0c8fab62843413732f301f7058543a073f3f3b5e4e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0f80afb5046244de30595b160b8d1f38bf6fb02d4454c0add41f7fef2faf3e5c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x126ee1f8504f15c3d77f0088c1cfc964abcfcf643f4a6fea7dc3f98219529d78"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x23c203d10cfcc60f69bfb3d919552ca10ffb4ee63175ddf8ef86f991d7d0a591"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2a2ae15d8b143709ec0d09705fa3a6303dec1ee4eec2cf747c5a339f7744fb94"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x07b60dee586ed6ef47e5c381ab6343ecc3d3b3006cb461bbb6b5d89081970b2b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x27316b559be3edfd885d95c494c1ae3d8a98a320baa7d152132cfe583c9311bd"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1d5c49ba157c32b8d8937cb2d3f84311ef834cc2a743ed662f5f9af0c0342e76"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2f8b124e78163b2f332774e0b850b5ec09c01bf6979938f67c24bd5940968488"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1e6843a5457416b6dc5b7aa09a9ce21b1d4cba6554e51d84665f75260113b3d5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x11cdf00a35f650c55fca25c9929c8ad9a68daf9ac6a189ab1f5bc79f21641d4b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x21632de3d3bbc5e42ef36e588158d6d4608b2815c77355b7e82b5b9b7eb560bc"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0de625758452efbd97b27025fbd245e0255ae48ef2a329e449d7b5c51c18498a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2ad253c053e75213e2febfd4d976cc01dd9e1e1c6f0fb6b09b09546ba0838098"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1d6b169ed63872dc6ec7681ec39b3be93dd49cdd13c813b7d35702e38d60b077"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1660b740a143664bb9127c4941b67fed0be3ea70a24d5568c3a54e706cfef7fe"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0065a92d1de81f34114f4ca2deef76e0ceacdddb12cf879096a29f10376ccbfe"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1f11f065202535987367f823da7d672c353ebe2ccbc4869bcf30d50a5871040d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x26596f5c5dd5a5d1b437ce7b14a2c3dd3bd1d1a39b6759ba110852d17df0693e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x16f49bc727e45a2f7bf3056efcf8b6d38539c4163a5f1e706743db15af91860f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1abe1deb45b3e3119954175efb331bf4568feaf7ea8b3dc5e1a4e7438dd39e5f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0e426ccab66984d1d8993a74ca548b779f5db92aaec5f102020d34aea15fba59"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0e7c30c2e2e8957f4933bd1942053f1f0071684b902d534fa841924303f6a6c6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0812a017ca92cf0a1622708fc7edff1d6166ded6e3528ead4c76e1f31d3fc69d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x21a5ade3df2bc1b5bba949d1db96040068afe5026edd7a9c2e276b47cf010d54"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x01f3035463816c84ad711bf1a058c6c6bd101945f50e5afe72b1a5233f8749ce"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0b115572f038c0e2028c2aafc2d06a5e8bf2f9398dbd0fdf4dcaa82b0f0c1c8b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c38ec0b99b62fd4f0ef255543f50d2e27fc24db42bc910a3460613b6ef59e2f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c89c6d9666272e8425c3ff1f4ac737b2f5d314606a297d4b1d0b254d880c53e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x03326e643580356bf6d44008ae4c042a21ad4880097a5eb38b71e2311bb88f8f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x268076b0054fb73f67cee9ea0e51e3ad50f27a6434b5dceb5bdde2299910a4c9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1acd63c67fbc9ab1626ed93491bda32e5da18ea9d8e4f10178d04aa6f8747ad0"),
+        repr_from_hex("0x19f8a5d670e8ab66c4e3144be58ef6901bf93375e2323ec3ca8c86cd2a28b5a5"),
+        repr_from_hex("0x1c0dc443519ad7a86efa40d2df10a011068193ea51f6c92ae1cfbb5f7b9b6893"),
+        ],
+        [repr_from_hex("0x14b39e7aa4068dbe50fe7190e421dc19fbeab33cb4f6a2c4180e4c3224987d3d"),
+        repr_from_hex("0x1d449b71bd826ec58f28c63ea6c561b7b820fc519f01f021afb1e35e28b0795e"),
+        repr_from_hex("0x1ea2c9a89baaddbb60fa97fe60fe9d8e89de141689d1252276524dc0a9e987fc"),
+        ],
+        [repr_from_hex("0x0478d66d43535a8cb57e9c1c3d6a2bd7591f9a46a0e9c058134d5cefdb3c7ff1"),
+        repr_from_hex("0x19272db71eece6a6f608f3b2717f9cd2662e26ad86c400b21cde5e4a7b00bebe"),
+        repr_from_hex("0x14226537335cab33c749c746f09208abb2dd1bd66a87ef75039be846af134166"),
+        ],
+        [repr_from_hex("0x01fd6af15956294f9dfe38c0d976a088b21c21e4a1c2e823f912f44961f9a9ce"),
+        repr_from_hex("0x18e5abedd626ec307bca190b8b2cab1aaee2e62ed229ba5a5ad8518d4e5f2a57"),
+        repr_from_hex("0x0fc1bbceba0590f5abbdffa6d3b35e3297c021a3a409926d0e2d54dc1c84fda6"),
+        ],
+    ])
+    .expect("the round constants are canonical")
+    .into_rounds()
+    .try_into()
+    .unwrap();
+
+    // Widths 2, 4 and 8, generated with the Grain LFSR of the reference Poseidon2 scripts
+    // (https://github.com/HorizenLabs/poseidon2/blob/main/poseidon2_rust_params.sage),
+    // which also reproduces RC3 above.
+    pub static ref MAT_DIAG2_M_1: [Fr; 2] = [
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+    ];
+
+    pub static ref MAT_INTERNAL2: Mat<Fr, 2> = [
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        ],
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+        ],
+    ];
+
+    pub static ref MAT_EXTERNAL2: Mat<Fr, 2> = [
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        ],
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        ],
+    ];
+
+    pub static ref RC2: [[Fr; 2]; 64] = RoundConstants::<Fr, 2>::from_reprs(&[
+        [repr_from_hex("0x09c46e9ec68e9bd4fe1faaba294cba38a71aa177534cdd1b6c7dc0dbd0abd7a7"),
+        repr_from_hex("0x0c0356530896eec42a97ed937f3135cfc5142b3ae405b8343c1d83ffa604cb81"),
+        ],
+        [repr_from_hex("0x1e28a1d935698ad1142e51182bb54cf4a00ea5aabd6268bd317ea977cc154a30"),
+        repr_from_hex("0x27af2d831a9d2748080965db30e298e40e5757c3e008db964cf9e2b12b91251f"),
+        ],
+        [repr_from_hex("0x1e6f11ce60fc8f513a6a3cfe16ae175a41291462f214cd0879aaf43545b74e03"),
+        repr_from_hex("0x2a67384d3bbd5e438541819cb681f0be04462ed14c3613d8f719206268d142d3"),
+        ],
+        [repr_from_hex("0x0b66fdf356093a611609f8e12fbfecf0b985e381f025188936408f5d5c9f45d0"),
+        repr_from_hex("0x012ee3ec1e78d470830c61093c2ade370b26c83cc5cebeeddaa6852dbdb09e21"),
+        ],
+        [repr_from_hex("0x0252ba5f6760bfbdfd88f67f8175e3fd6cd1c431b099b6bb2d108e7b445bb1b9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x179474cceca5ff676c6bec3cef54296354391a8935ff71d6ef5aeaad7ca932f1"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2c24261379a51bfa9228ff4a503fd4ed9c1f974a264969b37e1a2589bbed2b91"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1cc1d7b62692e63eac2f288bd0695b43c2f63f5001fc0fc553e66c0551801b05"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x255059301aada98bb2ed55f852979e9600784dbf17fbacd05d9eff5fd9c91b56"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x28437be3ac1cb2e479e1f5c0eccd32b3aea24234970a8193b11c29ce7e59efd9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x28216a442f2e1f711ca4fa6b53766eb118548da8fb4f78d4338762c37f5f2043"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2c1f47cd17fa5adf1f39f4e7056dd03feee1efce03094581131f2377323482c9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x07abad02b7a5ebc48632bcc9356ceb7dd9dafca276638a63646b8566a621afc9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0230264601ffdf29275b33ffaab51dfe9429f90880a69cd137da0c4d15f96c3c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1bc973054e51d905a0f168656497ca40a864414557ee289e717e5d66899aa0a9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2e1c22f964435008206c3157e86341edd249aff5c2d8421f2a6b22288f0a67fc"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1224f38df67c5378121c1d5f461bbc509e8ea1598e46c9f7a70452bc2bba86b8"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x02e4e69d8ba59e519280b4bd9ed0068fd7bfe8cd9dfeda1969d2989186cde20e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1f1eccc34aaba0137f5df81fc04ff3ee4f19ee364e653f076d47e9735d98018e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1672ad3d709a353974266c3039a9a7311424448032cd1819eacb8a4d4284f582"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x283e3fdc2c6e420c56f44af5192b4ae9cda6961f284d24991d2ed602df8c8fc7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c2a3d120c550ecfd0db0957170fa013683751f8fdff59d6614fbd69ff394bcc"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x216f84877aac6172f7897a7323456efe143a9a43773ea6f296cb6b8177653fbd"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2c0d272becf2a75764ba7e8e3e28d12bceaa47ea61ca59a411a1f51552f94788"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0aad2e79f15735f2bd77c0ed3d14aa27b11f092a53bbc6e1db0672ded84f31e5"),
-        from_hex("0x2252624f8617738cd6f661dd4094375f37028a98f1dece66091ccf1595b43f28"),
-        from_hex("0x1a24913a928b38485a65a84a291da1ff91c20626524b2b87d49f4f2c9018d735"),
+        [repr_from_hex("0x16e34299865c0e28484ee7a74c454e9f170a5480abe0508fcb4a6c3d89546f43"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x22fc468f1759b74d7bfc427b5f11ebb10a41515ddff497b14fd6dae1508fc47a"),
-        from_hex("0x1059ca787f1f89ed9cd026e9c9ca107ae61956ff0b4121d5efd65515617f6e4d"),
-        from_hex("0x02be9473358461d8f61f3536d877de982123011f0bf6f155a45cbbfae8b981ce"),
+        [repr_from_hex("0x175ceba599e96f5b375a232a6fb9cc71772047765802290f48cd939755488fc5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0ec96c8e32962d462778a749c82ed623aba9b669ac5b8736a1ff3a441a5084a4"),
-        from_hex("0x292f906e073677405442d9553c45fa3f5a47a7cdb8c99f9648fb2e4d814df57e"),
-        from_hex("0x274982444157b86726c11b9a0f5e39a5cc611160a394ea460c63f0b2ffe5657e"),
+        [repr_from_hex("0x0c7594440dc48c16fead9e1758b028066aa410bfbc354f54d8c5ffbb44a1ee32"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1a1d063e54b1e764b63e1855bff015b8cedd192f47308731499573f23597d4b5"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1a3c29bc39f21bb5c466db7d7eb6fd8f760e20013ccf912c92479882d919fd8d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x26abc66f3fdf8e68839d10956259063708235dccc1aa3793b91b002c5b257c37"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0ccfdd906f3426e5c0986ea049b253400855d349074f5a6695c8eeabcd22e68f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0c7c64a9d887385381a578cfed5aed370754427aabca92a70b3c2b12ff4d7be8"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x14f6bc81d9f186f62bdb475ce6c9411866a7a8a3fd065b3ce0e699b67dd9e796"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1cf5998769e9fab79e17f0b6d08b2d1eba2ebac30dc386b0edd383831354b495"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0962b82789fb3d129702ca70b2f6c5aacc099810c9c495c888edeb7386b97052"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0f5e3a8566be31b7564ca60461e9e08b19828764a9669bc17aba0b97e66b0109"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1a880af7074d18b3bf20c79de25127bc13284ab01ef02575afef0c8f6a31a86d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x18df6a9d19ea90d895e60e4db0794a01f359a53a180b7d4b42bf3d7a531c976e"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x10cba18419a6a332cd5e77f0211c154b20af2924fc20ff3f4c3012bb7ae9311b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x04f7bf2c5c0538ac6e4b782c3c6e601ad0ea1d3a3b9d25ef4e324055fa3123dc"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x057e62a9a8f89b3ebdc76ba63a9eaca8fa27b7319cae3406756a2849f302f10d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x29c76ce22255206e3c40058523748531e770c0584aa2328ce55d54628b89ebe6"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x287c971de91dc0abd44adf5384b4988cb961303bbf65cff5afa0413b44280cee"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x198d425a45b78e85c053659ab4347f5d65b1b8e9c6108dbe00e0e945dbc5ff15"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x21df3388af1687bbb3bca9da0cca908f1e562bc46d4aba4e6f7f7960e306891d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x25ee27ab6296cd5e6af3cc79c598a1daa7ff7f6878b3c49d49d3a9a90c3fdf74"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1be5c887d25bce703e25cc974d0934cd789df8f70b498fd83eff8b560e1682b3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x138ea8e0af41a1e024561001c0b6eb1505845d7d0c55b1b2c0f88687a96d1381"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x268da36f76e568fb68117175cea2cd0dd2cb5d42fda5acea48d59c2706a0d5c1"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x306197fb3fab671ef6e7c2cba2eefd0e42851b5b9811f2ca4013370a01d95687"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0e17ab091f6eae50c609beaf5510ececc5d8bb74135ebd05bd06460cc26a5ed6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1a0c7d52dc32a4432b66f0b4894d4f1a21db7565e5b4250486419eaf00e8f620"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x04d727e728ffa0a67aee535ab074a43091ef62d8cf83d270040f5caa1f62af40"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x2b46b418de80915f3ff86a8e5c8bdfccebfbe5f55163cd6caa52997da2c54a9f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0ddbd7bf9c29341581b549762bc022ed33702ac10f1bfd862b15417d7e39ca6e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x12d3e0dc0085873701f8b777b9673af9613a1af5db48e05bfb46e312b5829f64"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x2790eb3351621752768162e82989c6c234f5b0d1d3af9b588a29c49c8789654b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x263390cf74dc3a8870f5002ed21d089ffb2bf768230f648dba338a5cb19b3a1f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1e457c601a63b73e4471950193d8a570395f3d9ab8b2fd0984b764206142f9e9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0a14f33a5fe668a60ac884b4ca607ad0f8abb5af40f96f1d7d543db52b003dcd"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x21ae64301dca9625638d6ab2bbe7135ffa90ecd0c43ff91fc4c686fc46e091b0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x28ead9c586513eab1a5e86509d68b2da27be3a4f01171a1dd847df829bc683b9"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0379f63c8ce3468d4da293166f494928854be9e3432e09555858534eed8d350b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1c6ab1c328c3c6430972031f1bdb2ac9888f0ea1abe71cffea16cda6e1a7416c"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x002d56420359d0266a744a080809e054ca0e4921a46686ac8c9f58a324c35049"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1fc7e71bc0b819792b2500239f7f8de04f6decd608cb98a932346015c5b42c94"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x123158e5965b5d9b1d68b3cd32e10bbeda8d62459e21f4090fc2c5af963515a6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x03e107eb3a42b2ece380e0d860298f17c0c1e197c952650ee6dd85b93a0ddaa8"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0be29fc40847a941661d14bbf6cbe0420fbb2b6f52836d4e60c80eb49cad9ec1"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x2d354a251f381a4669c0d52bf88b772c46452ca57c08697f454505f6941d78cd"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1ac96991dec2bb0557716142015a453c36db9d859cad5f9a233802f24fdf4c1a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x094af88ab05d94baf687ef14bc566d1c522551d61606eda3d14b4606826f794b"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1596443f763dbcc25f4964fc61d23b3e5e12c9fa97f18a9251ca3355bcb0627e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x19705b783bf3d2dc19bcaeabf02f8ca5e1ab5b6f2e3195a9d52b2d249d1396f7"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x12e0bcd3654bdfa76b2861d4ec3aeae0f1857d9f17e715aed6d049eae3ba3212"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x09bf4acc3a8bce3f1fcc33fee54fc5b28723b16b7d740a3e60cef6852271200e"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0fc92b4f1bbea82b9ea73d4af9af2a50ceabac7f37154b1904e6c76c7cf964ba"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1803f8200db6013c50f83c0c8fab62843413732f301f7058543a073f3f3b5e4e"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1f9c0b1610446442d6f2e592a8013f40b14f7c7722236f4f9c7e965233872762"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x0f80afb5046244de30595b160b8d1f38bf6fb02d4454c0add41f7fef2faf3e5c"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0ebd74244ae72675f8cde06157a782f4050d914da38b4c058d159f643dbbf4d3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x126ee1f8504f15c3d77f0088c1cfc964abcfcf643f4a6fea7dc3f98219529d78"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x2cb7f0ed39e16e9f69a9fafd4ab951c03b0671e97346ee397a839839dccfc6d1"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x23c203d10cfcc60f69bfb3d919552ca10ffb4ee63175ddf8ef86f991d7d0a591"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1a9d6e2ecff022cc5605443ee41bab20ce761d0514ce526690c72bca7352d9bf"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x2a2ae15d8b143709ec0d09705fa3a6303dec1ee4eec2cf747c5a339f7744fb94"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x2a115439607f335a5ea83c3bc44a9331d0c13326a9a7ba3087da182d648ec72f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x07b60dee586ed6ef47e5c381ab6343ecc3d3b3006cb461bbb6b5d89081970b2b"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x23f9b6529b5d040d15b8fa7aee3e3410e738b56305cd44f29535c115c5a4c060"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x27316b559be3edfd885d95c494c1ae3d8a98a320baa7d152132cfe583c9311bd"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x05872c16db0f72a2249ac6ba484bb9c3a3ce97c16d58b68b260eb939f0e6e8a7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x1d5c49ba157c32b8d8937cb2d3f84311ef834cc2a743ed662f5f9af0c0342e76"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x1300bdee08bb7824ca20fb80118075f40219b6151d55b5c52b624a7cdeddf6a7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
         ],
-        [from_hex("0x2f8b124e78163b2f332774e0b850b5ec09c01bf6979938f67c24bd5940968488"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x19b9b63d2f108e17e63817863a8f6c288d7ad29916d98cb1072e4e7b7d52b376"),
+        repr_from_hex("0x015bee1357e3c015b5bda237668522f613d1c88726b5ec4224a20128481b4f7f"),
         ],
-        [from_hex("0x1e6843a5457416b6dc5b7aa09a9ce21b1d4cba6554e51d84665f75260113b3d5"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x2953736e94bb6b9f1b9707a4f1615e4efe1e1ce4bab218cbea92c785b128ffd1"),
+        repr_from_hex("0x0b069353ba091618862f806180c0385f851b98d372b45f544ce7266ed6608dfc"),
         ],
-        [from_hex("0x11cdf00a35f650c55fca25c9929c8ad9a68daf9ac6a189ab1f5bc79f21641d4b"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x304f74d461ccc13115e4e0bcfb93817e55aeb7eb9306b64e4f588ac97d81f429"),
+        repr_from_hex("0x15bbf146ce9bca09e8a33f5e77dfe4f5aad2a164a4617a4cb8ee5415cde913fc"),
         ],
-        [from_hex("0x21632de3d3bbc5e42ef36e588158d6d4608b2815c77355b7e82b5b9b7eb560bc"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [repr_from_hex("0x0ab4dfe0c2742cde44901031487964ed9b8f4b850405c10ca9ff23859572c8c6"),
+        repr_from_hex("0x0e32db320a044e3197f45f7649a19675ef5eedfea546dea9251de39f9639779a"),
         ],
-        [from_hex("0x0de625758452efbd97b27025fbd245e0255ae48ef2a329e449d7b5c51c18498a"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+    ])
+    .expect("the round constants are canonical")
+    .into_rounds()
+    .try_into()
+    .unwrap();
+
+    pub static ref MAT_DIAG4_M_1: [Fr; 4] = [
+        from_hex("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7"),
+        from_hex("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b"),
+        from_hex("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15"),
+        from_hex("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b"),
+    ];
+
+    pub static ref MAT_INTERNAL4: Mat<Fr, 4> = [
+        [from_hex("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e8"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x2ad253c053e75213e2febfd4d976cc01dd9e1e1c6f0fb6b09b09546ba0838098"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740c"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x1d6b169ed63872dc6ec7681ec39b3be93dd49cdd13c813b7d35702e38d60b077"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac16"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x1660b740a143664bb9127c4941b67fed0be3ea70a24d5568c3a54e706cfef7fe"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428c"),
         ],
-        [from_hex("0x0065a92d1de81f34114f4ca2deef76e0ceacdddb12cf879096a29f10376ccbfe"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+    ];
+
+    pub static ref MAT_EXTERNAL4: Mat<Fr, 4> = [
+        [from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
         ],
-        [from_hex("0x1f11f065202535987367f823da7d672c353ebe2ccbc4869bcf30d50a5871040d"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
         ],
-        [from_hex("0x26596f5c5dd5a5d1b437ce7b14a2c3dd3bd1d1a39b6759ba110852d17df0693e"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
         ],
-        [from_hex("0x16f49bc727e45a2f7bf3056efcf8b6d38539c4163a5f1e706743db15af91860f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
         ],
-        [from_hex("0x1abe1deb45b3e3119954175efb331bf4568feaf7ea8b3dc5e1a4e7438dd39e5f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+    ];
+
+    pub static ref RC4: [[Fr; 4]; 64] = RoundConstants::<Fr, 4>::from_reprs(&[
+        [repr_from_hex("0x19b849f69450b06848da1d39bd5e4a4302bb86744edc26238b0878e269ed23e5"),
+        repr_from_hex("0x265ddfe127dd51bd7239347b758f0a1320eb2cc7450acc1dad47f80c8dcf34d6"),
+        repr_from_hex("0x199750ec472f1809e0f66a545e1e51624108ac845015c2aa3dfc36bab497d8aa"),
+        repr_from_hex("0x157ff3fe65ac7208110f06a5f74302b14d743ea25067f0ffd032f787c7f1cdf8"),
+        ],
+        [repr_from_hex("0x2e49c43c4569dd9c5fd35ac45fca33f10b15c590692f8beefe18f4896ac94902"),
+        repr_from_hex("0x0e35fb89981890520d4aef2b6d6506c3cb2f0b6973c24fa82731345ffa2d1f1e"),
+        repr_from_hex("0x251ad47cb15c4f1105f109ae5e944f1ba9d9e7806d667ffec6fe723002e0b996"),
+        repr_from_hex("0x13da07dc64d428369873e97160234641f8beb56fdd05e5f3563fa39d9c22df4e"),
+        ],
+        [repr_from_hex("0x0c009b84e650e6d23dc00c7dccef7483a553939689d350cd46e7b89055fd4738"),
+        repr_from_hex("0x011f16b1c63a854f01992e3956f42d8b04eb650c6d535eb0203dec74befdca06"),
+        repr_from_hex("0x0ed69e5e383a688f209d9a561daa79612f3f78d0467ad45485df07093f367549"),
+        repr_from_hex("0x04dba94a7b0ce9e221acad41472b6bbe3aec507f5eb3d33f463672264c9f789b"),
+        ],
+        [repr_from_hex("0x0a3f2637d840f3a16eb094271c9d237b6036757d4bb50bf7ce732ff1d4fa28e8"),
+        repr_from_hex("0x259a666f129eea198f8a1c502fdb38fa39b1f075569564b6e54a485d1182323f"),
+        repr_from_hex("0x28bf7459c9b2f4c6d8e7d06a4ee3a47f7745d4271038e5157a32fdf7ede0d6a1"),
+        repr_from_hex("0x0a1ca941f057037526ea200f489be8d4c37c85bbcce6a2aeec91bd6941432447"),
+        ],
+        [repr_from_hex("0x0c6f8f958be0e93053d7fd4fc54512855535ed1539f051dcb43a26fd926361cf"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x123106a93cd17578d426e8128ac9d90aa9e8a00708e296e084dd57e69caaf811"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x26e1ba52ad9285d97dd3ab52f8e840085e8fa83ff1e8f1877b074867cd2dee75"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1cb55cad7bd133de18a64c5c47b9c97cbe4d8b7bf9e095864471537e6a4ae2c5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1dcd73e46acd8f8e0e2c7ce04bde7f6d2a53043d5060a41c7143f08e6e9055d0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x011003e32f6d9c66f5852f05474a4def0cda294a0eb4e9b9b12b9bb4512e5574"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2b1e809ac1d10ab29ad5f20d03a57dfebadfe5903f58bafed7c508dd2287ae8c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2539de1785b735999fb4dac35ee17ed0ef995d05ab2fc5faeaa69ae87bcec0a5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0c246c5a2ef8ee0126497f222b3e0a0ef4e1c3d41c86d46e43982cb11d77951d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x192089c4974f68e95408148f7c0632edbb09e6a6ad1a1c2f3f0305f5d03b527b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1eae0ad8ab68b2f06a0ee36eeb0d0c058529097d91096b756d8fdc2fb5a60d85"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x179190e5d0e22179e46f8282872abc88db6e2fdc0dee99e69768bd98c5d06bfb"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x29bb9e2c9076732576e9a81c7ac4b83214528f7db00f31bf6cafe794a9b3cd1c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x225d394e42207599403efd0c2464a90d52652645882aac35b10e590e6e691e08"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x064760623c25c8cf753d238055b444532be13557451c087de09efd454b23fd59"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x10ba3a0e01df92e87f301c4b716d8a394d67f4bf42a75c10922910a78f6b5b87"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0e070bf53f8451b24f9c6e96b0c2a801cb511bc0c242eb9d361b77693f21471c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1b94cd61b051b04dd39755ff93821a73ccd6cb11d2491d8aa7f921014de252fb"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1d7cb39bafb8c744e148787a2e70230f9d4e917d5713bb050487b5aa7d74070b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2ec93189bd1ab4f69117d0fe980c80ff8785c2961829f701bb74ac1f303b17db"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2db366bfdd36d277a692bb825b86275beac404a19ae07a9082ea46bd83517926"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x062100eb485db06269655cf186a68532985275428450359adc99cec6960711b8"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0761d33c66614aaa570e7f1e8244ca1120243f92fa59e4f900c567bf41f5a59b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x20fc411a114d13992c2705aa034e3f315d78608a0f7de4ccf7a72e494855ad0d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x25b5c004a4bdfcb5add9ec4e9ab219ba102c67e8b3effb5fc3a30f317250bc5a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x23b1822d278ed632a494e58f6df6f5ed038b186d8474155ad87e7dff62b37f4b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x22734b4c5c3f9493606c4ba9012499bf0f14d13bfcfcccaa16102a29cc2f69e0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x26c0c8fe09eb30b7e27a74dc33492347e5bdff409aa3610254413d3fad795ce5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x070dd0ccb6bd7bbae88eac03fa1fbb26196be3083a809829bbd626df348ccad9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x12b6595bdb329b6fb043ba78bb28c3bec2c0a6de46d8c5ad6067c4ebfd4250da"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x248d97d7f76283d63bec30e7a5876c11c06fca9b275c671c5e33d95bb7e8d729"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1a306d439d463b0816fc6fd64cc939318b45eb759ddde4aa106d15d9bd9baaaa"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x28a8f8372e3c38daced7c00421cb4621f4f1b54ddc27821b0d62d3d6ec7c56cf"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0094975717f9a8a8bb35152f24d43294071ce320c829f388bc852183e1e2ce7e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x04d5ee4c3aa78f7d80fde60d716480d3593f74d4f653ae83f4103246db2e8d65"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2a6cf5e9aa03d4336349ad6fb8ed2269c7bef54b8822cc76d08495c12efde187"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2304d31eaab960ba9274da43e19ddeb7f792180808fd6e43baae48d7efcba3f3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x03fd9ac865a4b2a6d5e7009785817249bff08a7e0726fcb4e1c11d39d199f0b0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x00b7258ded52bbda2248404d55ee5044798afc3a209193073f7954d4d63b0b64"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x159f81ada0771799ec38fca2d4bf65ebb13d3a74f3298db36272c5ca65e92d9a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1ef90e67437fbc8550237a75bc28e3bb9000130ea25f0c5471e144cf4264431f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1e65f838515e5ff0196b49aa41a2d2568df739bc176b08ec95a79ed82932e30d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2b1b045def3a166cec6ce768d079ba74b18c844e570e1f826575c1068c94c33f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0832e5753ceb0ff6402543b1109229c165dc2d73bef715e3f1c6e07c168bb173"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x02f614e9cedfb3dc6b762ae0a37d41bab1b841c2e8b6451bc5a8e3c390b6ad16"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0e2427d38bd46a60dd640b8e362cad967370ebb777bedff40f6a0be27e7ed705"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0493630b7c670b6deb7c84d414e7ce79049f0ec098c3c7c50768bbe29214a53a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x22ead100e8e482674decdab17066c5a26bb1515355d5461a3dc06cc85327cea9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x25b3e56e655b42cdaae2626ed2554d48583f1ae35626d04de5084e0b6d2a6f16"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1e32752ada8836ef5837a6cde8ff13dbb599c336349e4c584b4fdc0a0cf6f9d0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2fa2a871c15a387cc50f68f6f3c3455b23c00995f05078f672a9864074d412e5"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2f569b8a9a4424c9278e1db7311e889f54ccbf10661bab7fcd18e7c7a7d83505"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x044cb455110a8fdd531ade530234c518a7df93f7332ffd2144165374b246b43d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x227808de93906d5d420246157f2e42b191fe8c90adfe118178ddc723a5319025"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x02fcca2934e046bc623adead873579865d03781ae090ad4a8579d2e7a6800355"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0ef915f0ac120b876abccceb344a1d36bad3f3c5ab91a8ddcbec2e060d8befac"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1797130f4b7a3e1777eb757bc6f287f6ab0fb85f6be63b09f3b16ef2b1405d38"),
+        repr_from_hex("0x0a76225dc04170ae3306c85abab59e608c7f497c20156d4d36c668555decc6e5"),
+        repr_from_hex("0x1fffb9ec1992d66ba1e77a7b93209af6f8fa76d48acb664796174b5326a31a5c"),
+        repr_from_hex("0x25721c4fc15a3f2853b57c338fa538d85f8fbba6c6b9c6090611889b797b9c5f"),
+        ],
+        [repr_from_hex("0x0c817fd42d5f7a41215e3d07ba197216adb4c3790705da95eb63b982bfcaf75a"),
+        repr_from_hex("0x13abe3f5239915d39f7e13c2c24970b6df8cf86ce00a22002bc15866e52b5a96"),
+        repr_from_hex("0x2106feea546224ea12ef7f39987a46c85c1bc3dc29bdbd7a92cd60acb4d391ce"),
+        repr_from_hex("0x21ca859468a746b6aaa79474a37dab49f1ca5a28c748bc7157e1b3345bb0f959"),
+        ],
+        [repr_from_hex("0x05ccd6255c1e6f0c5cf1f0df934194c62911d14d0321662a8f1a48999e34185b"),
+        repr_from_hex("0x0f0e34a64b70a626e464d846674c4c8816c4fb267fe44fe6ea28678cb09490a4"),
+        repr_from_hex("0x0558531a4e25470c6157794ca36d0e9647dbfcfe350d64838f5b1a8a2de0d4bf"),
+        repr_from_hex("0x09d3dca9173ed2faceea125157683d18924cadad3f655a60b72f5864961f1455"),
+        ],
+        [repr_from_hex("0x0328cbd54e8c0913493f866ed03d218bf23f92d68aaec48617d4c722e5bd4335"),
+        repr_from_hex("0x2bf07216e2aff0a223a487b1a7094e07e79e7bcc9798c648ee3347dd5329d34b"),
+        repr_from_hex("0x1daf345a58006b736499c583cb76c316d6f78ed6a6dffc82111e11a63fe412df"),
+        repr_from_hex("0x176563472456aaa746b694c60e1823611ef39039b2edc7ff391e6f2293d2c404"),
+        ],
+    ])
+    .expect("the round constants are canonical")
+    .into_rounds()
+    .try_into()
+    .unwrap();
+
+    pub static ref MAT_DIAG8_M_1: [Fr; 8] = [
+        from_hex("0x05bffb5e301d8c468c35e24eb2165b6b71725fb7ac9a48efe5ce041bdb05676d"),
+        from_hex("0x2aa7a81812688343fc6d78073312996d75f4c5505db0ed22af5ec0df7888cdc7"),
+        from_hex("0x2f5856fd71dab60d78cc3af15a89c1e4d61ba189849a4cea10acc1dd228faf00"),
+        from_hex("0x12299a260999ac95d271e184968cda40bd4358877a6dcf43d779251fffa61348"),
+        from_hex("0x1443aad4693d692a62a8e21f03d5643a123f0c8783a3d27c275f9d01089685fb"),
+        from_hex("0x21561b0204a44488082e31472f5885a3adc179bb278233aedc4b316369ec9937"),
+        from_hex("0x0c7cc2afa53f9898f30a69b294a4e24f6b2176e1ae0ca49b021792d55e34e97d"),
+        from_hex("0x2dd221096053de389fae88e7caa5c43ab55e22aeb758ee130d1246c1dff47b53"),
+    ];
+
+    pub static ref MAT_INTERNAL8: Mat<Fr, 8> = [
+        [from_hex("0x05bffb5e301d8c468c35e24eb2165b6b71725fb7ac9a48efe5ce041bdb05676e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x0e426ccab66984d1d8993a74ca548b779f5db92aaec5f102020d34aea15fba59"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x2aa7a81812688343fc6d78073312996d75f4c5505db0ed22af5ec0df7888cdc8"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x0e7c30c2e2e8957f4933bd1942053f1f0071684b902d534fa841924303f6a6c6"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x2f5856fd71dab60d78cc3af15a89c1e4d61ba189849a4cea10acc1dd228faf01"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x0812a017ca92cf0a1622708fc7edff1d6166ded6e3528ead4c76e1f31d3fc69d"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x12299a260999ac95d271e184968cda40bd4358877a6dcf43d779251fffa61349"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x21a5ade3df2bc1b5bba949d1db96040068afe5026edd7a9c2e276b47cf010d54"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x1443aad4693d692a62a8e21f03d5643a123f0c8783a3d27c275f9d01089685fc"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x01f3035463816c84ad711bf1a058c6c6bd101945f50e5afe72b1a5233f8749ce"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x21561b0204a44488082e31472f5885a3adc179bb278233aedc4b316369ec9938"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x0b115572f038c0e2028c2aafc2d06a5e8bf2f9398dbd0fdf4dcaa82b0f0c1c8b"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0c7cc2afa53f9898f30a69b294a4e24f6b2176e1ae0ca49b021792d55e34e97e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x1c38ec0b99b62fd4f0ef255543f50d2e27fc24db42bc910a3460613b6ef59e2f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x2dd221096053de389fae88e7caa5c43ab55e22aeb758ee130d1246c1dff47b54"),
         ],
-        [from_hex("0x1c89c6d9666272e8425c3ff1f4ac737b2f5d314606a297d4b1d0b254d880c53e"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+    ];
+
+    pub static ref MAT_EXTERNAL8: Mat<Fr, 8> = [
+        [from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000005"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000007"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+        ],
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000004"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
         ],
-        [from_hex("0x03326e643580356bf6d44008ae4c042a21ad4880097a5eb38b71e2311bb88f8f"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000005"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000007"),
         ],
-        [from_hex("0x268076b0054fb73f67cee9ea0e51e3ad50f27a6434b5dceb5bdde2299910a4c9"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
-        from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000004"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
         ],
-        [from_hex("0x1acd63c67fbc9ab1626ed93491bda32e5da18ea9d8e4f10178d04aa6f8747ad0"),
-        from_hex("0x19f8a5d670e8ab66c4e3144be58ef6901bf93375e2323ec3ca8c86cd2a28b5a5"),
-        from_hex("0x1c0dc443519ad7a86efa40d2df10a011068193ea51f6c92ae1cfbb5f7b9b6893"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000005"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000007"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
         ],
-        [from_hex("0x14b39e7aa4068dbe50fe7190e421dc19fbeab33cb4f6a2c4180e4c3224987d3d"),
-        from_hex("0x1d449b71bd826ec58f28c63ea6c561b7b820fc519f01f021afb1e35e28b0795e"),
-        from_hex("0x1ea2c9a89baaddbb60fa97fe60fe9d8e89de141689d1252276524dc0a9e987fc"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000004"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
         ],
-        [from_hex("0x0478d66d43535a8cb57e9c1c3d6a2bd7591f9a46a0e9c058134d5cefdb3c7ff1"),
-        from_hex("0x19272db71eece6a6f608f3b2717f9cd2662e26ad86c400b21cde5e4a7b00bebe"),
-        from_hex("0x14226537335cab33c749c746f09208abb2dd1bd66a87ef75039be846af134166"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000005"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000007"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000a"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000e"),
         ],
-        [from_hex("0x01fd6af15956294f9dfe38c0d976a088b21c21e4a1c2e823f912f44961f9a9ce"),
-        from_hex("0x18e5abedd626ec307bca190b8b2cab1aaee2e62ed229ba5a5ad8518d4e5f2a57"),
-        from_hex("0x0fc1bbceba0590f5abbdffa6d3b35e3297c021a3a409926d0e2d54dc1c84fda6"),
+        [from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000001"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000004"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000006"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000002"),
+        from_hex("0x0000000000000000000000000000000000000000000000000000000000000008"),
+        from_hex("0x000000000000000000000000000000000000000000000000000000000000000c"),
         ],
     ];
 
+    pub static ref RC8: [[Fr; 8]; 65] = RoundConstants::<Fr, 8>::from_reprs(&[
+        [repr_from_hex("0x0dad22d08a6b8d81d4a5ffc34b9677a7c5254c85e953551f9eba9a0a97590c10"),
+        repr_from_hex("0x03cfc441111f1bab6e75957b9f0274e92d17aefc2d8da460435dfaf259bd1039"),
+        repr_from_hex("0x27c6d1b8a2e2ce670376cd2970192a2a08e4290883f6ca49e7976c6c447d6392"),
+        repr_from_hex("0x28f2882b9abfda8efc2d121da0c871d2d5313a53c67d0407eef4e93ee23e6c2d"),
+        repr_from_hex("0x0a3943390fe4939fa2e931e18094c7c93aa107ced2bfaefcfe5e613a3f1262d5"),
+        repr_from_hex("0x2a8f37d8da8f2319e188f8909007408b57fba3555bb784c1ad6c7b0755805456"),
+        repr_from_hex("0x0cef8658e33f20c39649528f353ef5d03198c75683f8493534add693a4ff4f0f"),
+        repr_from_hex("0x1cdc2a6f0f0e7c6c62ee1c0cad4fd11363a48db7095171faaf9a7b6e01c3e52b"),
+        ],
+        [repr_from_hex("0x0a2e681a2db19c8d1cfb0c28f0b7b3d9830d35fca8c0b076b4e64328060139d6"),
+        repr_from_hex("0x16f013cd703be7de16b4361ce5c67cc2997f858e8a808885da67fbafd30d2717"),
+        repr_from_hex("0x29af57131c9cf548be669fe76c675e6d90bee17a76cccf4ddc5d35f9a936ebb8"),
+        repr_from_hex("0x21807b1ec6258bb155ed159da90b815dee119eae8138b1bdf950111bf5205b3b"),
+        repr_from_hex("0x07c00db8c50a8c860fbcb8ebd234a54350bdbcb8747891a4e5adab1c4abc6909"),
+        repr_from_hex("0x22bc08fc054d08a3dbb6094396d0de373a4c33205c49a1254cf25ebec1747765"),
+        repr_from_hex("0x2dad58bdf3e24c78f21146de084d9b8afad58956fedce67d43f7d375a813bb2e"),
+        repr_from_hex("0x292cee6bc19a64dc5a8a74036e676e238f60b4577db86213ced417906f1d6e91"),
+        ],
+        [repr_from_hex("0x27d655ae125928ad6b48bba54593b74ca0c23002b32a0a9ef375939a22387a8c"),
+        repr_from_hex("0x0e0e2e781fe9ac0a97f0d961c7b359a415d1ead41c99eccee90e56b68fbfa2a8"),
+        repr_from_hex("0x13fec23e678d1c9943b6daed8ed8212289116ab96344ded7ced8aac2f09f1672"),
+        repr_from_hex("0x27550dd21647e37fc31640684e02cb07810669b1c1545443fd58204b2cde73c2"),
+        repr_from_hex("0x1de6d5f356a92c48c7b9f6aa45c8a6216e0a6adbc799c0bec242054674cb8f09"),
+        repr_from_hex("0x14c5347c4580363f74c885d1fbb378c61d953e490892b10baf84f6e1f3a3f39d"),
+        repr_from_hex("0x27f2768023c4df7952ca0d967f14034e98c4ca76973ae1cddfccd212cbd78683"),
+        repr_from_hex("0x1f4f499fc4853f7e189ad6d2269c467f77e15bbd28963c516b4fbf1ba7b88f95"),
+        ],
+        [repr_from_hex("0x0d62ba9a8de3b97c72a5425a97a7f027fbad23f299ff46bafa12dcffb0e72bf1"),
+        repr_from_hex("0x22b649ae468f20c7cbfb435d932ba9ba4be14ea40c0c54e6bec970ab4c513f04"),
+        repr_from_hex("0x03a1aae439e47e7e14186d5513da5e263e063a3da6d042c16e55cab2e81a8e95"),
+        repr_from_hex("0x25c7eee1fce422600f5b1e78323f2e99cceee803f62763217eadbd046c1e0e6c"),
+        repr_from_hex("0x1c66258f103a955274ed71de56169bba34ff6aea97a2c32b98805dc3b011a383"),
+        repr_from_hex("0x29403b00757a647671a3691094aef420ad830f95743d45894d6d7f40aa356e4b"),
+        repr_from_hex("0x13496363ef5f7d0e91fd1319fd5dc0269c5190d05d8a0ad28a3ce0638a133e0e"),
+        repr_from_hex("0x1c6a4586c15c4638a1686921d7c204e2b6e1a88c1fc49dcfb87ded2a1ed7edc5"),
+        ],
+        [repr_from_hex("0x0da23df12d0bf47bb92f15ee189386c20f552d0bc66ae492b92d69556b2fe75a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x155be88bc5ddf8e0c6c741286cdd5ddd559279a455b9bbb61d5a0b004e545d5e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0553fbb5b32db860e0bb12cef3aab4e0dbbdb1ea8704d335a18fa8b4df112c36"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x12f6a3a61a3139221cb5875cf455b942580c00942499932dfcacf6f75cb80ad6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2faf9e95457781b3c61de8a1faadda67f76f52bb81255fac8819bcc104071e05"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c7639cb68d3cfa69f3d795d1a1dd4eba176d0c29701e7b677ea0750b4ccd1db"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0d9d85a46281f81502fbcfb430f4d08ab504c502d2feae2ab4a26d87bebea0dd"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x00a9b7074ea11d9931cf4af16952f398777aaa36413df14cedc101c7693812d3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x07fab349ddb7d62a5edc0b6a62b300715634e5dccab808d8fa6ae62b406705a3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x11f8f3afbd6fe6bda7d055a536ab3de9c40c8155b31d397f63ef21ac974cf52d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0007c569f8f49899805d22a1930883968a3cf058993e0adfd06da437ae50e830"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x15dfb18bf1b6087c0e638f2189a7b34534549ffe8fca5afccf7e528a3338cc92"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2140caa062d2fa72722ad48fb71bbc9f534043dab90dfa24b96664eaa2f0ac6a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x11c06fe4e5398d4704271b84e6023b07b189dd1d2023dd599b33defe4251b715"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0775f90bae5f8f8157eb73bbc7ae69b74d44761cf0df3b4666eeb2860878c0df"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0225befb64b758b837a4305e104d5e510d893653559d8768dc79325ff89d6b9f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x201a730245d9227f1232cc02f486c18cfa1fba40665ed3d1bbbdcd19b03cac4f"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x128b63b12a6647f80c686aaab15b0563c1a33f475dc18caa434ab64f1e290acb"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0a34ce8153d4e2e1158de05d276530cf3c749c16c7e09f3b671a973b0efb0839"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2809c5d323665513c8c4a96ea4dc08e3aba43699bca1198f2b559915082495ce"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x23b929e7d71fe425f36f32975e9a68bbf83c16622678f061ed78a0c9ce318be3"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1ca1a93d02ae448ab773f95c6b88c0d01f89c0c3d25ad625042771dd5a7e74a9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x03c7381ccdfd209a8cba5627a058c33f472b24d2b1a55d9dd47f60dc7b474e7e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x00be1a29bb668a25eaedffccef6c01c90688229e8fc4be9812e371d4d61c3015"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2c821bfbd5c415fc076fc83895edab9e5256ee826946a865b8c82f0189af4626"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0b8ff5b252182f6a15cff90144c1e7ac1678739882c90fe46d0a29db7a0cba77"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1c0e94b15306baf1b017623888b8d4c5402ee81fd213c4a5e42f52729deb7d5c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2031deb0f13ce17a5b44edbacf5a0410e23348c2c7657383a8ffdeb5c5ca1cd4"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0e34d14f44e8eb98ac078ec90eb718c3034249fbaeb13d24f3d5b6a4002018ac"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2a10f1b76f8b1cc429820030c58b3d23ebfb775a43b07928b9868891c2cb4cca"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0535bf4f7fb763f981d2c0c7ecd2237a461ac385e2235fb35222b512f1dbd1b7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x12ccb3fbffeff74fb7ce3b9943f609dc0d7fe34e3b5e6b199628e86d5576cc18"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1f44cd78220a2e66be6c9eb3bcffc498a47f22c3eb476591cf456d518e3e126c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1d60e9e3444a748b8c22d7d10e825c536290b723fcdfa046436353d6f9abc073"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x05a3ccc67bd014ef2544927f9b96ff58e348954e40af67861ab88d78ff4b6688"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1be5b195896e9ec23b9d3eb898734c385272ed64bdcb236223f5a3436986dc6d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1d6427dd07a0b46a8ebacaa82e8c04de8e7e94a18849c9275b8b4827a873cdab"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2f09a403f946b692704fe4d5ae69625441c93a7f93dcb4f8f7d337baa735ad9a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1807540511593488e084b7297b6ff67b748c71731b6ca64aa8d3823e81f41b37"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0f1daab7a702ac80d108b24845bda04b732f3564ebe57392e470380199522e1d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1797d0f72bf0b47a45ceaf82df261439bc5e3b0ae55cf1069733d5a0b51765ca"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2adbb9b4003631c5e3d02ce959930c75d4d25281c86febc61dfdf35e00cfbdad"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1347ad87393ef928d2800fa2a548328ef9bddf5646da79d507d2f2055db335d6"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x15658e02d31e2c200dadf6a985652eccd30cb959168cde7b74c7239539d2df1e"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x01f00dc28bced12a74c3ce687dd3fa996d6f8bb29bc60cda4f3aed191a6fe8d0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x154ebcc230244b68b9a8f51a56ff0541a92ea2cbb3e77b263cb03a8bd89491f7"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x1f65f74b523a8c875885378e93d1946c7a3f810e2eecdea94b80c4f741ea6cf9"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2504a559a4bd23ed689acc9b6cb4062479a6b29692e2b8cce40058daf0505778"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x24496d9926e4e565ef860e52396a2935689c87ca99abd046bc279ce1bfc43206"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2c9ccd22b143f4d8d483846d1960e7144a5c9354ea48cd1a71bdc82fbb929838"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2b9bd02e1b064a8db321a1cd212a7a6dc47274f6eb5fcfa4f17d6f690dddf8c4"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x0b21f0004118382f32fe441fbf36c0a38ae41873ae42d3b6ea5f0c6816e00472"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x10756efb491852587059f3e48b605a3bebabaab0a1ee5ff1758eb30623b7602c"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x127574a38823886d1f4841b540e29d67efe35aa8b3c685f969b6bc6cbab5064a"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x24751d620ed2a3db4382265b8c33b666b85beae7aa87f38e28bd2f8d637ff9d0"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x2e3d7208892e8a92e887fccccfe04d8d0fe994bc443641a9fc76c43da02f453d"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x11c3f6719071e6810699be20e1b1858b9ed00d8166bf15434cd5e43507b9d62b"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        repr_from_hex("0x0000000000000000000000000000000000000000000000000000000000000000"),
+        ],
+        [repr_from_hex("0x00b86112693a4e7f3ac4eadfcc3f04d24a7afe51a87f82d4a435ad27f30aa2f9"),
+        repr_from_hex("0x1a77348400a87f0180e294286a7a06c857f590e8ed17eed7a6b4cc1ca5a97929"),
+        repr_from_hex("0x207feb0ffff2e419bc967dd544abbdd2dcc601316f49957f2319c9a04f56157d"),
+        repr_from_hex("0x0df4aac4e6483265aa3fc462835a90380bc84004fc46d1a85e61baed3196ba48"),
+        repr_from_hex("0x2f2df3db6ed0e27d3151201dfe7f57fc2e5ccc69c2458535dbdbb5d88aeabb4f"),
+        repr_from_hex("0x2f51811c3568dbae396f86838c5c8b1bbf9b5bcb2ce98542e289184b95f74b7e"),
+        repr_from_hex("0x1198ec466dc8c3f55b048369eac0490e835928891325d4cf4461da1d22230219"),
+        repr_from_hex("0x19036827eb0714a1ee4fdd653d8667df192402e290dfdfc39bf005fdfa86c043"),
+        ],
+        [repr_from_hex("0x0978d774018c29f2818df365312371ce4082a6c8c52d2a965516b76436349069"),
+        repr_from_hex("0x231ed2d94767eb9ace111af9317b233fee037214e3f6d7e366a5812c8fd1dce5"),
+        repr_from_hex("0x0fbd8502ceba8ab93eb04d1db535d25f4c3be8acac4e8e0467b9d606653456c2"),
+        repr_from_hex("0x20e9fac35dda464e436a4d2eae4cdd5b52c9b712d5b4bfa43334463f8478d4bb"),
+        repr_from_hex("0x15ba9fc4b175278a12a5ab39ee0121b06dd09b923bdedb69a26182821445ba00"),
+        repr_from_hex("0x0c3c9aec9048b10a06d062675b63417a1ae8704f2918382466bf575e22a654d3"),
+        repr_from_hex("0x2795a46eb4c79595a9de2191c524f0aaf92f764153b23c357ed50bf3a91cddf0"),
+        repr_from_hex("0x10ab7893c4253d861276ea30f229eb86b90c69985fb6dde4aa50e24828caa81a"),
+        ],
+        [repr_from_hex("0x2d4509d8159af62c44503f64fc01e953e90c6dced8ba75c58df2aa090a5fd359"),
+        repr_from_hex("0x157946957f4feb94eae09b9a6c272204d107ea7b0e31b67524a6c9ee7f04e7b9"),
+        repr_from_hex("0x30464efe02b65b862f9a0b59fea6d6934b393e65cdc3d29ae67a751a0d0f136a"),
+        repr_from_hex("0x0965b3a2d05666d38ffcdac4e5ed020c742ba41d760e70f74dcd8f8b1324c901"),
+        repr_from_hex("0x24531c784663d57639928f0a8f152038a779eebbd70f6585dacc971668ebec55"),
+        repr_from_hex("0x2202070df8f85b79bf7debf24a9cc0f2cf30370f26a9372e01718dae7cb6171d"),
+        repr_from_hex("0x08282fc0baa2e9eb76167b9ab2405db9c78f1f37b168be6435d5385a806e745a"),
+        repr_from_hex("0x178c3d6e47cd33e5570311ec8ad8dbca0dbf54aadd1393738d898384c57795e7"),
+        ],
+        [repr_from_hex("0x12ad905f9d82f33643b1fefb90dcefffeb818d1ec3f4fc3d6857203eff5ed1ea"),
+        repr_from_hex("0x229682c9e4165b6ed1a2870c14a1c8bb7b1c7d3b52f42ce0228b85480666b4c4"),
+        repr_from_hex("0x25190d853744dc11d155de5e479640302a333d47cf18dbf1ff241ce42c88d4c9"),
+        repr_from_hex("0x00db47fadc76bf4fd8dcfe26908fe48ab42d399da939ac1f167ca8600cfbe7bc"),
+        repr_from_hex("0x2ba5e88adcbe025760f2c8654935f0557d14c6d4125a838f312f2ba439e2e94c"),
+        repr_from_hex("0x1376bbae85699dcc294f850c858b4b370e51ff3139bc3e0a1e52eb58d08ca41b"),
+        repr_from_hex("0x1333fda1e3d58e0747f5c7361d5075526933ea283eee2ac37b6042c6083b6a42"),
+        repr_from_hex("0x28bfc4dc9594e9add687744b79f4feb979e2143d742d4e4d1d5bb64bb799a02a"),
+        ],
+    ])
+    .expect("the round constants are canonical")
+    .into_rounds()
+    .try_into()
+    .unwrap();
 }
 
 // lazy_static! {
@@ -1,25 +1,94 @@
 pub use halo2curves::bn256::Fr as Fp;
+use halo2curves::bn256::Fq;
 
+use super::p128pow5t2::P128Pow5T2Constants;
 use super::p128pow5t3::P128Pow5T3Constants;
+use super::p128pow5t4::P128Pow5T4Constants;
+use super::p128pow5t8::P128Pow5T8Constants;
 use super::primitives::Mds;
 
 
 
 pub mod fp;
+pub mod fq;
 
 impl P128Pow5T3Constants for Fp {
     fn partial_rounds() -> usize {
         56
     }
 
-    fn round_constants() -> Vec<[Fp; 3]> {
-        fp::RC3.to_vec()
+    fn round_constants() -> &'static [[Fp; 3]] {
+        &*fp::RC3
     }
-    fn mds_internal() -> Mds<Fp, 3> {
-        *fp::MAT_INTERNAL3
+    fn mds_internal() -> &'static Mds<Fp, 3> {
+        &fp::MAT_INTERNAL3
     }
-    fn mds_external() -> Mds<Fp, 3> {
-        *fp::MAT_EXTERNAL3
+    fn mds_external() -> &'static Mds<Fp, 3> {
+        &fp::MAT_EXTERNAL3
+    }
+}
+
+impl P128Pow5T3Constants for Fq {
+    fn partial_rounds() -> usize {
+        56
+    }
+
+    fn round_constants() -> &'static [[Fq; 3]] {
+        &*fq::RC3
+    }
+    fn mds_internal() -> &'static Mds<Fq, 3> {
+        &fq::MAT_INTERNAL3
+    }
+    fn mds_external() -> &'static Mds<Fq, 3> {
+        &fq::MAT_EXTERNAL3
+    }
+}
+
+impl P128Pow5T2Constants for Fp {
+    fn partial_rounds() -> usize {
+        56
+    }
+
+    fn round_constants() -> &'static [[Fp; 2]] {
+        &*fp::RC2
+    }
+    fn mds_internal() -> &'static Mds<Fp, 2> {
+        &fp::MAT_INTERNAL2
+    }
+    fn mds_external() -> &'static Mds<Fp, 2> {
+        &fp::MAT_EXTERNAL2
+    }
+}
+
+impl P128Pow5T4Constants for Fp {
+    fn partial_rounds() -> usize {
+        56
+    }
+
+    fn round_constants() -> &'static [[Fp; 4]] {
+        &*fp::RC4
+    }
+    fn mds_internal() -> &'static Mds<Fp, 4> {
+        &fp::MAT_INTERNAL4
+    }
+    fn mds_external() -> &'static Mds<Fp, 4> {
+        &fp::MAT_EXTERNAL4
+    }
+}
+
+impl P128Pow5T8Constants for Fp {
+    fn partial_rounds() -> usize {
+        57
+    }
+
+    fn round_constants() -> &'static [[Fp; 8]] {
+        &*fp::RC8
+    }
+    fn mds_internal() -> &'static Mds<Fp, 8> {
+        &fp::MAT_INTERNAL8
+    }
+    fn mds_external() -> &'static Mds<Fp, 8> {
+        &fp::MAT_EXTERNAL8
     }
 }
 
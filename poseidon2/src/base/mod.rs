@@ -1,12 +1,24 @@
 pub mod hash;
+pub mod p128pow5t2;
 pub mod p128pow5t3;
 pub mod p128pow5t3_compact;
+pub mod p128pow5t4;
+pub mod p128pow5t8;
 pub mod bn256;
 pub mod primitives;
+pub mod security;
+#[cfg(test)]
+pub(crate) mod test_vectors;
 
 
+pub use p128pow5t2::{P128Pow5T2, P128Pow5T2Constants};
 pub use p128pow5t3::P128Pow5T3;
 pub use p128pow5t3::P128Pow5T3Constants;
+pub use p128pow5t4::{P128Pow5T4, P128Pow5T4Constants};
+pub use p128pow5t8::{P128Pow5T8, P128Pow5T8Constants};
 pub use p128pow5t3_compact::P128Pow5T3Compact;
 
 pub use hash::{Hashable, HASHABLE_DOMAIN_SPEC};
+pub use security::{
+    check_security, recommended_full_rounds, recommended_partial_rounds, SecurityError,
+};
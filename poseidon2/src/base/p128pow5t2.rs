@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use ff::FromUniformBytes;
+
+use super::primitives::{Mds, Spec};
+
+/// The trait required for fields can handle a pow5 sbox, 2 field, 1 rate permutation
+///
+/// It is implemented for `bn256::Fr` only.
+pub trait P128Pow5T2Constants: FromUniformBytes<64> + Ord {
+    fn partial_rounds() -> usize {
+        56
+    }
+    fn round_constants() -> &'static [[Self; 2]];
+    fn mds_internal() -> &'static Mds<Self, 2>;
+    fn mds_external() -> &'static Mds<Self, 2>;
+}
+
+/// Poseidon-128 using the $x^5$ S-box, with a width of 2 field elements, and the
+/// standard number of rounds for 128-bit security "with margin".
+///
+/// The standard specification for this set of parameters (on bn256) uses
+/// $R_F = 8, R_P = 56$.
+#[derive(Debug)]
+pub struct P128Pow5T2<C> {
+    _marker: PhantomData<C>,
+}
+
+impl<Fp: P128Pow5T2Constants> Spec<Fp, 2, 1> for P128Pow5T2<Fp> {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        Fp::partial_rounds()
+    }
+
+    fn alpha() -> u64 {
+        5
+    }
+
+    fn secure_mds() -> usize {
+        unimplemented!()
+    }
+
+    fn round_constants() -> Cow<'static, [[Fp; 2]]> {
+        Cow::Borrowed(Fp::round_constants())
+    }
+
+    fn internal_matrix() -> Cow<'static, Mds<Fp, 2>> {
+        Cow::Borrowed(Fp::mds_internal())
+    }
+
+    fn external_matrix() -> Cow<'static, Mds<Fp, 2>> {
+        Cow::Borrowed(Fp::mds_external())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::P128Pow5T2;
+    use crate::base::primitives::permute;
+    use crate::base::test_vectors::from_hex;
+
+    #[test]
+    fn permute_test_vector() {
+        let mut state = [0u64, 1].map(Fp::from);
+        permute::<_, P128Pow5T2<Fp>, 2, 1>(&mut state);
+        assert_eq!(
+            state,
+            [
+                "0x1d01e56f49579cec72319e145f06f6177f6c5253206e78c2689781452a31878b",
+                "0x0d189ec589c41b8cffa88cfc523618a055abe8192c70f75aa72fc514560f6c61",
+            ]
+            .map(from_hex::<Fp>)
+        );
+    }
+}
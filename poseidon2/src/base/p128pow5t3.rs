@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use halo2curves;
@@ -6,13 +7,16 @@ use ff::FromUniformBytes;
 use super::primitives::{Mds, Spec};
 
 /// The trait required for fields can handle a pow5 sbox, 3 field, 2 rate permutation
+///
+/// It is implemented for `bn256::Fr`, and for `bn256::Fq` (i.e. `grumpkin::Fr`) for the
+/// other half of a bn256/Grumpkin cycle.
 pub trait P128Pow5T3Constants: FromUniformBytes<64> + Ord {
     fn partial_rounds() -> usize {
         56
     }
-    fn round_constants() -> Vec<[Self; 3]>;
-    fn mds_internal() -> Mds<Self, 3>;
-    fn mds_external() -> Mds<Self, 3>;
+    fn round_constants() -> &'static [[Self; 3]];
+    fn mds_internal() -> &'static Mds<Self, 3>;
+    fn mds_external() -> &'static Mds<Self, 3>;
 }
 
 /// Poseidon-128 using the $x^5$ S-box, with a width of 3 field elements, and the
@@ -21,6 +25,12 @@ pub trait P128Pow5T3Constants: FromUniformBytes<64> + Ord {
 /// The standard specification for this set of parameters (on either of the Pasta
 /// fields) uses $R_F = 8, R_P = 56$. This is conveniently an even number of
 /// partial rounds, making it easier to construct a Halo 2 circuit.
+///
+/// See [`P128Pow5T2`], [`P128Pow5T4`] and [`P128Pow5T8`] for the other standard widths.
+///
+/// [`P128Pow5T2`]: super::P128Pow5T2
+/// [`P128Pow5T4`]: super::P128Pow5T4
+/// [`P128Pow5T8`]: super::P128Pow5T8
 #[derive(Debug)]
 pub struct P128Pow5T3<C> {
     _marker: PhantomData<C>,
@@ -35,16 +45,24 @@ impl<Fp: P128Pow5T3Constants> Spec<Fp, 3, 2> for P128Pow5T3<Fp> {
         Fp::partial_rounds()
     }
 
-    fn sbox(val: Fp) -> Fp {
-        val.pow_vartime([5])
+    fn alpha() -> u64 {
+        5
     }
 
     fn secure_mds() -> usize {
         unimplemented!()
     }
 
-    fn constants() -> (Vec<[Fp; 3]>, Mds<Fp, 3>, Mds<Fp, 3>) {
-        (Fp::round_constants(), Fp::mds_internal(), Fp::mds_external())
+    fn round_constants() -> Cow<'static, [[Fp; 3]]> {
+        Cow::Borrowed(Fp::round_constants())
+    }
+
+    fn internal_matrix() -> Cow<'static, Mds<Fp, 3>> {
+        Cow::Borrowed(Fp::mds_internal())
+    }
+
+    fn external_matrix() -> Cow<'static, Mds<Fp, 3>> {
+        Cow::Borrowed(Fp::mds_external())
     }
 }
 
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use halo2curves;
@@ -25,39 +26,42 @@ impl<Fp: P128Pow5T3Constants + FromUniformBytes<64> + Ord> Spec<Fp, 3, 2>
         Fp::partial_rounds()
     }
 
-    fn sbox(val: Fp) -> Fp {
-        // much faster than val.pow_vartime([5])
-        let a = val * val;
-        let b = a * a;
-        b * val
+    fn alpha() -> u64 {
+        5
     }
 
     fn secure_mds() -> usize {
         unimplemented!()
     }
 
-    fn constants() -> (Vec<[Fp; 3]>, Mds<Fp, 3>, Mds<Fp, 3>) {
-        let (mut rc, mds_internal, mds_external) = (Fp::round_constants(), Fp::mds_internal(), Fp::mds_external());
-
-        // let first_partial = Self::full_rounds() / 2;
-        // let after_partials = first_partial + Self::partial_rounds();
-
-        // // Propagate the constants of each partial round into the next.
-        // for i in first_partial..after_partials {
-        //     // Extract the constants rc[i][1] and rc[i][2] that do not pass through the S-box.
-        //     // Leave the value 0 in their place.
-        //     // rc[i][0] stays in place.
-        //     let rc_tail = vec_remove_tail(&mut rc[i]);
-
-        //     // Pass forward through the MDS matrix.
-        //     let rc_carry = mat_mul(&mds, &rc_tail);
+    // The constants are not yet compacted, so they are shared with `P128Pow5T3`. Compacting
+    // them would need a static per field to borrow from.
+    //
+    // let first_partial = Self::full_rounds() / 2;
+    // let after_partials = first_partial + Self::partial_rounds();
+    // // Propagate the constants of each partial round into the next.
+    // for i in first_partial..after_partials {
+    //     // Extract the constants rc[i][1] and rc[i][2] that do not pass through the S-box.
+    //     // Leave the value 0 in their place.
+    //     // rc[i][0] stays in place.
+    //     let rc_tail = vec_remove_tail(&mut rc[i]);
+    //     // Pass forward through the MDS matrix.
+    //     let rc_carry = mat_mul(&mds, &rc_tail);
+    //     // Accumulate the carried constants into the next round.
+    //     vec_accumulate(&mut rc[i + 1], &rc_carry);
+    // }
+    // // Now constants have accumulated into the next full round.
+
+    fn round_constants() -> Cow<'static, [[Fp; 3]]> {
+        Cow::Borrowed(Fp::round_constants())
+    }
 
-        //     // Accumulate the carried constants into the next round.
-        //     vec_accumulate(&mut rc[i + 1], &rc_carry);
-        // }
-        // // Now constants have accumulated into the next full round.
+    fn internal_matrix() -> Cow<'static, Mds<Fp, 3>> {
+        Cow::Borrowed(Fp::mds_internal())
+    }
 
-        (rc, mds_internal, mds_external)
+    fn external_matrix() -> Cow<'static, Mds<Fp, 3>> {
+        Cow::Borrowed(Fp::mds_external())
     }
 }
 
@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use ff::FromUniformBytes;
+
+use super::primitives::{Mds, Spec};
+
+/// The trait required for fields can handle a pow5 sbox, 4 field, 3 rate permutation
+///
+/// It is implemented for `bn256::Fr` only.
+pub trait P128Pow5T4Constants: FromUniformBytes<64> + Ord {
+    fn partial_rounds() -> usize {
+        56
+    }
+    fn round_constants() -> &'static [[Self; 4]];
+    fn mds_internal() -> &'static Mds<Self, 4>;
+    fn mds_external() -> &'static Mds<Self, 4>;
+}
+
+/// Poseidon-128 using the $x^5$ S-box, with a width of 4 field elements, and the
+/// standard number of rounds for 128-bit security "with margin".
+///
+/// The standard specification for this set of parameters (on bn256) uses
+/// $R_F = 8, R_P = 56$.
+#[derive(Debug)]
+pub struct P128Pow5T4<C> {
+    _marker: PhantomData<C>,
+}
+
+impl<Fp: P128Pow5T4Constants> Spec<Fp, 4, 3> for P128Pow5T4<Fp> {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        Fp::partial_rounds()
+    }
+
+    fn alpha() -> u64 {
+        5
+    }
+
+    fn secure_mds() -> usize {
+        unimplemented!()
+    }
+
+    fn round_constants() -> Cow<'static, [[Fp; 4]]> {
+        Cow::Borrowed(Fp::round_constants())
+    }
+
+    fn internal_matrix() -> Cow<'static, Mds<Fp, 4>> {
+        Cow::Borrowed(Fp::mds_internal())
+    }
+
+    fn external_matrix() -> Cow<'static, Mds<Fp, 4>> {
+        Cow::Borrowed(Fp::mds_external())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::P128Pow5T4;
+    use crate::base::primitives::permute;
+    use crate::base::test_vectors::from_hex;
+
+    #[test]
+    fn permute_test_vector() {
+        let mut state = [0u64, 1, 2, 3].map(Fp::from);
+        permute::<_, P128Pow5T4<Fp>, 4, 3>(&mut state);
+        assert_eq!(
+            state,
+            [
+                "0x1216bc898ece504930683952006f737dfedeace69511d8730cf38817d10112d7",
+                "0x264c65d5941ed7ff16dd743ccdbda873fe09c733a96fb14ac7a4cea490a5e0bb",
+                "0x1a35d82a82e10a61b98c32e87d60d324a862e0bca947ad385df18c4cb7901a64",
+                "0x2075a89cc0f5613492e96622a129a36a2f19f4d9ce0dcffc40bef271cd7e9bd2",
+            ]
+            .map(from_hex::<Fp>)
+        );
+    }
+}
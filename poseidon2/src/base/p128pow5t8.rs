@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use ff::FromUniformBytes;
+
+use super::primitives::{Mds, Spec};
+
+/// The trait required for fields can handle a pow5 sbox, 8 field, 7 rate permutation
+///
+/// It is implemented for `bn256::Fr` only.
+pub trait P128Pow5T8Constants: FromUniformBytes<64> + Ord {
+    fn partial_rounds() -> usize {
+        57
+    }
+    fn round_constants() -> &'static [[Self; 8]];
+    fn mds_internal() -> &'static Mds<Self, 8>;
+    fn mds_external() -> &'static Mds<Self, 8>;
+}
+
+/// Poseidon-128 using the $x^5$ S-box, with a width of 8 field elements, and the
+/// standard number of rounds for 128-bit security "with margin".
+///
+/// The standard specification for this set of parameters (on bn256) uses
+/// $R_F = 8, R_P = 57$.
+///
+/// The odd number of partial rounds is what the round-number analysis gives for this
+/// width.
+#[derive(Debug)]
+pub struct P128Pow5T8<C> {
+    _marker: PhantomData<C>,
+}
+
+impl<Fp: P128Pow5T8Constants> Spec<Fp, 8, 7> for P128Pow5T8<Fp> {
+    fn full_rounds() -> usize {
+        8
+    }
+
+    fn partial_rounds() -> usize {
+        Fp::partial_rounds()
+    }
+
+    fn alpha() -> u64 {
+        5
+    }
+
+    fn secure_mds() -> usize {
+        unimplemented!()
+    }
+
+    fn round_constants() -> Cow<'static, [[Fp; 8]]> {
+        Cow::Borrowed(Fp::round_constants())
+    }
+
+    fn internal_matrix() -> Cow<'static, Mds<Fp, 8>> {
+        Cow::Borrowed(Fp::mds_internal())
+    }
+
+    fn external_matrix() -> Cow<'static, Mds<Fp, 8>> {
+        Cow::Borrowed(Fp::mds_external())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::P128Pow5T8;
+    use crate::base::primitives::permute;
+    use crate::base::test_vectors::from_hex;
+
+    #[test]
+    fn permute_test_vector() {
+        let mut state = [0u64, 1, 2, 3, 4, 5, 6, 7].map(Fp::from);
+        permute::<_, P128Pow5T8<Fp>, 8, 7>(&mut state);
+        assert_eq!(
+            state,
+            [
+                "0x1d1a50bcde871247856df135d56a4ca61af575f1140ed9b1503c77528cf345df",
+                "0x2d3943cf476ed49fd8a636660d8a76c83b55f07d06bc082005ad7eb1a21791c5",
+                "0x2fcda2dd846fadfde8104b1d05175dcf3cf8bd698ed8ea3ad2fbcf9c06e00310",
+                "0x28811ac7e0829171f9d3d81f1c0ff8f34b360d407a16b331a1cb6b5d992de094",
+                "0x2c07c1817cfccb67c1297935514885c07abad5a0e15477f6c076c0b0fb1ad6f3",
+                "0x1b6114397199bc44e37437dd3ba1754dff007d3315bfcdcdc14ec27d02452f52",
+                "0x1431250baf36fb61a07618caee4dd2f500da339a05c553e8f529a3349e617aa2",
+                "0x0b19bfa00c8f1d505074130e7f8b49a8624b1905e280ceca5ba11099b081b265",
+            ]
+            .map(from_hex::<Fp>)
+        );
+    }
+}
@@ -1,12 +1,15 @@
 //! The Poseidon algebraic hash function.
 
+use std::borrow::Cow;
 use std::convert::TryInto;
 use std::fmt;
 use std::iter;
 use std::marker::PhantomData;
+use std::ops;
 use ff::FromUniformBytes;
 use ff::PrimeField;
 use halo2_proofs::arithmetic::Field;
+use thiserror::Error;
 
 use crate::circuit::params_bn254::RC3;
 
@@ -24,7 +27,61 @@ use crate::circuit::params_bn254::RC3;
 // use grain::SboxType;
 
 /// The type used to hold permutation state.
-pub(crate) type State<F, const T: usize> = [F; T];
+///
+/// As this is an array, a `[F; T]` can be used directly as a state; other collections
+/// of words can be converted with [`state_from_iter`].
+pub type State<F, const T: usize> = [F; T];
+
+/// Builds a state from the words yielded by `words`, e.g. field elements or
+/// [`StateWord`]s.
+///
+/// # Panics
+///
+/// Panics if `words` does not yield exactly `T` words.
+///
+/// [`StateWord`]: crate::circuit::pow5::StateWord
+pub fn state_from_iter<W, const T: usize>(words: impl IntoIterator<Item = W>) -> State<W, T> {
+    let words = words.into_iter().collect::<Vec<_>>();
+    let len = words.len();
+    words
+        .try_into()
+        .unwrap_or_else(|_| panic!("expected {} state words, got {}", T, len))
+}
+
+/// Splits a 256-bit word, given as 32 little-endian bytes, into its low and high
+/// 128-bit limbs, in the order [`Sponge::absorb_u256`] absorbs them.
+///
+/// Unlike reducing the word modulo the field, this is injective even for words that
+/// exceed the modulus, as Ethereum-style words may.
+///
+/// [`Sponge::absorb_u256`]: crate::circuit::poseidon::Sponge::absorb_u256
+pub fn u256_limbs<F: PrimeField>(bytes: [u8; 32]) -> [F; 2] {
+    let lo = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let hi = u128::from_le_bytes(bytes[16..].try_into().unwrap());
+    [F::from_u128(lo), F::from_u128(hi)]
+}
+
+/// Packs `value` and a boolean `tag` into the single word `2 * value + tag`, as
+/// [`Sponge::absorb_tagged`] does before absorbing it.
+///
+/// This is only injective for values below `(p - 1) / 2`, e.g. values of at most
+/// `F::NUM_BITS - 2` bits.
+///
+/// [`Sponge::absorb_tagged`]: crate::circuit::poseidon::Sponge::absorb_tagged
+pub fn pack_tagged<F: PrimeField>(value: F, tag: bool) -> F {
+    value.double() + F::from(u64::from(tag))
+}
+
+/// Reduces `value`, as an integer below the field modulus, modulo `modulus`, as
+/// [`Sponge::squeeze_index`] does in-circuit, e.g. to derive the indices of a shuffle.
+///
+/// `value` is read from its canonical little-endian encoding.
+///
+/// [`Sponge::squeeze_index`]: crate::circuit::poseidon::Sponge::squeeze_index
+pub fn reduce_index<F: PrimeField<Repr = [u8; 32]>>(value: F, modulus: u64) -> u64 {
+    let value = num_bigint::BigUint::from_bytes_le(&value.to_repr());
+    u64::try_from(value % modulus).expect("the remainder is below a u64 modulus")
+}
 
 /// The type used to hold sponge rate.
 pub(crate) type SpongeRate<F, const RATE: usize> = [Option<F>; RATE];
@@ -32,7 +89,102 @@ pub(crate) type SpongeRate<F, const RATE: usize> = [Option<F>; RATE];
 /// The type used to hold the MDS matrix and its inverse.
 pub type Mds<F, const T: usize> = [[F; T]; T];
 
+/// An internal matrix of the form `J + diag(diagonal)`, where `J` is the all-ones
+/// matrix, as Poseidon2 internal matrices are chosen.
+///
+/// Multiplying a state by it takes the sum of the state and one product per word,
+/// rather than a product per matrix entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InternalMatrix<F, const T: usize> {
+    /// The diagonal entries of the matrix, minus one.
+    pub diagonal: [F; T],
+}
+
+impl<F: Field, const T: usize> InternalMatrix<F, T> {
+    /// Returns `mat` in this form, if every entry off its diagonal is one.
+    pub fn from_dense(mat: &Mds<F, T>) -> Option<Self> {
+        let off_diagonal_ones = (0..T)
+            .flat_map(|i| (0..T).map(move |j| (i, j)))
+            .all(|(i, j)| i == j || mat[i][j] == F::ONE);
+        off_diagonal_ones.then(|| InternalMatrix {
+            diagonal: std::array::from_fn(|i| mat[i][i] - F::ONE),
+        })
+    }
+
+    /// The matrix with every entry written out.
+    pub fn to_dense(&self) -> Mds<F, T> {
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { self.diagonal[i] + F::ONE } else { F::ONE })
+        })
+    }
+
+    /// Multiplies `state` by this matrix.
+    pub fn mul_vec(&self, state: &[F; T]) -> [F; T] {
+        let sum: F = state.iter().sum();
+        std::array::from_fn(|i| sum + self.diagonal[i] * state[i])
+    }
+}
+
+/// Round constants checked to be canonical field elements, one array per round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundConstants<F, const T: usize>(Vec<[F; T]>);
+
+impl<F: PrimeField, const T: usize> RoundConstants<F, T> {
+    /// Decodes one array of constants per round from their byte representations.
+    ///
+    /// Unlike decoding them one by one and unwrapping, a representation of a value not
+    /// below the modulus, or one that is not the minimal representative of its value,
+    /// is rejected rather than trusted.
+    pub fn from_reprs(reprs: &[[F::Repr; T]]) -> Result<Self, ConstantError> {
+        let decode = |round: usize, index: usize, repr: &F::Repr| {
+            Option::<F>::from(F::from_repr(*repr))
+                .filter(|value| value.to_repr().as_ref() == repr.as_ref())
+                .ok_or(ConstantError::NonCanonical { round, index })
+        };
+        let rounds = reprs
+            .iter()
+            .enumerate()
+            .map(|(round, reprs)| {
+                let words: Result<Vec<_>, _> = reprs
+                    .iter()
+                    .enumerate()
+                    .map(|(index, repr)| decode(round, index, repr))
+                    .collect();
+                words.map(state_from_iter)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(RoundConstants(rounds))
+    }
+
+    /// The constants, one array per round.
+    pub fn rounds(&self) -> &[[F; T]] {
+        &self.0
+    }
+
+    /// Returns the constants, one array per round.
+    pub fn into_rounds(self) -> Vec<[F; T]> {
+        self.0
+    }
+}
+
+/// Why round constants cannot be decoded.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ConstantError {
+    /// A representation is not that of a reduced field element.
+    #[error("constant {index} of round {round} is not a canonical field element")]
+    NonCanonical {
+        /// The round of the constant.
+        round: usize,
+        /// The index of the constant within its round.
+        index: usize,
+    },
+}
+
 /// A specification for a Poseidon permutation.
+///
+/// The field bound excludes extension fields: constants are derived by reducing
+/// uniform bytes into `F`, and the chip built from a spec works over `F` natively. The
+/// S-box itself, [`sbox`], only needs [`Field`].
 pub trait Spec<F: FromUniformBytes<64> + Ord, const T: usize, const RATE: usize>: fmt::Debug {
     /// The number of full rounds for this specification.
     ///
@@ -42,8 +194,16 @@ pub trait Spec<F: FromUniformBytes<64> + Ord, const T: usize, const RATE: usize>
     /// The number of partial rounds for this specification.
     fn partial_rounds() -> usize;
 
+    /// The exponent `alpha` of the S-box $x^\alpha$, which the chip constrains.
+    fn alpha() -> u64;
+
     /// The S-box for this specification.
-    fn sbox(val: F) -> F;
+    ///
+    /// Defaults to $x^\alpha$ for [`Self::alpha`]. An override must compute the same
+    /// map, as the chip only constrains that one.
+    fn sbox(val: F) -> F {
+        sbox(val, Self::alpha())
+    }
 
     /// Side-loaded index of the first correct and secure MDS that will be generated by
     /// the reference implementation.
@@ -52,8 +212,207 @@ pub trait Spec<F: FromUniformBytes<64> + Ord, const T: usize, const RATE: usize>
     /// hard-coding the constants, you may leave this unimplemented.
     fn secure_mds() -> usize;
 
-    /// Generates `(round_constants, mds, mds^-1)` corresponding to this specification.
-    fn constants() -> (Vec<[F; T]>, Mds<F, T>, Mds<F, T>);
+    /// The round constants of this specification, one array per round.
+    ///
+    /// Specs holding their constants in a `static` should return them borrowed, so that
+    /// configuring a chip does not copy them.
+    fn round_constants() -> Cow<'static, [[F; T]]>;
+
+    /// The matrix applied after each partial round.
+    fn internal_matrix() -> Cow<'static, Mds<F, T>>;
+
+    /// The matrix applied before the first round and after each full round.
+    fn external_matrix() -> Cow<'static, Mds<F, T>>;
+
+    /// The matrices applied after the partial rounds, cycled through so that partial
+    /// round `i` uses `internal_matrices()[i % len]`. Defaults to [`Self::internal_matrix`]
+    /// alone, shared by every partial round.
+    fn internal_matrices() -> Cow<'static, [Mds<F, T>]> {
+        matrix_as_slice(Self::internal_matrix())
+    }
+
+    /// Returns owned copies of `(round_constants, internal_matrix, external_matrix)`.
+    #[deprecated(note = "use `round_constants`, `internal_matrix` and `external_matrix`")]
+    fn constants() -> (Vec<[F; T]>, Mds<F, T>, Mds<F, T>) {
+        (
+            Self::round_constants().into_owned(),
+            Self::internal_matrix().into_owned(),
+            Self::external_matrix().into_owned(),
+        )
+    }
+
+    /// Which permutation this specification describes. Defaults to
+    /// [`PoseidonVariant::Poseidon2`].
+    fn variant() -> PoseidonVariant {
+        PoseidonVariant::Poseidon2
+    }
+
+    /// Whether this specification describes a Poseidon2 permutation, i.e. one whose
+    /// partial rounds use an internal matrix distinct from the external one. Defaults
+    /// to whether [`Self::variant`] is [`PoseidonVariant::Poseidon2`].
+    fn is_poseidon2() -> bool {
+        Self::variant() == PoseidonVariant::Poseidon2
+    }
+
+    /// Which end of the state holds the rate, and hence which element is squeezed
+    /// first. Defaults to [`StateEndianness::Little`].
+    fn state_endianness() -> StateEndianness {
+        StateEndianness::Little
+    }
+
+    /// Whether the final full round omits its external-matrix multiplication, as in
+    /// variants whose output is truncated anyway. Defaults to `false`.
+    fn skip_last_mds() -> bool {
+        false
+    }
+
+    /// A SHA-256 fingerprint of the permutation this spec describes, to check its
+    /// constants against a published value, e.g. with
+    /// [`Pow5Chip::configure_with_expected_fingerprint`].
+    ///
+    /// The digest is over, in order: `T`, `RATE`, the full and partial rounds and
+    /// [`Self::alpha`] as little-endian `u64`s; one byte each for whether the variant
+    /// is Poseidon2, whether the last MDS is skipped and whether the state is
+    /// [`StateEndianness::Big`]; the number of rounds of constants as a `u64` and the
+    /// canonical encoding of every round constant; the external matrix; and the number
+    /// of matrices the partial rounds use as a `u64`, followed by their entries.
+    /// Matrices are encoded entry by entry, in row-major order.
+    ///
+    /// [`Pow5Chip::configure_with_expected_fingerprint`]:
+    /// crate::circuit::pow5::Pow5Chip::configure_with_expected_fingerprint
+    #[cfg(feature = "fingerprint")]
+    fn constants_fingerprint() -> [u8; 32]
+    where
+        Self: Sized,
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for n in [T, RATE, Self::full_rounds(), Self::partial_rounds()] {
+            hasher.update((n as u64).to_le_bytes());
+        }
+        hasher.update(Self::alpha().to_le_bytes());
+        hasher.update([
+            u8::from(Self::is_poseidon2()),
+            u8::from(Self::skip_last_mds()),
+            u8::from(Self::state_endianness() == StateEndianness::Big),
+        ]);
+
+        let round_constants = Self::round_constants();
+        hasher.update((round_constants.len() as u64).to_le_bytes());
+        for word in round_constants.iter().flatten() {
+            hasher.update(word.to_repr());
+        }
+        for word in Self::external_matrix().iter().flatten() {
+            hasher.update(word.to_repr());
+        }
+        let matrices = Self::variant().partial_round_matrices::<F, Self, T, RATE>();
+        hasher.update((matrices.len() as u64).to_le_bytes());
+        for word in matrices.iter().flatten().flatten() {
+            hasher.update(word.to_repr());
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// A spec whose [`Spec::constants_fingerprint`] is not the one it was expected to have.
+#[cfg(feature = "fingerprint")]
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error(
+    "the spec's constants have fingerprint {}, but {} was expected",
+    hex::encode(.actual),
+    hex::encode(.expected)
+)]
+pub struct FingerprintMismatch {
+    /// The fingerprint the spec was expected to have.
+    pub expected: [u8; 32],
+    /// The fingerprint of the spec.
+    pub actual: [u8; 32],
+}
+
+/// The permutation described by a [`Spec`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoseidonVariant {
+    /// The original Poseidon permutation: no linear layer before the first round, and
+    /// the external matrix after every round, partial rounds included. The internal
+    /// matrices of the spec are ignored.
+    Poseidon1,
+    /// The Poseidon2 permutation: the external matrix is applied before the first
+    /// round and after each full round, and the internal matrices after the partial
+    /// rounds.
+    Poseidon2,
+}
+
+impl PoseidonVariant {
+    /// Whether the external matrix is applied to the state before the first round.
+    pub fn has_first_layer(self) -> bool {
+        self == PoseidonVariant::Poseidon2
+    }
+
+    /// The matrices cycled through by the partial rounds of `S`.
+    pub fn partial_round_matrices<F, S, const T: usize, const RATE: usize>(
+        self,
+    ) -> Cow<'static, [Mds<F, T>]>
+    where
+        F: FromUniformBytes<64> + Ord,
+        S: Spec<F, T, RATE>,
+    {
+        match self {
+            PoseidonVariant::Poseidon1 => matrix_as_slice(S::external_matrix()),
+            PoseidonVariant::Poseidon2 => S::internal_matrices(),
+        }
+    }
+}
+
+/// A single matrix as a slice of matrices, still borrowed if it was.
+fn matrix_as_slice<F: Clone, const T: usize>(
+    matrix: Cow<'static, Mds<F, T>>,
+) -> Cow<'static, [Mds<F, T>]> {
+    match matrix {
+        Cow::Borrowed(matrix) => Cow::Borrowed(std::slice::from_ref(matrix)),
+        Cow::Owned(matrix) => Cow::Owned(vec![matrix]),
+    }
+}
+
+/// The ordering of the rate and capacity elements within a sponge state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateEndianness {
+    /// The rate is `state[..RATE]`, followed by the capacity element in `state[RATE]`.
+    Little,
+    /// The capacity element is `state[0]`, followed by the rate in `state[1..]`.
+    Big,
+}
+
+impl StateEndianness {
+    /// The index of the capacity element in a state of width `t`.
+    pub fn capacity_index(self, t: usize) -> usize {
+        match self {
+            StateEndianness::Little => t - 1,
+            StateEndianness::Big => 0,
+        }
+    }
+
+    /// The index of the `i`th rate element in a state of width `t`.
+    pub fn rate_index(self, t: usize, i: usize) -> usize {
+        debug_assert!(i < t - 1);
+        match self {
+            StateEndianness::Little => i,
+            StateEndianness::Big => i + 1,
+        }
+    }
+
+    /// Adds `words`, in order, to the rate elements of `state`, leaving the capacity
+    /// element as it is.
+    pub fn add_to_rate<V: Clone + ops::Add<Output = V>, const T: usize>(
+        self,
+        state: &mut [V; T],
+        words: impl IntoIterator<Item = V>,
+    ) {
+        for (i, word) in words.into_iter().enumerate() {
+            let index = self.rate_index(T, i);
+            state[index] = state[index].clone() + word;
+        }
+    }
 }
 
 // /// Generates `(round_constants, mds, mds^-1)` corresponding to this specification.
@@ -86,74 +445,222 @@ pub trait Spec<F: FromUniformBytes<64> + Ord, const T: usize, const RATE: usize>
 //     (round_constants, mds, mds_inv)
 // }
 
-pub fn mat_mul<F: FromUniformBytes<64> + Ord, const T: usize>(
+/// Raises `val` to the power `alpha` by square-and-multiply.
+///
+/// This is generic so that the chip's gates (over expressions) and every S-box
+/// evaluation (over field elements) go through the same multiplication chain. For
+/// `alpha` of 3 or 5 the chain is the shortest one.
+pub fn sbox<T: Clone + ops::Mul<Output = T>>(val: T, alpha: u64) -> T {
+    assert!(alpha > 0, "S-box exponent must be positive");
+    let mut acc = val.clone();
+    for bit in (0..63 - alpha.leading_zeros()).rev() {
+        acc = acc.clone() * acc;
+        if (alpha >> bit) & 1 == 1 {
+            acc = acc * val.clone();
+        }
+    }
+    acc
+}
+
+/// Multiplies `current_state` by `mat`, on the stack.
+pub fn mat_mul<F: Field, const T: usize>(current_state: &mut State<F, T>, mat: &Mds<F, T>) {
+    let state = *current_state;
+    for (output, m_i) in current_state.iter_mut().zip(mat.iter()) {
+        *output = m_i
+            .iter()
+            .zip(state.iter())
+            .fold(F::ZERO, |acc, (m_ij, s_j)| acc + *m_ij * s_j);
+    }
+}
+
+/// Multiplies `current_state` by `mat` like [`mat_mul`], but four rows at a time.
+///
+/// The rows' inner products are independent, so keeping four accumulators live lets
+/// their field multiplications overlap instead of each waiting on the previous row.
+/// The field arithmetic itself is left to `F`, as the limbs of a generic `F` are not
+/// visible here.
+#[cfg(feature = "batched-mds")]
+pub fn mat_mul_batched<F: FromUniformBytes<64> + Ord, const T: usize>(
     current_state: &mut State<F, T>,
     mat: &Mds<F, T>,
 ) {
-    let new_state = mat.iter().map(|m_i| {
-        current_state
+    let state = *current_state;
+    let mut rows = mat.chunks_exact(4);
+    let mut outputs = current_state.chunks_exact_mut(4);
+    for (m, output) in (&mut rows).zip(&mut outputs) {
+        let mut acc = [F::ZERO; 4];
+        for (j, s_j) in state.iter().enumerate() {
+            acc[0] += m[0][j] * s_j;
+            acc[1] += m[1][j] * s_j;
+            acc[2] += m[2][j] * s_j;
+            acc[3] += m[3][j] * s_j;
+        }
+        output.copy_from_slice(&acc);
+    }
+    for (m_i, output) in rows.remainder().iter().zip(outputs.into_remainder()) {
+        *output = m_i
             .iter()
-            .enumerate()
-            .fold(F::ZERO, |acc, (j, r_j)| acc + m_i[j] * r_j)
-    }).collect::<Vec<_>>().try_into().unwrap();
-    *current_state = new_state;
+            .zip(state.iter())
+            .fold(F::ZERO, |acc, (m_ij, s_j)| acc + *m_ij * s_j);
+    }
 }
 
+// The matrix multiplication used by `permute`.
+#[cfg(not(feature = "batched-mds"))]
+use self::mat_mul as linear_layer;
+#[cfg(feature = "batched-mds")]
+use self::mat_mul_batched as linear_layer;
+
 /// Runs the Poseidon permutation on the given state.
-pub(crate) fn permute<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>(
+///
+/// [`State`] is an alias of `[F; T]`, so a plain array can be permuted in place
+/// without any conversion.
+pub fn permute<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>(
     current_state: &mut State<F, T>
 ) {
+    // Linear layer at beginning
+    if S::variant().has_first_layer() {
+        linear_layer(current_state, &S::external_matrix());
+    }
+    permute_rounds::<F, S, T, RATE>(current_state);
+}
 
+/// Runs the rounds of the permutation on the given state, without the first layer.
+fn permute_rounds<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>(
+    current_state: &mut State<F, T>
+) {
     let r_f = S::full_rounds() / 2;
     let r_p = S::partial_rounds();
     let total_rounds = 2*r_f + r_p;
-    let (round_constants, mat_internal, mat_external) = S::constants();
-    
-    // Linear layer at beginning
-    mat_mul(current_state, &mat_external);
+    let round_constants = S::round_constants();
+    let mat_internal = S::variant().partial_round_matrices::<F, S, T, RATE>();
+    let mat_external = S::external_matrix();
 
     for rc in round_constants.iter().take(r_f) {
         for (i, state_elem) in current_state.iter_mut().enumerate() {
             state_elem.add_assign(&rc[i]);
             *state_elem = S::sbox(*state_elem);
         }
-        mat_mul(current_state, &mat_external);
+        linear_layer(current_state, &mat_external);
     }
     let p_end = r_f + r_p;
-    for rc in round_constants.iter().take(p_end).skip(r_f) {
+    for (i, rc) in round_constants.iter().take(p_end).skip(r_f).enumerate() {
         current_state[0].add_assign(&rc[0]);
         current_state[0] = S::sbox(current_state[0]);
-        mat_mul(current_state, &mat_internal);
+        linear_layer(current_state, &mat_internal[i % mat_internal.len()]);
     }
     
-    for rc in round_constants.iter().take(total_rounds).skip(p_end) {
+    for (round, rc) in round_constants.iter().enumerate().take(total_rounds).skip(p_end) {
         for (i, state_elem) in current_state.iter_mut().enumerate() {
             state_elem.add_assign(&rc[i]);
             *state_elem = S::sbox(*state_elem);
         }
-        mat_mul(current_state, &mat_external);
+        if !(S::skip_last_mds() && round == total_rounds - 1) {
+            linear_layer(current_state, &mat_external);
+        }
     }
 }
 
+/// A constant initial state, or IV, with the first layer of the permutation already
+/// applied, so that permuting it skips that layer.
+///
+/// The first layer is linear, so the first block of a sponge started from the IV is
+/// absorbed as `M_E·IV + M_E·block`: [`PreparedIV::absorb_block`] only multiplies the
+/// block. A spec without a first layer, as in Poseidon, leaves the IV as it is.
+///
+/// This is a native optimization only: in [`Pow5Chip`], the first-layer row is what
+/// binds the witnessed block to the permuted state, so a circuit has no row to save.
+///
+/// [`Pow5Chip`]: crate::circuit::pow5::Pow5Chip
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PreparedIV<F, const T: usize>(State<F, T>);
+
+impl<F: FromUniformBytes<64> + Ord, const T: usize> PreparedIV<F, T> {
+    /// Applies the first layer of the spec `S` to `iv`.
+    pub fn new<S: Spec<F, T, RATE>, const RATE: usize>(mut iv: State<F, T>) -> Self {
+        if S::variant().has_first_layer() {
+            linear_layer(&mut iv, &S::external_matrix());
+        }
+        PreparedIV(iv)
+    }
+
+    /// The IV after the first layer.
+    pub fn state(&self) -> &State<F, T> {
+        &self.0
+    }
+
+    /// Permutes the IV with the spec `S`, which it must have been prepared for, running
+    /// only the rounds.
+    pub fn permute<S: Spec<F, T, RATE>, const RATE: usize>(&self) -> State<F, T> {
+        let mut state = self.0;
+        permute_rounds::<F, S, T, RATE>(&mut state);
+        state
+    }
+
+    /// Absorbs `block` into the IV and permutes it with the spec `S`, which the IV must
+    /// have been prepared for, as [`absorb_block`] does for the unprepared IV.
+    ///
+    /// Only the block goes through the first layer; its image is added to the prepared
+    /// IV before the rounds.
+    pub fn absorb_block<S: Spec<F, T, RATE>, const RATE: usize>(
+        &self,
+        block: &[F; RATE],
+    ) -> State<F, T> {
+        let mut words = [F::ZERO; T];
+        S::state_endianness().add_to_rate(&mut words, block.iter().copied());
+        if S::variant().has_first_layer() {
+            linear_layer(&mut words, &S::external_matrix());
+        }
+        let mut state = self.0;
+        for (word, image) in state.iter_mut().zip(words) {
+            *word += image;
+        }
+        permute_rounds::<F, S, T, RATE>(&mut state);
+        state
+    }
+}
+
+/// Absorbs one block of the sponge: adds `block`, followed by `padding`, to the rate
+/// elements of `state`, then permutes it.
+///
+/// This is what the circuit's `add_input` and `permute` compute, so a sponge driven by
+/// it block by block precomputes the circuit's witnesses.
+///
+/// # Panics
+///
+/// Panics if `block` and `padding` are not `RATE` words together.
+pub fn absorb_block<
+    F: FromUniformBytes<64> + Ord,
+    S: Spec<F, T, RATE>,
+    const T: usize,
+    const RATE: usize,
+>(
+    state: &mut State<F, T>,
+    block: &[F],
+    padding: impl IntoIterator<Item = F>,
+) {
+    let words: Vec<F> = block.iter().copied().chain(padding).collect();
+    assert_eq!(words.len(), RATE, "a padded block must be RATE words");
+    S::state_endianness().add_to_rate(state, words);
+    permute::<F, S, T, RATE>(state);
+}
+
 fn poseidon_sponge<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>(
     state: &mut State<F, T>,
     input: Option<&Absorbing<F, RATE>>,
-    mds_matrix: &Mds<F, T>,
-    round_constants: &[[F; T]],
 ) -> Squeezing<F, RATE> {
-    if let Some(Absorbing(input)) = input {
-        // `Iterator::zip` short-circuits when one iterator completes, so this will only
-        // mutate the rate portion of the state.
-        for (word, value) in state.iter_mut().zip(input.iter()) {
-            *word += value.expect("poseidon_sponge is called with a padded input");
+    let endianness = S::state_endianness();
+    match input {
+        Some(Absorbing(input)) => {
+            let block = input.map(|value| value.expect("poseidon_sponge is called with a padded input"));
+            absorb_block::<F, S, T, RATE>(state, &block, iter::empty());
         }
+        None => permute::<F, S, T, RATE>(state),
     }
 
-    permute::<F, S, T, RATE>(state);
-
     let mut output = [None; RATE];
-    for (word, value) in output.iter_mut().zip(state.iter()) {
-        *word = Some(*value);
+    for (i, word) in output.iter_mut().enumerate() {
+        *word = Some(state[endianness.rate_index(T, i)]);
     }
     Squeezing(output)
 }
@@ -178,6 +685,23 @@ pub struct Squeezing<F, const RATE: usize>(pub(crate) SpongeRate<F, RATE>);
 impl<F, const RATE: usize> SpongeMode for Absorbing<F, RATE> {}
 impl<F, const RATE: usize> SpongeMode for Squeezing<F, RATE> {}
 
+/// A sponge mode whose buffered words can be reached, e.g. to be zeroized.
+pub(crate) trait BufferedWords<F> {
+    fn words_mut(&mut self) -> &mut [Option<F>];
+}
+
+impl<F, const RATE: usize> BufferedWords<F> for Absorbing<F, RATE> {
+    fn words_mut(&mut self) -> &mut [Option<F>] {
+        &mut self.0
+    }
+}
+
+impl<F, const RATE: usize> BufferedWords<F> for Squeezing<F, RATE> {
+    fn words_mut(&mut self) -> &mut [Option<F>] {
+        &mut self.0
+    }
+}
+
 impl<F: fmt::Debug, const RATE: usize> Absorbing<F, RATE> {
     pub(crate) fn init_with(val: F) -> Self {
         Self(
@@ -195,14 +719,12 @@ impl<F: fmt::Debug, const RATE: usize> Absorbing<F, RATE> {
 pub(crate) struct Sponge<
     F: FromUniformBytes<64> + Ord,
     S: Spec<F, T, RATE>,
-    M: SpongeMode,
+    M: SpongeMode + BufferedWords<F>,
     const T: usize,
     const RATE: usize,
 > {
     mode: M,
     state: State<F, T>,
-    mds_matrix: Mds<F, T>,
-    round_constants: Vec<[F; T]>,
     layout: usize,
     _marker: PhantomData<S>,
 }
@@ -212,25 +734,21 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const R
 {
     /// Constructs a new sponge for the given Poseidon specification.
     pub(crate) fn new(initial_capacity_element: F, layout: usize) -> Self {
-        let (round_constants, mds_matrix, _) = S::constants();
-
         let mode = Absorbing([None; RATE]);
         let mut state = [F::ZERO; T];
-        state[RATE] = initial_capacity_element;
+        state[S::state_endianness().capacity_index(T)] = initial_capacity_element;
 
         Sponge {
             mode,
             state,
-            mds_matrix,
             layout,
-            round_constants,
             _marker: PhantomData::default(),
         }
     }
 
     /// add the capacity into current position of output
     pub(crate) fn update_capacity(&mut self, capacity_element: F) {
-        self.state[(RATE + self.layout) % T] += capacity_element;
+        self.state[(S::state_endianness().capacity_index(T) + self.layout) % T] += capacity_element;
     }
     /// Absorbs an element into the sponge.
     pub(crate) fn absorb(&mut self, value: F) {
@@ -242,29 +760,21 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const R
         }
 
         // We've already absorbed as many elements as we can
-        let _ = poseidon_sponge::<F, S, T, RATE>(
-            &mut self.state,
-            Some(&self.mode),
-            &self.mds_matrix,
-            &self.round_constants,
-        );
+        let _ = poseidon_sponge::<F, S, T, RATE>(&mut self.state, Some(&self.mode));
         self.mode = Absorbing::init_with(value);
     }
 
     /// Transitions the sponge into its squeezing state.
+    ///
+    /// If nothing was absorbed, as for an empty message, the state is permuted as is,
+    /// which is equivalent to absorbing a block of zeros.
     pub(crate) fn finish_absorbing(mut self) -> Sponge<F, S, Squeezing<F, RATE>, T, RATE> {
-        let mode = poseidon_sponge::<F, S, T, RATE>(
-            &mut self.state,
-            Some(&self.mode),
-            &self.mds_matrix,
-            &self.round_constants,
-        );
+        let input = Some(&self.mode).filter(|mode| mode.0.iter().any(Option::is_some));
+        let mode = poseidon_sponge::<F, S, T, RATE>(&mut self.state, input);
 
         Sponge {
             mode,
             state: self.state,
-            mds_matrix: self.mds_matrix,
-            round_constants: self.round_constants,
             layout: self.layout,
             _marker: PhantomData,
         }
@@ -284,12 +794,7 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const R
             }
 
             // We've already squeezed out all available elements
-            self.mode = poseidon_sponge::<F, S, T, RATE>(
-                &mut self.state,
-                None,
-                &self.mds_matrix,
-                &self.round_constants,
-            );
+            self.mode = poseidon_sponge::<F, S, T, RATE>(&mut self.state, None);
         }
     }
 }
@@ -306,8 +811,29 @@ pub trait Domain<F: FromUniformBytes<64> + Ord, const RATE: usize> {
     fn initial_capacity_element() -> F;
 
     /// Returns the padding to be appended to the input.
+    ///
+    /// `input_len` counts the message only, so a domain with a [`prefix`] must pad
+    /// `input_len + 1` words.
+    ///
+    /// [`prefix`]: Domain::prefix
     fn padding(input_len: usize) -> Self::Padding;
 
+    /// A word absorbed ahead of every message, separating this domain from others that
+    /// share its capacity element, without resorting to a keyed hash.
+    ///
+    /// Keyed hashers replace the domain's initial state, and do not absorb it.
+    fn prefix() -> Option<F> {
+        None
+    }
+
+    /// Checks that a message of `len` words can be hashed in this domain, before any of
+    /// it is absorbed.
+    ///
+    /// Every length is valid by default.
+    fn validate_len(_len: usize) -> Result<(), DomainError> {
+        Ok(())
+    }
+
     /// Set the position of inputs in state: how many fields
     /// of offset the first input should be put in, for iden3,
     /// inputs are right aligned in the state array
@@ -316,6 +842,21 @@ pub trait Domain<F: FromUniformBytes<64> + Ord, const RATE: usize> {
     }
 }
 
+/// Why a message cannot be hashed in a [`Domain`].
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum DomainError {
+    /// The domain only hashes messages of one length.
+    #[error("{domain} hashes messages of {expected} words, but this one has {actual}")]
+    WrongLength {
+        /// The name of the domain.
+        domain: String,
+        /// The length the domain hashes.
+        expected: usize,
+        /// The length of the message.
+        actual: usize,
+    },
+}
+
 /// A Poseidon hash function used with variable input length.
 ///
 /// Domain specified in [ePrint 2019/458 section 4.2](https://eprint.iacr.org/2019/458.pdf).
@@ -363,6 +904,18 @@ impl<F: FromUniformBytes<64> + Ord, const RATE: usize, const L: usize> Domain<F,
         F::from_u128((L as u128) << 64)
     }
 
+    fn validate_len(len: usize) -> Result<(), DomainError> {
+        if len == L {
+            Ok(())
+        } else {
+            Err(DomainError::WrongLength {
+                domain: <Self as Domain<F, RATE>>::name(),
+                expected: L,
+                actual: len,
+            })
+        }
+    }
+
     fn padding(input_len: usize) -> Self::Padding {
         assert_eq!(input_len, L);
         // For constant-input-length hashing, we pad the input with zeroes to a multiple
@@ -404,10 +957,23 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, con
 impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
     Hash<F, S, D, T, RATE>
 {
-    /// Initializes a new hasher.
+    /// Initializes a new hasher, which has absorbed the domain's [`Domain::prefix`].
     pub fn init() -> Self {
+        let mut sponge = Sponge::new(D::initial_capacity_element(), D::layout(T));
+        if let Some(prefix) = D::prefix() {
+            sponge.absorb(prefix);
+        }
+        Hash {
+            sponge,
+            _domain: PhantomData::default(),
+        }
+    }
+
+    /// Initializes a keyed hasher, whose capacity element is `key` instead of the
+    /// domain's initial capacity element.
+    pub fn init_with_key(key: F) -> Self {
         Hash {
-            sponge: Sponge::new(D::initial_capacity_element(), D::layout(T)),
+            sponge: Sponge::new(key, D::layout(T)),
             _domain: PhantomData::default(),
         }
     }
@@ -415,13 +981,45 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, con
     pub fn permute(&self, state: &mut [F; T]) {
         permute::<F, S, T, RATE>(state);
     }
+
+    /// Hashes `message` followed by the domain's [`Domain::padding`], e.g. in a domain
+    /// of the caller's own.
+    pub fn hash_padded(mut self, message: &[F]) -> F {
+        for value in message.iter().copied().chain(D::padding(message.len())) {
+            self.sponge.absorb(value);
+        }
+        self.sponge.finish_absorbing().squeeze()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>
+    Hash<F, S, VariableLength, T, RATE>
+{
+    /// Hashes any number of `children`, e.g. the hashes of the children of a node in a
+    /// wide tree.
+    ///
+    /// [`VariableLength`] padding alone does not tell a message that fills its last
+    /// block from one padded up to it, so the number of children is absorbed first;
+    /// it fixes the length of the message, which makes the padding unambiguous.
+    pub fn combine_n(self, children: &[F]) -> F {
+        let message: Vec<F> = iter::once(F::from(children.len() as u64))
+            .chain(children.iter().copied())
+            .collect();
+        self.hash_padded(&message)
+    }
 }
 
 impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize, const L: usize>
     Hash<F, S, ConstantLength<L>, T, RATE>
 {
     /// Hashes the given input.
-    pub fn hash(mut self, message: [F; L], domain: F) -> F {
+    pub fn hash(self, message: [F; L], domain: F) -> F {
+        self.hash_with_state(message, domain).0
+    }
+
+    /// Hashes the given input, and also returns the sponge state the output was
+    /// squeezed from.
+    pub fn hash_with_state(mut self, message: [F; L], domain: F) -> (F, State<F, T>) {
         self.sponge.update_capacity(domain);
         for value in message
             .into_iter()
@@ -429,7 +1027,30 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const R
         {
             self.sponge.absorb(value);
         }
-        self.sponge.finish_absorbing().squeeze()
+        let mut sponge = self.sponge.finish_absorbing();
+        let output = sponge.squeeze();
+        (output, sponge.state)
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize, const L: usize>
+    Hash<F, S, ConstantLength<L>, T, RATE>
+{
+    /// Hashes the given input and checks, in constant time, that the output is `tag`,
+    /// e.g. to verify a MAC computed by a keyed hasher.
+    pub fn verify(self, message: [F; L], domain: F, tag: &F) -> subtle::Choice {
+        ct_eq(&self.hash(message, domain), tag)
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const RATE: usize>
+    Hash<F, S, ConstantLength<2>, T, RATE>
+{
+    /// Hashes a 256-bit word, given as 32 little-endian bytes, as its two
+    /// [`u256_limbs`].
+    pub fn hash_u256(self, bytes: [u8; 32], domain: F) -> F {
+        self.hash(u256_limbs(bytes), domain)
     }
 }
 
@@ -451,6 +1072,968 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, const T: usize, const R
     }
 }
 
+/// A Fiat-Shamir transcript over a duplex Poseidon sponge.
+///
+/// Before a challenge is squeezed, the pending block is padded with a one followed by
+/// zeros, i.e. 10* padding, and absorbed; a full pending block is absorbed first, and
+/// the padding takes a block of its own. Messages differing only in trailing zeros
+/// thus yield different challenges. The challenge is then absorbed back as the first
+/// word of the next block, so that every later challenge depends on it.
+pub struct Transcript<
+    F: FromUniformBytes<64> + Ord,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    state: State<F, T>,
+    /// At most `RATE` words, within the capacity reserved by [`Self::init`], so that the
+    /// buffer is never reallocated and no copies of its words are left behind.
+    pending: Vec<F>,
+    _marker: PhantomData<(S, D)>,
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    fmt::Debug for Transcript<F, S, D, T, RATE>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcript")
+            .field("width", &T)
+            .field("rate", &RATE)
+            .field("domain", &D::name())
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    Transcript<F, S, D, T, RATE>
+{
+    /// Initializes an empty transcript.
+    pub fn init() -> Self {
+        let mut state = [F::ZERO; T];
+        state[S::state_endianness().capacity_index(T)] = D::initial_capacity_element();
+        Transcript {
+            state,
+            pending: Vec::with_capacity(RATE),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Absorbs the given prover message.
+    pub fn absorb(&mut self, message: &[F]) {
+        for value in message {
+            if self.pending.len() == RATE {
+                self.absorb_block();
+            }
+            self.pending.push(*value);
+        }
+    }
+
+    /// Squeezes a challenge, which is absorbed back into the transcript.
+    pub fn squeeze_challenge(&mut self) -> F {
+        if self.pending.len() == RATE {
+            self.absorb_block();
+        }
+        self.pending.push(F::ONE);
+        self.pending.resize(RATE, F::ZERO);
+        self.absorb_block();
+        let challenge = self.state[S::state_endianness().rate_index(T, 0)];
+        self.pending.push(challenge);
+        challenge
+    }
+
+    fn absorb_block(&mut self) {
+        absorb_block::<F, S, T, RATE>(&mut self.state, &self.pending, iter::empty());
+        clear_words(&mut self.pending);
+    }
+}
+
+/// A duplex sponge, whose absorbs and squeezes may be interleaved in any order.
+///
+/// Absorbed words are buffered until a block of `RATE` words is full, at which point it
+/// is added to the state and permuted. Squeezing zero-pads and absorbs the buffered
+/// block, then returns the rate words in order, permuting again once they run out.
+/// Absorbing after a squeeze discards any unused squeezed words.
+///
+/// [`Duplex::set_rates`] lowers the number of words absorbed or squeezed per block, e.g.
+/// to squeeze at a smaller rate than was absorbed at for domain separation.
+pub struct Duplex<
+    F: FromUniformBytes<64> + Ord,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    state: State<F, T>,
+    mode: DuplexMode<F, F>,
+    rates: DuplexRates,
+    _marker: PhantomData<(S, D)>,
+}
+
+/// The number of rate words a duplex sponge touches per block, in each phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct DuplexRates {
+    /// The words absorbed into a block before it is permuted.
+    pub(crate) absorb: usize,
+    /// The words squeezed from a block before the state is permuted again.
+    pub(crate) squeeze: usize,
+}
+
+impl DuplexRates {
+    /// Rates using every rate word of the state.
+    pub(crate) fn full(rate: usize) -> Self {
+        DuplexRates {
+            absorb: rate,
+            squeeze: rate,
+        }
+    }
+
+    /// Checks and sets new rates, for a duplex of rate `rate` which is in `mode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either rate is zero or above `rate`, or if `mode` has buffered more
+    /// words for absorption than the new absorb rate.
+    pub(crate) fn set<A, Q>(
+        &mut self,
+        rate: usize,
+        absorb: usize,
+        squeeze: usize,
+        mode: &DuplexMode<A, Q>,
+    ) {
+        assert!(
+            (1..=rate).contains(&absorb) && (1..=rate).contains(&squeeze),
+            "duplex rates must be between 1 and {}",
+            rate
+        );
+        if let DuplexMode::Absorbing(pending) = mode {
+            assert!(
+                pending.len() <= absorb,
+                "{} words are already buffered, more than the absorb rate {}",
+                pending.len(),
+                absorb
+            );
+        }
+        *self = DuplexRates { absorb, squeeze };
+    }
+}
+
+/// The phase of a duplex sponge, with the words it has buffered.
+#[derive(Clone, Debug)]
+pub(crate) enum DuplexMode<A, Q> {
+    /// Words absorbed since the last permutation.
+    Absorbing(Vec<A>),
+    /// Squeezed words not yet returned, in order.
+    Squeezing(Vec<Q>),
+}
+
+impl<F: Field> DuplexMode<F, F> {
+    /// Empties the buffered words as [`clear_words`] does, and returns the buffer for
+    /// the next phase, so that switching phase neither drops stale words nor
+    /// reallocates.
+    fn take_cleared(&mut self) -> Vec<F> {
+        let (DuplexMode::Absorbing(words) | DuplexMode::Squeezing(words)) = self;
+        clear_words(words);
+        std::mem::take(words)
+    }
+}
+
+/// Zero-pads the buffered words `pending` into a block, and empties the buffer as
+/// [`clear_words`] does.
+fn pad_block<F: Field, const RATE: usize>(pending: &mut Vec<F>) -> Absorbing<F, RATE> {
+    pending.resize(RATE, F::ZERO);
+    let block = Absorbing(std::array::from_fn(|i| Some(pending[i])));
+    clear_words(pending);
+    block
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    fmt::Debug for Duplex<F, S, D, T, RATE>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Duplex")
+            .field("width", &T)
+            .field("rate", &RATE)
+            .field("domain", &D::name())
+            .field("rates", &self.rates)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    Duplex<F, S, D, T, RATE>
+{
+    /// Initializes an empty duplex sponge.
+    pub fn init() -> Self {
+        let mut state = [F::ZERO; T];
+        state[S::state_endianness().capacity_index(T)] = D::initial_capacity_element();
+        Duplex {
+            state,
+            mode: DuplexMode::Absorbing(Vec::with_capacity(RATE)),
+            rates: DuplexRates::full(RATE),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of words absorbed into each block, and squeezed from each block,
+    /// from now on. Both start at `RATE`; the rate words a block leaves out are not
+    /// touched.
+    ///
+    /// Squeezed words not yet returned keep the rate they were squeezed at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either rate is zero or above `RATE`, or if more words are buffered for
+    /// absorption than `absorb_rate`.
+    pub fn set_rates(&mut self, absorb_rate: usize, squeeze_rate: usize) {
+        self.rates.set(RATE, absorb_rate, squeeze_rate, &self.mode);
+    }
+
+    /// Absorbs the given words.
+    pub fn absorb(&mut self, words: &[F]) {
+        for word in words {
+            if let DuplexMode::Squeezing(_) = self.mode {
+                self.mode = DuplexMode::Absorbing(self.mode.take_cleared());
+            }
+            if let DuplexMode::Absorbing(pending) = &mut self.mode {
+                if pending.len() == self.rates.absorb {
+                    let block = pad_block(pending);
+                    poseidon_sponge::<F, S, T, RATE>(&mut self.state, Some(&block));
+                }
+                pending.push(*word);
+            }
+        }
+    }
+
+    /// Squeezes `n` words.
+    pub fn squeeze(&mut self, n: usize) -> Vec<F> {
+        (0..n)
+            .map(|_| {
+                let output = match &mut self.mode {
+                    DuplexMode::Absorbing(pending) => {
+                        let block = pad_block(pending);
+                        Some(poseidon_sponge::<F, S, T, RATE>(&mut self.state, Some(&block)))
+                    }
+                    DuplexMode::Squeezing(remaining) if remaining.is_empty() => {
+                        Some(poseidon_sponge::<F, S, T, RATE>(&mut self.state, None))
+                    }
+                    DuplexMode::Squeezing(_) => None,
+                };
+                if let Some(Squeezing(output)) = output {
+                    let mut words = self.mode.take_cleared();
+                    words.extend(output.iter().take(self.rates.squeeze).map(|word| word.unwrap()));
+                    self.mode = DuplexMode::Squeezing(words);
+                }
+                match &mut self.mode {
+                    DuplexMode::Squeezing(remaining) => remaining.remove(0),
+                    DuplexMode::Absorbing(_) => unreachable!("squeezing leaves the duplex squeezing"),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A deterministic stream of field elements, seeded by absorbing words into a [`Duplex`].
+///
+/// Each element is squeezed from the duplex, which permutes again whenever the squeezed
+/// rate words run out.
+pub struct PoseidonRng<
+    F: FromUniformBytes<64> + Ord,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    duplex: Duplex<F, S, D, T, RATE>,
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    fmt::Debug for PoseidonRng<F, S, D, T, RATE>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoseidonRng")
+            .field("duplex", &self.duplex)
+            .finish()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    PoseidonRng<F, S, D, T, RATE>
+{
+    /// Initializes a stream seeded by `seed`.
+    pub fn seed(seed: &[F]) -> Self {
+        let mut duplex = Duplex::init();
+        duplex.absorb(seed);
+        PoseidonRng { duplex }
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, T, RATE>, D: Domain<F, RATE>, const T: usize, const RATE: usize>
+    Iterator for PoseidonRng<F, S, D, T, RATE>
+{
+    type Item = F;
+
+    /// Returns the next element of the stream, which never ends.
+    fn next(&mut self) -> Option<F> {
+        self.duplex.squeeze(1).pop()
+    }
+}
+
+/// Compares two hash outputs in constant time, through their canonical encodings.
+///
+/// Comparing an output to a secret, such as the tag of a keyed [`Hash`] used as a MAC,
+/// is security-sensitive: `==` may return at the first differing limb, leaking through
+/// its timing how much of a forged tag is right. Comparisons of public values, such as
+/// a digest against a circuit's instance, need not use this.
+#[cfg(feature = "subtle")]
+pub fn ct_eq<F: PrimeField>(a: &F, b: &F) -> subtle::Choice {
+    use subtle::ConstantTimeEq;
+
+    a.to_repr().as_ref().ct_eq(b.to_repr().as_ref())
+}
+
+/// Overwrites `words` with zeros, in a way the compiler may not elide even if the
+/// words are never read again.
+///
+/// This is how the types of this module holding secret state, such as a keyed [`Hash`],
+/// zeroize it; it can also be applied to a bare [`State`].
+///
+/// Those types zeroize the state and buffers they own, on drop and whenever a buffer is
+/// emptied or replaced. Copies of words made on the stack while permuting or padding a
+/// block are not zeroized.
+#[cfg(feature = "zeroize")]
+pub fn zeroize_words<F: Field>(words: &mut [F]) {
+    for word in words.iter_mut() {
+        // SAFETY: `word` is a valid, aligned and exclusive reference, and `F` is `Copy`,
+        // so overwriting it drops nothing.
+        unsafe { std::ptr::write_volatile(word, F::ZERO) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Zeroizes buffered words, and then empties their slots.
+///
+/// Empty slots are zeroized too: taking a word out of a slot only marks it empty, and
+/// leaves the word's bytes in place.
+#[cfg(feature = "zeroize")]
+fn zeroize_buffer<F: Field>(words: &mut [Option<F>]) {
+    for word in words.iter_mut() {
+        // SAFETY: as in `zeroize_words`, with `Option<F>` also `Copy`.
+        unsafe { std::ptr::write_volatile(word, Some(F::ZERO)) };
+        *word = None;
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Zeroizes a vector of words, including any spare capacity left over from words it
+/// held before, and then empties it.
+#[cfg(feature = "zeroize")]
+fn zeroize_vec<F: Field>(words: &mut Vec<F>) {
+    words.resize(words.capacity(), F::ZERO);
+    zeroize_words(words);
+    words.clear();
+}
+
+/// Empties a buffer of words, zeroizing them first with the `zeroize` feature, so that
+/// a buffer that is reused or dropped afterwards holds no stale words.
+fn clear_words<F: Field>(words: &mut Vec<F>) {
+    #[cfg(feature = "zeroize")]
+    zeroize_vec(words);
+    #[cfg(not(feature = "zeroize"))]
+    words.clear();
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroize_impls {
+    use zeroize::{Zeroize, ZeroizeOnDrop};
+
+    use super::{
+        zeroize_buffer, zeroize_vec, zeroize_words, BufferedWords, Domain, Duplex, DuplexMode,
+        Hash, PoseidonRng, Spec, Sponge, SpongeMode, Transcript,
+    };
+    use ff::FromUniformBytes;
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            M: SpongeMode + BufferedWords<F>,
+            const T: usize,
+            const RATE: usize,
+        > Drop for Sponge<F, S, M, T, RATE>
+    {
+        fn drop(&mut self) {
+            zeroize_words(&mut self.state);
+            zeroize_buffer(self.mode.words_mut());
+        }
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Zeroize for Hash<F, S, D, T, RATE>
+    {
+        fn zeroize(&mut self) {
+            zeroize_words(&mut self.sponge.state);
+            zeroize_buffer(self.sponge.mode.words_mut());
+        }
+    }
+
+    /// The sponge zeroizes itself on drop.
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > ZeroizeOnDrop for Hash<F, S, D, T, RATE>
+    {
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Zeroize for Transcript<F, S, D, T, RATE>
+    {
+        fn zeroize(&mut self) {
+            zeroize_words(&mut self.state);
+            zeroize_vec(&mut self.pending);
+        }
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Drop for Transcript<F, S, D, T, RATE>
+    {
+        fn drop(&mut self) {
+            self.zeroize();
+        }
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > ZeroizeOnDrop for Transcript<F, S, D, T, RATE>
+    {
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Zeroize for Duplex<F, S, D, T, RATE>
+    {
+        fn zeroize(&mut self) {
+            zeroize_words(&mut self.state);
+            match &mut self.mode {
+                DuplexMode::Absorbing(words) | DuplexMode::Squeezing(words) => zeroize_vec(words),
+            }
+        }
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Drop for Duplex<F, S, D, T, RATE>
+    {
+        fn drop(&mut self) {
+            self.zeroize();
+        }
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > ZeroizeOnDrop for Duplex<F, S, D, T, RATE>
+    {
+    }
+
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > Zeroize for PoseidonRng<F, S, D, T, RATE>
+    {
+        fn zeroize(&mut self) {
+            self.duplex.zeroize();
+        }
+    }
+
+    /// The duplex zeroizes itself on drop.
+    impl<
+            F: FromUniformBytes<64> + Ord,
+            S: Spec<F, T, RATE>,
+            D: Domain<F, RATE>,
+            const T: usize,
+            const RATE: usize,
+        > ZeroizeOnDrop for PoseidonRng<F, S, D, T, RATE>
+    {
+    }
+}
+
+/// Off-circuit permutations over arkworks fields, e.g. to compute witnesses with a
+/// codebase's existing field types.
+///
+/// An arkworks element is bridged to the `ff` field of a [`Spec`] with the same modulus
+/// through its canonical little-endian encoding, permuted there, and bridged back. The
+/// circuits stay `ff`-based.
+#[cfg(feature = "ark")]
+pub mod ark {
+    use ark_ff::{BigInteger, PrimeField as ArkPrimeField};
+    use ff::{FromUniformBytes, PrimeField};
+
+    use super::Spec;
+
+    /// Asserts that the arkworks field `A` and the `ff` field `F` have the same modulus,
+    /// by comparing their encodings of `-1`.
+    fn assert_same_modulus<A: ArkPrimeField, F: PrimeField<Repr = [u8; 32]>>() {
+        assert!(
+            to_ff::<A, F>(-A::one()) == -F::ONE,
+            "the arkworks and ff fields have different moduli"
+        );
+    }
+
+    /// Converts `value` to the `ff` field `F`, which must have the modulus of `A`.
+    pub fn to_ff<A: ArkPrimeField, F: PrimeField<Repr = [u8; 32]>>(value: A) -> F {
+        let mut repr = [0u8; 32];
+        let bytes = value.into_bigint().to_bytes_le();
+        repr[..bytes.len()].copy_from_slice(&bytes);
+        Option::from(F::from_repr(repr)).expect("the fields have the same modulus")
+    }
+
+    /// Converts `value` to the arkworks field `A`, which must have the modulus of `F`.
+    pub fn from_ff<A: ArkPrimeField, F: PrimeField<Repr = [u8; 32]>>(value: F) -> A {
+        A::from_le_bytes_mod_order(&value.to_repr())
+    }
+
+    /// Applies the permutation of the spec `S` over `F` to a state of arkworks words,
+    /// as [`super::permute`] does to `F` words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `A` and `F` have different moduli.
+    pub fn permute<
+        A: ArkPrimeField,
+        F: FromUniformBytes<64> + Ord + PrimeField<Repr = [u8; 32]>,
+        S: Spec<F, T, RATE>,
+        const T: usize,
+        const RATE: usize,
+    >(
+        state: &mut [A; T],
+    ) {
+        assert_same_modulus::<A, F>();
+        let mut words = state.map(to_ff::<A, F>);
+        super::permute::<F, S, T, RATE>(&mut words);
+        *state = words.map(from_ff::<A, F>);
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ark_bn254::Fr as ArkFr;
+        use halo2curves::bn256::Fr as Fp;
+
+        use super::{from_ff, permute, to_ff};
+        use crate::base::P128Pow5T3;
+
+        #[test]
+        fn permute_matches_ff_twin() {
+            let mut ark_state = [0u64, 1, 2].map(ArkFr::from);
+            permute::<_, Fp, P128Pow5T3<Fp>, 3, 2>(&mut ark_state);
+
+            let mut ff_state = [0u64, 1, 2].map(Fp::from);
+            crate::base::primitives::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut ff_state);
+            assert_eq!(ark_state.map(to_ff::<_, Fp>), ff_state);
+            assert_eq!(ff_state.map(from_ff::<ArkFr, _>), ark_state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod state_tests {
+    use std::iter;
+
+    use ff::Field;
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{absorb_block, permute, state_from_iter, PreparedIV, Spec, State};
+    use crate::base::test_vectors::{BigEndianSpec, Poseidon1Spec};
+    use crate::base::P128Pow5T3;
+
+    #[test]
+    fn round_constants_reject_non_canonical_reprs() {
+        use super::{ConstantError, RoundConstants};
+        use ff::PrimeField;
+
+        let reprs = [
+            [Fp::ONE, Fp::from(2), -Fp::ONE].map(|word| word.to_repr()),
+            [Fp::ZERO, Fp::from(3), Fp::from(4)].map(|word| word.to_repr()),
+        ];
+        let constants = RoundConstants::<Fp, 3>::from_reprs(&reprs).unwrap();
+        assert_eq!(constants.rounds()[0], [Fp::ONE, Fp::from(2), -Fp::ONE]);
+
+        // The bytes of `-1`, incremented, encode the modulus itself, which would wrap
+        // to zero if it were reduced.
+        let mut modulus = (-Fp::ONE).to_repr();
+        modulus[0] += 1;
+        let mut non_canonical = reprs;
+        non_canonical[1][2] = modulus;
+        assert_eq!(
+            RoundConstants::<Fp, 3>::from_reprs(&non_canonical),
+            Err(ConstantError::NonCanonical { round: 1, index: 2 })
+        );
+    }
+
+    #[test]
+    fn constant_length_validates_len() {
+        use super::{ConstantLength, Domain, DomainError, VariableLength};
+
+        assert_eq!(<ConstantLength<3> as Domain<Fp, 2>>::validate_len(3), Ok(()));
+        let err = <ConstantLength<3> as Domain<Fp, 2>>::validate_len(2).unwrap_err();
+        assert_eq!(
+            err,
+            DomainError::WrongLength {
+                domain: "ConstantLength<3>".to_string(),
+                expected: 3,
+                actual: 2,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "ConstantLength<3> hashes messages of 3 words, but this one has 2"
+        );
+        assert_eq!(<VariableLength as Domain<Fp, 2>>::validate_len(100), Ok(()));
+    }
+
+    #[test]
+    fn duplex_switches_rates() {
+        use super::{absorb_block, Domain, Duplex, VariableLength};
+        use crate::base::test_vectors::Width4Spec;
+
+        let mut duplex = Duplex::<_, Width4Spec, VariableLength, 4, 3>::init();
+        duplex.set_rates(2, 1);
+        duplex.absorb(&[1u64, 2, 3].map(Fp::from));
+        let squeezed = duplex.squeeze(3);
+
+        // Blocks of two words then single squeezed words, leaving the other rate words
+        // as they are.
+        let mut state = [Fp::ZERO; 4];
+        state[3] = <VariableLength as Domain<Fp, 3>>::initial_capacity_element();
+        absorb_block::<_, Width4Spec, 4, 3>(&mut state, &[Fp::from(1), Fp::from(2)], [Fp::ZERO]);
+        absorb_block::<_, Width4Spec, 4, 3>(&mut state, &[Fp::from(3)], [Fp::ZERO; 2]);
+        let mut expected = vec![state[0]];
+        for _ in 0..2 {
+            permute::<_, Width4Spec, 4, 3>(&mut state);
+            expected.push(state[0]);
+        }
+        assert_eq!(squeezed, expected);
+
+        let mut full = Duplex::<_, Width4Spec, VariableLength, 4, 3>::init();
+        full.absorb(&[1u64, 2, 3].map(Fp::from));
+        assert_ne!(full.squeeze(3), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplex rates must be between 1 and 3")]
+    fn duplex_rejects_rates_above_rate() {
+        use super::{Duplex, VariableLength};
+        use crate::base::test_vectors::Width4Spec;
+
+        Duplex::<Fp, Width4Spec, VariableLength, 4, 3>::init().set_rates(4, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "2 words are already buffered, more than the absorb rate 1")]
+    fn duplex_rejects_absorb_rate_below_buffer() {
+        use super::{Duplex, VariableLength};
+        use crate::base::test_vectors::Width4Spec;
+
+        let mut duplex = Duplex::<Fp, Width4Spec, VariableLength, 4, 3>::init();
+        duplex.absorb(&[Fp::ONE, Fp::ONE]);
+        duplex.set_rates(1, 1);
+    }
+
+    #[test]
+    fn transcript_pads_trailing_zeros_apart() {
+        use super::{Transcript, VariableLength};
+
+        let challenge = |message: &[Fp]| {
+            let mut transcript = Transcript::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init();
+            transcript.absorb(message);
+            transcript.squeeze_challenge()
+        };
+        let a = Fp::from(7);
+        assert_ne!(challenge(&[a]), challenge(&[a, Fp::ZERO]));
+        assert_ne!(challenge(&[]), challenge(&[Fp::ZERO]));
+        // A full block is absorbed before the padding, which takes a block of its own.
+        assert_ne!(challenge(&[a, Fp::ZERO]), challenge(&[a, Fp::ZERO, Fp::ZERO]));
+
+        let mut state = [Fp::ZERO; 3];
+        state[2] = <VariableLength as super::Domain<Fp, 2>>::initial_capacity_element();
+        absorb_block::<_, P128Pow5T3<Fp>, 3, 2>(&mut state, &[a, Fp::ZERO], iter::empty());
+        absorb_block::<_, P128Pow5T3<Fp>, 3, 2>(&mut state, &[Fp::ONE, Fp::ZERO], iter::empty());
+        assert_eq!(challenge(&[a, Fp::ZERO]), state[0]);
+    }
+
+    #[test]
+    fn state_from_array_and_iter() {
+        let from_array: State<Fp, 3> = [Fp::ZERO, Fp::ONE, Fp::from(2)];
+        let from_iter: State<Fp, 3> = state_from_iter((0..3).map(|i| Fp::from(i as u64)));
+        assert_eq!(from_array, from_iter);
+
+        let mut permuted = from_iter;
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut permuted);
+        let mut expected = from_array;
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+        assert_eq!(permuted, expected);
+        assert_ne!(permuted, from_array);
+    }
+
+    #[test]
+    fn permute_plain_array() {
+        let mut array = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut array);
+
+        let mut state: State<Fp, 3> = state_from_iter([1, 2, 3].map(Fp::from));
+        super::Hash::<_, P128Pow5T3<Fp>, super::VariableLength, 3, 2>::init().permute(&mut state);
+        assert_eq!(array, state);
+    }
+
+    #[test]
+    fn sbox_defaults_to_pow_alpha() {
+        let val = Fp::from(7);
+        assert_eq!(<P128Pow5T3<Fp> as Spec<Fp, 3, 2>>::alpha(), 5);
+        assert_eq!(P128Pow5T3::<Fp>::sbox(val), val.pow([5]));
+    }
+
+    #[test]
+    fn sbox_matches_pow() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        for _ in 0..100 {
+            let val = Fp::random(&mut rng);
+            for alpha in [1, 3, 5, 7, 11] {
+                assert_eq!(super::sbox(val, alpha), val.pow([alpha, 0, 0, 0]));
+            }
+        }
+    }
+
+    #[cfg(feature = "batched-mds")]
+    #[test]
+    fn batched_mat_mul_matches_scalar() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        fn check<const T: usize>(rng: &mut ChaCha8Rng) {
+            let mat: super::Mds<Fp, T> = [(); T].map(|_| [(); T].map(|_| Fp::random(&mut *rng)));
+            let state: State<Fp, T> = [(); T].map(|_| Fp::random(&mut *rng));
+
+            let mut scalar = state;
+            super::mat_mul(&mut scalar, &mat);
+            let mut batched = state;
+            super::mat_mul_batched(&mut batched, &mat);
+            assert_eq!(batched, scalar);
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        for _ in 0..20 {
+            // Below, at, and across the four-row batches.
+            check::<3>(&mut rng);
+            check::<4>(&mut rng);
+            check::<8>(&mut rng);
+            check::<9>(&mut rng);
+        }
+    }
+
+    #[test]
+    fn poseidon1_permute_matches_textbook_rounds() {
+        assert!(!Poseidon1Spec::is_poseidon2());
+
+        // Poseidon1 as specified: every round adds its constants, applies the S-box to
+        // the whole state in full rounds or to its first word in partial rounds, then
+        // multiplies by the one MDS matrix.
+        let r_f = Poseidon1Spec::full_rounds() / 2;
+        let r_p = Poseidon1Spec::partial_rounds();
+        let mds = Poseidon1Spec::external_matrix();
+        let mut expected = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        for (round, rc) in Poseidon1Spec::round_constants().iter().enumerate() {
+            let full = round < r_f || round >= r_f + r_p;
+            for (i, word) in expected.iter_mut().enumerate() {
+                *word += rc[i];
+                if full || i == 0 {
+                    *word = word.pow([5]);
+                }
+            }
+            expected = [0, 1, 2].map(|i| (0..3).map(|j| mds[i][j] * expected[j]).sum());
+        }
+
+        let mut state = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        permute::<_, Poseidon1Spec, 3, 2>(&mut state);
+        assert_eq!(state, expected);
+
+        let mut poseidon2 = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut poseidon2);
+        assert_ne!(state, poseidon2);
+    }
+
+    fn check_prepared_iv<S: Spec<Fp, 3, 2>>() {
+        let iv = [Fp::ZERO, Fp::ZERO, Fp::from(1 << 20)];
+        let prepared = PreparedIV::new::<S, 2>(iv);
+
+        let mut expected = iv;
+        permute::<_, S, 3, 2>(&mut expected);
+        assert_eq!(prepared.permute::<S, 2>(), expected);
+
+        // Hashing many messages from the same IV: each block only goes through the
+        // first layer once, on its own.
+        for block in [[1u64, 2], [3, 5], [0, 0]] {
+            let block = block.map(Fp::from);
+            let mut expected = iv;
+            absorb_block::<_, S, 3, 2>(&mut expected, &block, iter::empty());
+            assert_eq!(prepared.absorb_block::<S, 2>(&block), expected);
+        }
+    }
+
+    #[test]
+    fn prepared_iv_matches_permute() {
+        check_prepared_iv::<P128Pow5T3<Fp>>();
+        check_prepared_iv::<BigEndianSpec>();
+        // Without a first layer, preparing leaves the IV as it is.
+        check_prepared_iv::<Poseidon1Spec>();
+        let iv = [Fp::ZERO, Fp::ONE, Fp::from(2)];
+        assert_eq!(*PreparedIV::new::<Poseidon1Spec, 2>(iv).state(), iv);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn constants_is_built_from_accessors() {
+        let (round_constants, internal, external) = P128Pow5T3::<Fp>::constants();
+        assert_eq!(round_constants, P128Pow5T3::<Fp>::round_constants().into_owned());
+        assert_eq!(internal, *P128Pow5T3::<Fp>::internal_matrix());
+        assert_eq!(external, *P128Pow5T3::<Fp>::external_matrix());
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn ct_eq_agrees_with_eq() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        use super::{ct_eq, ConstantLength, Hash};
+
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        for _ in 0..100 {
+            let a = Fp::random(&mut rng);
+            // Pairs differing in a single low bit, as well as unrelated and equal ones.
+            for b in [a, a + Fp::ONE, Fp::random(&mut rng)] {
+                assert_eq!(bool::from(ct_eq(&a, &b)), a == b);
+            }
+        }
+
+        let key = Fp::from(42);
+        let message = [Fp::from(1), Fp::from(2)];
+        let hasher = || Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init_with_key(key);
+        let tag = hasher().hash(message, Fp::ZERO);
+        assert!(bool::from(hasher().verify(message, Fp::ZERO, &tag)));
+        assert!(!bool::from(hasher().verify(message, Fp::ZERO, &(tag + Fp::ONE))));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn keyed_state_is_zeroized() {
+        use zeroize::Zeroize;
+
+        use super::{ConstantLength, Duplex, DuplexMode, Hash, Transcript};
+
+        let mut hasher =
+            Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init_with_key(Fp::from(42));
+        hasher.sponge.absorb(Fp::from(7));
+        assert!(hasher.sponge.state.iter().any(|word| !bool::from(word.is_zero())));
+        assert!(hasher.sponge.mode.0[0].is_some());
+
+        // Dropping runs the same zeroization, but the words cannot be inspected after.
+        hasher.zeroize();
+        let sponge = &hasher.sponge;
+        assert!(sponge.state.iter().all(|word| bool::from(word.is_zero())));
+        assert!(sponge.mode.0.iter().all(Option::is_none));
+
+        let mut transcript = Transcript::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init();
+        transcript.absorb(&[Fp::from(1), Fp::from(2), Fp::from(3)]);
+        transcript.zeroize();
+        assert_eq!(transcript.state, [Fp::ZERO; 3]);
+        assert!(transcript.pending.is_empty());
+
+        // Switching phase reuses the one buffer rather than dropping or growing it.
+        fn buffer(duplex: &Duplex<Fp, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>) -> *const Fp {
+            let (DuplexMode::Absorbing(words) | DuplexMode::Squeezing(words)) = &duplex.mode;
+            words.as_ptr()
+        }
+        let mut duplex = Duplex::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init();
+        let initial = buffer(&duplex);
+        duplex.absorb(&[Fp::from(1), Fp::from(2), Fp::from(3)]);
+        duplex.squeeze(1);
+        duplex.absorb(&[Fp::from(4)]);
+        duplex.squeeze(3);
+        assert_eq!(buffer(&duplex), initial);
+        duplex.zeroize();
+        assert_eq!(duplex.state, [Fp::ZERO; 3]);
+    }
+
+    #[test]
+    fn internal_matrix_matches_dense() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        use super::{mat_mul, InternalMatrix};
+        use crate::base::bn256::fp::MAT_DIAG3_M_1;
+
+        let dense = P128Pow5T3::<Fp>::internal_matrix();
+        let internal = InternalMatrix::from_dense(&dense).unwrap();
+        assert_eq!(internal.diagonal, *MAT_DIAG3_M_1);
+        assert_eq!(internal.to_dense(), *dense);
+        assert_eq!(InternalMatrix::from_dense(&P128Pow5T3::<Fp>::external_matrix()), None);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..20 {
+            let state: State<Fp, 3> = [(); 3].map(|_| Fp::random(&mut rng));
+            let mut expected = state;
+            mat_mul(&mut expected, &dense);
+            assert_eq!(internal.mul_vec(&state), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 state words, got 2")]
+    fn state_from_short_iter() {
+        let _: State<Fp, 3> = state_from_iter([Fp::ZERO, Fp::ONE]);
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use ff::PrimeField;
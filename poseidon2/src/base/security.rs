@@ -0,0 +1,296 @@
+//! Checks of a [`Spec`]'s round counts against the known attacks on Poseidon.
+//!
+//! The bounds are those of the reference round-number script of the Poseidon and
+//! Poseidon2 papers, for an $x^\alpha$ S-box over a prime field, including the Groebner
+//! basis attack of <https://eprint.iacr.org/2023/537>. They are the bounds the attacks
+//! set, without the security margin the papers add on top (two full rounds, and 7.5%
+//! more partial rounds).
+
+use ff::FromUniformBytes;
+use num_bigint::BigUint;
+use num_traits::{Num, ToPrimitive};
+use thiserror::Error;
+
+use super::primitives::Spec;
+
+/// The partial rounds [`recommended_partial_rounds`] searches, exclusive, as the
+/// reference script does.
+const MAX_PARTIAL_ROUNDS: usize = 500;
+/// The full rounds [`recommended_partial_rounds`] searches, exclusive.
+const MAX_FULL_ROUNDS: usize = 100;
+
+/// Why a [`Spec`] falls short of a security level.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum SecurityError {
+    /// The S-box $x^\alpha$ is not a permutation of the field, as `alpha` shares a
+    /// factor with `p - 1`.
+    #[error("x^{alpha} is not a permutation of the field")]
+    NotAPermutation {
+        /// The exponent of the S-box.
+        alpha: u64,
+    },
+    /// An attack needs more full rounds, given the spec's partial rounds, than the spec
+    /// has.
+    #[error("{attack} attacks need {required} full rounds, but the spec has {full_rounds}")]
+    TooFewRounds {
+        /// The attack the rounds fall short of.
+        attack: &'static str,
+        /// The full rounds the attack needs.
+        required: usize,
+        /// The full rounds of the spec.
+        full_rounds: usize,
+    },
+    /// The Groebner basis attack of eprint 2023/537 costs fewer bits than targeted.
+    #[error("a Groebner basis attack costs 2^{cost_bits}, below 2^{target_bits}")]
+    GroebnerCost {
+        /// The log2 of the attack's cost.
+        cost_bits: usize,
+        /// The targeted security level.
+        target_bits: usize,
+    },
+}
+
+/// Checks that the round counts of `S` withstand the known attacks at a security
+/// level of `target_bits`.
+///
+/// The S-box exponent is [`Spec::alpha`], the one the chip constrains, and the field
+/// size is read from [`ff::PrimeField::MODULUS`].
+pub fn check_security<
+    F: FromUniformBytes<64> + Ord,
+    S: Spec<F, T, RATE>,
+    const T: usize,
+    const RATE: usize,
+>(
+    target_bits: usize,
+) -> Result<(), SecurityError> {
+    let alpha = S::alpha();
+
+    let modulus = BigUint::from_str_radix(F::MODULUS.trim_start_matches("0x"), 16)
+        .expect("MODULUS is a hex string");
+    let p_minus_1_mod_alpha = ((&modulus - 1u32) % alpha).to_u64().unwrap();
+    if gcd(alpha, p_minus_1_mod_alpha) != 1 {
+        return Err(SecurityError::NotAPermutation { alpha });
+    }
+
+    let log2_p = modulus.to_f64().unwrap().log2();
+    withstands_attacks(
+        T,
+        alpha,
+        log2_p,
+        target_bits,
+        S::full_rounds(),
+        S::partial_rounds(),
+    )
+}
+
+/// The number of full rounds [`recommended_partial_rounds`] goes with, margin included.
+pub fn recommended_full_rounds(
+    width: usize,
+    alpha: u64,
+    field_bits: usize,
+    security_bits: usize,
+) -> usize {
+    recommended_rounds(width, alpha, field_bits, security_bits).0
+}
+
+/// The number of partial rounds the Poseidon2 parameter-selection script picks for a
+/// permutation of `width` words with an $x^\alpha$ S-box, over a field of `field_bits`
+/// bits, at a security level of `security_bits`.
+///
+/// Like the script, this picks the round counts that withstand the attacks of
+/// [`check_security`] with the fewest S-boxes, `width` per full round and one per
+/// partial round, and adds its security margin: two more full rounds, and 7.5% more
+/// partial rounds, rounded up. [`recommended_full_rounds`] returns the full rounds of
+/// the same choice.
+pub fn recommended_partial_rounds(
+    width: usize,
+    alpha: u64,
+    field_bits: usize,
+    security_bits: usize,
+) -> usize {
+    recommended_rounds(width, alpha, field_bits, security_bits).1
+}
+
+/// The `(full, partial)` rounds of [`recommended_partial_rounds`].
+fn recommended_rounds(
+    width: usize,
+    alpha: u64,
+    field_bits: usize,
+    security_bits: usize,
+) -> (usize, usize) {
+    let mut best: Option<(usize, usize, usize)> = None;
+    for r_p in 1..MAX_PARTIAL_ROUNDS {
+        // Attacks only get harder with more full rounds, so the fewest that suffice
+        // are the cheapest for these partial rounds.
+        let r_f = (4..MAX_FULL_ROUNDS).step_by(2).find(|&r_f| {
+            withstands_attacks(width, alpha, field_bits as f64, security_bits, r_f, r_p).is_ok()
+        });
+        if let Some(r_f) = r_f {
+            let r_f = r_f + 2;
+            let r_p = (r_p as f64 * 1.075).ceil() as usize;
+            let cost = r_f * width + r_p;
+            if best.map_or(true, |(best_cost, best_r_f, _)| {
+                cost < best_cost || (cost == best_cost && r_f < best_r_f)
+            }) {
+                best = Some((cost, r_f, r_p));
+            }
+        }
+    }
+    let (_, r_f, r_p) = best.expect("some round counts withstand every attack");
+    (r_f, r_p)
+}
+
+/// Checks that `r_f` full and `r_p` partial rounds of a permutation of `t` words with
+/// an $x^\alpha$ S-box, over a field of `log2_p` bits, withstand the known attacks at
+/// a security level of `target_bits`.
+fn withstands_attacks(
+    t: usize,
+    alpha: u64,
+    log2_p: f64,
+    target_bits: usize,
+    r_f: usize,
+    r_p: usize,
+) -> Result<(), SecurityError> {
+    let m = target_bits as f64;
+    let t = t as f64;
+    let a = alpha as f64;
+    let r_p = r_p as f64;
+    let log_alpha = |x: f64| x.ln() / a.ln();
+
+    let statistical = if m <= (log2_p - (a - 1.0) / 2.0).floor() * (t + 1.0) {
+        6.0
+    } else {
+        10.0
+    };
+    let bounds = [
+        ("statistical", statistical),
+        (
+            "interpolation",
+            1.0 + (log_alpha(2.0) * m.min(log2_p.ceil())).ceil() + log_alpha(t).ceil() - r_p,
+        ),
+        ("Groebner basis", log_alpha(2.0) * m.min(log2_p) - r_p),
+        (
+            "Groebner basis",
+            t - 1.0 + log_alpha(2.0) * (m / (t + 1.0)).min(log2_p / 2.0) - r_p,
+        ),
+        (
+            "Groebner basis",
+            (t - 2.0 + m / (2.0 * a.log2()) - r_p) / (t - 1.0),
+        ),
+    ];
+    for (attack, bound) in bounds {
+        let required = bound.ceil().max(0.0) as usize;
+        if r_f < required {
+            return Err(SecurityError::TooFewRounds {
+                attack,
+                required,
+                full_rounds: r_f,
+            });
+        }
+    }
+
+    let r_f = r_f as f64;
+    let r_temp = (t / 3.0).floor();
+    let over = (r_f - 1.0) * t + r_p + r_temp + r_temp * (r_f / 2.0) + r_p + a;
+    let under = r_temp * (r_f / 2.0) + r_p + a;
+    let cost_bits = (2.0 * log2_binomial(over as u64, under as u64)).ceil() as usize;
+    if cost_bits < target_bits {
+        return Err(SecurityError::GroebnerCost {
+            cost_bits,
+            target_bits,
+        });
+    }
+
+    Ok(())
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The log2 of `n` choose `k`.
+fn log2_binomial(n: u64, k: u64) -> f64 {
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).log2()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{check_security, log2_binomial, SecurityError};
+    use crate::base::primitives::Spec;
+    use crate::base::test_vectors::test_spec;
+    use crate::base::P128Pow5T3;
+
+    test_spec! {
+        /// [`P128Pow5T3`] with the given round counts and S-box exponent.
+        struct RoundsSpec<const FULL: usize, const PARTIAL: usize, const ALPHA: u64>;
+        impl Spec<3, 2> {
+            full_rounds: FULL,
+            partial_rounds: PARTIAL,
+            alpha: ALPHA,
+        }
+    }
+
+    #[test]
+    fn p128pow5t3_is_secure_at_128_bits() {
+        assert_eq!(check_security::<_, P128Pow5T3<Fp>, 3, 2>(128), Ok(()));
+        assert!(check_security::<_, P128Pow5T3<Fp>, 3, 2>(256).is_err());
+    }
+
+    #[test]
+    fn weak_specs_are_rejected() {
+        assert_eq!(
+            check_security::<_, RoundsSpec<2, 4, 5>, 3, 2>(128),
+            Err(SecurityError::TooFewRounds {
+                attack: "statistical",
+                required: 6,
+                full_rounds: 2,
+            })
+        );
+        // Enough full rounds, but far too few partial ones to stop interpolation.
+        assert!(matches!(
+            check_security::<_, RoundsSpec<8, 4, 5>, 3, 2>(128),
+            Err(SecurityError::TooFewRounds {
+                attack: "interpolation",
+                ..
+            })
+        ));
+        // 3 divides p - 1 for BN254, so x^3 is not a bijection.
+        assert_eq!(
+            check_security::<_, RoundsSpec<8, 56, 3>, 3, 2>(128),
+            Err(SecurityError::NotAPermutation { alpha: 3 })
+        );
+    }
+
+    #[test]
+    fn recommended_rounds_match_p128pow5t3() {
+        use super::{recommended_full_rounds, recommended_partial_rounds};
+        use ff::PrimeField;
+
+        let field_bits = Fp::NUM_BITS as usize;
+        assert_eq!(
+            recommended_full_rounds(3, 5, field_bits, 128),
+            P128Pow5T3::<Fp>::full_rounds()
+        );
+        assert_eq!(
+            recommended_partial_rounds(3, 5, field_bits, 128),
+            P128Pow5T3::<Fp>::partial_rounds()
+        );
+        // Width 4 needs as many partial rounds as width 3 over BN254, and width 8 one
+        // more, as in the parameter table of the Poseidon2 paper.
+        assert_eq!(recommended_partial_rounds(4, 5, field_bits, 128), 56);
+        assert_eq!(recommended_partial_rounds(8, 5, field_bits, 128), 57);
+        assert_eq!(recommended_full_rounds(8, 5, field_bits, 128), 8);
+    }
+
+    #[test]
+    fn binomial_logs() {
+        assert_eq!(log2_binomial(4, 2), 6f64.log2());
+        assert!((log2_binomial(10, 5) - 252f64.log2()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,476 @@
+//! An in-circuit duplex sponge, whose absorbs and squeezes may be interleaved.
+
+use std::marker::PhantomData;
+
+use ff::FromUniformBytes;
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter},
+    plonk::Error,
+};
+
+use super::poseidon::{PaddedWord, PoseidonSpongeInstructions};
+use crate::base::primitives::{
+    state_from_iter, Absorbing, Domain, DuplexMode, DuplexRates, Spec, State,
+};
+
+/// A duplex sponge over a Poseidon chip.
+///
+/// This is the in-circuit analog of [`crate::base::primitives::Duplex`]: absorbed words
+/// are buffered until a block is full, and squeezing zero-pads and absorbs the buffered
+/// block before returning the rate words in order, permuting again once they run out.
+/// Its rates can likewise be lowered with [`Duplex::set_rates`].
+#[derive(Debug)]
+pub struct Duplex<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    chip: PoseidonChip,
+    state: State<PoseidonChip::Word, T>,
+    mode: DuplexMode<PaddedWord<F>, PoseidonChip::Word>,
+    rates: DuplexRates,
+    _marker: PhantomData<(S, D)>,
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+        S: Spec<F, T, RATE>,
+        D: Domain<F, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > Duplex<F, PoseidonChip, S, D, T, RATE>
+{
+    /// Initializes an empty duplex sponge.
+    pub fn new(chip: PoseidonChip, mut layouter: impl Layouter<F>) -> Result<Self, Error> {
+        let state = chip.initial_state(&mut layouter)?;
+        Ok(Duplex {
+            chip,
+            state,
+            mode: DuplexMode::Absorbing(Vec::with_capacity(RATE)),
+            rates: DuplexRates::full(RATE),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Sets the number of words absorbed into each block, and squeezed from each block,
+    /// from now on, as [`crate::base::primitives::Duplex::set_rates`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either rate is zero or above `RATE`, or if more words are buffered for
+    /// absorption than `absorb_rate`.
+    pub fn set_rates(&mut self, absorb_rate: usize, squeeze_rate: usize) {
+        self.rates.set(RATE, absorb_rate, squeeze_rate, &self.mode);
+    }
+
+    /// Absorbs the given words. Any squeezed words not yet returned are discarded.
+    pub fn absorb(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        words: &[AssignedCell<F, F>],
+    ) -> Result<(), Error> {
+        for (i, word) in words.iter().enumerate() {
+            if let DuplexMode::Squeezing(_) = self.mode {
+                self.mode = DuplexMode::Absorbing(Vec::with_capacity(RATE));
+            }
+            if let DuplexMode::Absorbing(pending) = &mut self.mode {
+                if pending.len() == self.rates.absorb {
+                    let padding = RATE - pending.len();
+                    pending.extend((0..padding).map(|_| PaddedWord::Padding(F::ZERO)));
+                    let block = Absorbing(state_from_iter(pending.drain(..).map(Some)));
+                    self.state = self.chip.add_input(&mut layouter, &self.state, &block)?;
+                    self.state = self.chip.permute(
+                        &mut layouter.namespace(|| format!("permute before word {}", i)),
+                        &self.state,
+                    )?;
+                }
+                pending.push(PaddedWord::Message(word.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Squeezes `n` words.
+    pub fn squeeze(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        n: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        (0..n)
+            .map(|i| {
+                let mut layouter = layouter.namespace(|| format!("squeeze_{}", i));
+                let permute = match &mut self.mode {
+                    DuplexMode::Absorbing(pending) => {
+                        let padding = RATE - pending.len();
+                        pending.extend((0..padding).map(|_| PaddedWord::Padding(F::ZERO)));
+                        let block = Absorbing(state_from_iter(pending.drain(..).map(Some)));
+                        self.state = self.chip.add_input(&mut layouter, &self.state, &block)?;
+                        true
+                    }
+                    DuplexMode::Squeezing(remaining) => remaining.is_empty(),
+                };
+                if permute {
+                    self.state = self.chip.permute(&mut layouter, &self.state)?;
+                    let output = PoseidonChip::get_output(&self.state);
+                    self.mode = DuplexMode::Squeezing(
+                        output
+                            .0
+                            .into_iter()
+                            .take(self.rates.squeeze)
+                            .map(|word| word.unwrap())
+                            .collect(),
+                    );
+                }
+                match &mut self.mode {
+                    DuplexMode::Squeezing(remaining) => Ok(remaining.remove(0).into()),
+                    DuplexMode::Absorbing(_) => unreachable!("squeezing leaves the duplex squeezing"),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A deterministic stream of field elements, seeded by absorbing words into a [`Duplex`].
+///
+/// This is the in-circuit analog of [`crate::base::primitives::PoseidonRng`].
+#[derive(Debug)]
+pub struct PoseidonRng<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    duplex: Duplex<F, PoseidonChip, S, D, T, RATE>,
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+        S: Spec<F, T, RATE>,
+        D: Domain<F, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > PoseidonRng<F, PoseidonChip, S, D, T, RATE>
+{
+    /// Initializes a stream seeded by `seed`.
+    pub fn seed(
+        chip: PoseidonChip,
+        mut layouter: impl Layouter<F>,
+        seed: &[AssignedCell<F, F>],
+    ) -> Result<Self, Error> {
+        let mut duplex = Duplex::new(chip, layouter.namespace(|| "init"))?;
+        duplex.absorb(layouter.namespace(|| "seed"), seed)?;
+        Ok(PoseidonRng { duplex })
+    }
+
+    /// Returns the next element of the stream.
+    pub fn next(&mut self, layouter: impl Layouter<F>) -> Result<AssignedCell<F, F>, Error> {
+        Ok(self
+            .duplex
+            .squeeze(layouter, 1)?
+            .pop()
+            .expect("squeezed one word"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{Duplex, PoseidonRng};
+    use crate::base::primitives::{self, VariableLength};
+    use crate::base::test_vectors::Width4Spec;
+    use crate::base::P128Pow5T3;
+    use crate::circuit::pow5::{Pow5Chip, Pow5Config};
+
+    /// A step of the interleaved schedule under test.
+    #[derive(Clone)]
+    enum Op {
+        Absorb(Vec<Fp>),
+        Squeeze(usize),
+    }
+
+    fn schedule() -> Vec<Op> {
+        vec![
+            Op::Absorb(vec![Fp::from(1), Fp::from(2), Fp::from(3)]),
+            Op::Squeeze(1),
+            Op::Absorb(vec![Fp::from(4)]),
+            Op::Squeeze(3),
+            Op::Squeeze(1),
+        ]
+    }
+
+    #[derive(Clone, Debug)]
+    struct DuplexConfig {
+        pow5: Pow5Config<Fp, 3, 2>,
+        words: Column<Advice>,
+        instance: Column<Instance>,
+    }
+
+    /// Runs `schedule()` and binds every squeezed word, in order, to the instance column.
+    struct DuplexCircuit;
+
+    impl Circuit<Fp> for DuplexCircuit {
+        type Config = DuplexConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            DuplexCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DuplexConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let words = meta.advice_column();
+            meta.enable_equality(words);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            DuplexConfig {
+                pow5: Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                words,
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: DuplexConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let mut duplex = Duplex::<_, _, P128Pow5T3<Fp>, VariableLength, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+
+            let mut squeezed = 0;
+            for (step, op) in schedule().into_iter().enumerate() {
+                match op {
+                    Op::Absorb(values) => {
+                        let words = layouter.assign_region(
+                            || format!("load words for step {}", step),
+                            |mut region| {
+                                values
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(i, value)| {
+                                        region.assign_advice(
+                                            || format!("word_{}", i),
+                                            config.words,
+                                            i,
+                                            || Value::known(*value),
+                                        )
+                                    })
+                                    .collect::<Result<Vec<_>, Error>>()
+                            },
+                        )?;
+                        duplex.absorb(layouter.namespace(|| format!("step {}", step)), &words)?;
+                    }
+                    Op::Squeeze(n) => {
+                        let outputs =
+                            duplex.squeeze(layouter.namespace(|| format!("step {}", step)), n)?;
+                        for output in outputs {
+                            layouter.constrain_instance(output.cell(), config.instance, squeezed)?;
+                            squeezed += 1;
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn duplex_matches_reference() {
+        let mut reference = primitives::Duplex::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init();
+        let mut expected = vec![];
+        for op in schedule() {
+            match op {
+                Op::Absorb(values) => reference.absorb(&values),
+                Op::Squeeze(n) => expected.extend(reference.squeeze(n)),
+            }
+        }
+        assert_eq!(expected.len(), 5);
+
+        let prover = MockProver::run(10, &DuplexCircuit, vec![expected.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        expected.swap(1, 2);
+        let prover = MockProver::run(10, &DuplexCircuit, vec![expected]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Absorbs three words at rate 2 and squeezes three at rate 1 from a width-4
+    /// duplex of rate 3, binding the squeezed words to the instance column.
+    struct RatesCircuit;
+
+    impl Circuit<Fp> for RatesCircuit {
+        type Config = (Pow5Config<Fp, 4, 3>, Column<Advice>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            RatesCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; 4].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 4].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 4].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let words = meta.advice_column();
+            meta.enable_equality(words);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let pow5 =
+                Pow5Chip::configure::<Width4Spec>(meta, state, partial_sbox, rc_a, pad_fixed);
+            (pow5, words, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (pow5, words, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    (0..3)
+                        .map(|i| {
+                            region.assign_advice(
+                                || format!("word_{}", i),
+                                words,
+                                i,
+                                || Value::known(Fp::from(i as u64 + 1)),
+                            )
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(pow5);
+            let mut duplex = Duplex::<_, _, Width4Spec, VariableLength, 4, 3>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            duplex.set_rates(2, 1);
+            duplex.absorb(layouter.namespace(|| "absorb"), &message)?;
+            let outputs = duplex.squeeze(layouter.namespace(|| "squeeze"), 3)?;
+            for (i, output) in outputs.iter().enumerate() {
+                layouter.constrain_instance(output.cell(), instance, i)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn duplex_rates_match_reference() {
+        let mut reference = primitives::Duplex::<_, Width4Spec, VariableLength, 4, 3>::init();
+        reference.set_rates(2, 1);
+        reference.absorb(&[1u64, 2, 3].map(Fp::from));
+        let expected = reference.squeeze(3);
+
+        let prover = MockProver::run(10, &RatesCircuit, vec![expected]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // At full rate the same words squeeze differently.
+        let mut full = primitives::Duplex::<_, Width4Spec, VariableLength, 4, 3>::init();
+        full.absorb(&[1u64, 2, 3].map(Fp::from));
+        let prover = MockProver::run(10, &RatesCircuit, vec![full.squeeze(3)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Seeds a [`PoseidonRng`] with [`SEED`] and binds its first outputs, in order, to the
+    /// instance column.
+    struct RngCircuit;
+
+    const SEED: [u64; 3] = [1, 2, 3];
+
+    impl Circuit<Fp> for RngCircuit {
+        type Config = DuplexConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            RngCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> DuplexConfig {
+            DuplexCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: DuplexConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let seed = layouter.assign_region(
+                || "load seed",
+                |mut region| {
+                    SEED.iter()
+                        .enumerate()
+                        .map(|(i, value)| {
+                            region.assign_advice(
+                                || format!("seed_{}", i),
+                                config.words,
+                                i,
+                                || Value::known(Fp::from(*value)),
+                            )
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let mut rng = PoseidonRng::<_, _, P128Pow5T3<Fp>, VariableLength, 3, 2>::seed(
+                chip,
+                layouter.namespace(|| "seed"),
+                &seed,
+            )?;
+            for i in 0..5 {
+                let output = rng.next(layouter.namespace(|| format!("next_{}", i)))?;
+                layouter.constrain_instance(output.cell(), config.instance, i)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rng_matches_reference() {
+        let reference = primitives::PoseidonRng::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::seed(
+            &SEED.map(Fp::from),
+        );
+        let expected: Vec<_> = reference.take(5).collect();
+
+        let prover = MockProver::run(10, &RngCircuit, vec![expected]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+}
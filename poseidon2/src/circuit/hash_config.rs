@@ -0,0 +1,223 @@
+//! Backend-agnostic wrappers around the Poseidon chips.
+//!
+//! A circuit that stores a [`HashConfig`] in its `Config`, and hashes through a
+//! [`HashChip`], can switch chip backends without changing its own types.
+
+use ff::FromUniformBytes;
+use halo2_proofs::{
+    circuit::{Chip, Layouter},
+    plonk::Error,
+};
+
+use super::poseidon::{PaddedWord, PoseidonInstructions, PoseidonSpongeInstructions};
+use super::pow5::{Pow5Chip, Pow5Config, StateWord};
+use crate::base::primitives::{Absorbing, Domain, Spec, Squeezing, State};
+
+/// The configuration of one of the Poseidon chip backends.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum HashConfig<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> {
+    /// A [`Pow5Chip`] configuration.
+    Pow5(Pow5Config<F, WIDTH, RATE>),
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize>
+    From<Pow5Config<F, WIDTH, RATE>> for HashConfig<F, WIDTH, RATE>
+{
+    fn from(config: Pow5Config<F, WIDTH, RATE>) -> Self {
+        HashConfig::Pow5(config)
+    }
+}
+
+/// A Poseidon chip for whichever backend its [`HashConfig`] was built for.
+///
+/// Every backend uses [`StateWord`] as its state word, so states and outputs do not
+/// depend on the backend either.
+#[derive(Clone, Debug)]
+pub struct HashChip<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> {
+    config: HashConfig<F, WIDTH, RATE>,
+    backend: Backend<F, WIDTH, RATE>,
+}
+
+#[derive(Clone, Debug)]
+enum Backend<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> {
+    Pow5(Pow5Chip<F, WIDTH, RATE>),
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> HashChip<F, WIDTH, RATE> {
+    /// Constructs the chip for the backend `config` was built for.
+    pub fn construct(config: HashConfig<F, WIDTH, RATE>) -> Self {
+        let backend = match &config {
+            HashConfig::Pow5(config) => Backend::Pow5(Pow5Chip::construct(config.clone())),
+        };
+        HashChip { config, backend }
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Chip<F>
+    for HashChip<F, WIDTH, RATE>
+{
+    type Config = HashConfig<F, WIDTH, RATE>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, S: Spec<F, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>
+    PoseidonInstructions<F, S, WIDTH, RATE> for HashChip<F, WIDTH, RATE>
+{
+    type Word = StateWord<F>;
+
+    fn permute(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<Self::Word, WIDTH>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        match &self.backend {
+            Backend::Pow5(chip) => {
+                PoseidonInstructions::<F, S, WIDTH, RATE>::permute(chip, layouter, initial_state)
+            }
+        }
+    }
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        S: Spec<F, WIDTH, RATE>,
+        D: Domain<F, RATE>,
+        const WIDTH: usize,
+        const RATE: usize,
+    > PoseidonSpongeInstructions<F, S, D, WIDTH, RATE> for HashChip<F, WIDTH, RATE>
+{
+    fn initial_state(
+        &self,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        match &self.backend {
+            Backend::Pow5(chip) => {
+                PoseidonSpongeInstructions::<F, S, D, WIDTH, RATE>::initial_state(chip, layouter)
+            }
+        }
+    }
+
+    fn add_input(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<Self::Word, WIDTH>,
+        input: &Absorbing<PaddedWord<F>, RATE>,
+    ) -> Result<State<Self::Word, WIDTH>, Error> {
+        match &self.backend {
+            Backend::Pow5(chip) => PoseidonSpongeInstructions::<F, S, D, WIDTH, RATE>::add_input(
+                chip,
+                layouter,
+                initial_state,
+                input,
+            ),
+        }
+    }
+
+    fn get_output(state: &State<Self::Word, WIDTH>) -> Squeezing<Self::Word, RATE> {
+        <Pow5Chip<F, WIDTH, RATE> as PoseidonSpongeInstructions<F, S, D, WIDTH, RATE>>::get_output(
+            state,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{HashChip, HashConfig};
+    use crate::base::primitives::ConstantLength;
+    use crate::base::test_vectors::{from_hex, hash};
+    use crate::base::P128Pow5T3;
+    use crate::circuit::poseidon::Hash;
+    use crate::circuit::pow5::Pow5Chip;
+
+    /// Hashes a two-word message through a [`HashChip`], and binds the output to the
+    /// instance column.
+    struct WrappedHashCircuit {
+        message: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for WrappedHashCircuit {
+        type Config = (HashConfig<Fp, 3, 2>, Column<Advice>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            WrappedHashCircuit {
+                message: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let message = meta.advice_column();
+            meta.enable_equality(message);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let pow5 =
+                Pow5Chip::configure::<P128Pow5T3<Fp>>(meta, state, partial_sbox, rc_a, pad_fixed);
+            (pow5.into(), message, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, message_column, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            message_column,
+                            i,
+                            || self.message[i],
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            let chip = HashChip::construct(config);
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    #[test]
+    fn hash_through_hash_config() {
+        for tv in hash() {
+            let circuit = WrappedHashCircuit {
+                message: tv.input.map(|word| Value::known(from_hex::<Fp>(word))),
+            };
+            let output = from_hex::<Fp>(tv.output);
+            let prover = MockProver::run(8, &circuit, vec![vec![output]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+}
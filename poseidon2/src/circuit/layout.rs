@@ -0,0 +1,207 @@
+//! A machine-readable dump of where a circuit assigns its cells.
+//!
+//! Unlike a rendered layout, [`cell_assignments`] lists every assigned cell as a
+//! `(region, column, row, annotation)` record, in assignment order, and `layout_json`
+//! dumps them as JSON, so that the layouts of two versions of a circuit can be diffed
+//! line by line. The module is only compiled with the `layout-json` feature, and for
+//! this crate's own tests.
+//!
+//! `Assignment` only requires `fork` and `merge` with halo2's `parallel_syn` feature,
+//! which cannot be detected from here: the recorder implements them under this crate's
+//! own `parallel_syn`, which enables halo2's. A build enabling halo2's `parallel_syn`
+//! but not this crate's therefore fails to compile this module.
+
+#[cfg(feature = "parallel_syn")]
+use std::ops::Range;
+
+use ff::Field;
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{
+        Advice, Any, Assigned, Assignment, Challenge, Circuit, Column, ConstraintSystem,
+        Error, Fixed, FloorPlanner, Instance, Selector,
+    },
+};
+#[cfg(feature = "layout-json")]
+use serde_json::json;
+
+/// A cell assigned while synthesizing a circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellAssignment {
+    /// The name of the region the cell was assigned in, if any.
+    pub region: Option<String>,
+    /// The column of the cell.
+    pub column: Column<Any>,
+    /// The row of the cell, counted from the start of the circuit.
+    pub row: usize,
+    /// The annotation the cell was assigned with.
+    pub annotation: String,
+}
+
+#[cfg(feature = "layout-json")]
+impl CellAssignment {
+    fn to_json(&self) -> serde_json::Value {
+        let column_type = match self.column.column_type() {
+            Any::Advice(_) => "advice",
+            Any::Fixed => "fixed",
+            Any::Instance => "instance",
+        };
+        json!({
+            "region": self.region,
+            "column": { "type": column_type, "index": self.column.index() },
+            "row": self.row,
+            "annotation": self.annotation,
+        })
+    }
+}
+
+/// Records the advice and fixed cells a floor planner assigns, without their values.
+#[derive(Debug, Default)]
+struct LayoutRecorder {
+    region: Option<String>,
+    cells: Vec<CellAssignment>,
+}
+
+impl LayoutRecorder {
+    fn record<A, AR>(&mut self, annotation: A, column: Column<Any>, row: usize)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.cells.push(CellAssignment {
+            region: self.region.clone(),
+            column,
+            row,
+            annotation: annotation().into(),
+        });
+    }
+}
+
+impl<F: Field> Assignment<F> for LayoutRecorder {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.region = Some(name_fn().into());
+    }
+
+    fn exit_region(&mut self) {
+        self.region = None;
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, _: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.record(annotation, column.into(), row);
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.record(annotation, column.into(), row);
+        Ok(())
+    }
+
+    fn copy(&mut self, _: Column<Any>, _: usize, _: Column<Any>, _: usize) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get_challenge(&self, _: Challenge) -> Value<F> {
+        Value::unknown()
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+
+    #[cfg(feature = "parallel_syn")]
+    fn fork(&mut self, ranges: &Vec<Range<usize>>) -> Result<Vec<Self>, Error> {
+        Ok(ranges
+            .iter()
+            .map(|_| LayoutRecorder {
+                region: self.region.clone(),
+                cells: vec![],
+            })
+            .collect())
+    }
+
+    #[cfg(feature = "parallel_syn")]
+    fn merge(&mut self, sub_cs: Vec<Self>) -> Result<(), Error> {
+        self.cells.extend(sub_cs.into_iter().flat_map(|sub| sub.cells));
+        Ok(())
+    }
+}
+
+/// Synthesizes `circuit` without witnesses, and returns the cells it assigns in order.
+pub fn cell_assignments<F: Field, C: Circuit<F>>(
+    circuit: &C,
+) -> Result<Vec<CellAssignment>, Error> {
+    let mut meta = ConstraintSystem::default();
+    let config = C::configure(&mut meta);
+    let mut recorder = LayoutRecorder::default();
+    C::FloorPlanner::synthesize(
+        &mut recorder,
+        &circuit.without_witnesses(),
+        config,
+        meta.constants().clone(),
+    )?;
+    Ok(recorder.cells)
+}
+
+/// Dumps the layout of `circuit` as a pretty-printed JSON array of its
+/// [`cell_assignments`], each an object with `region`, `column` (its `type` and
+/// `index`), `row` and `annotation` fields.
+#[cfg(feature = "layout-json")]
+pub fn layout_json<F: Field, C: Circuit<F>>(circuit: &C) -> Result<String, Error> {
+    let cells = cell_assignments(circuit)?;
+    let cells: Vec<_> = cells.iter().map(CellAssignment::to_json).collect();
+    Ok(serde_json::to_string_pretty(&cells).expect("a JSON value serializes"))
+}
@@ -0,0 +1,411 @@
+//! Merkle root gadgets built on the width-3 Poseidon compression.
+
+use ff::FromUniformBytes;
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter},
+    plonk::Error,
+};
+
+use super::poseidon::{Hash, PoseidonSpongeInstructions};
+use crate::base::primitives::{ConstantLength, Spec};
+
+/// Compresses two nodes into their parent, i.e. hashes `[left, right]` in the
+/// [`ConstantLength<2>`] domain.
+pub fn compress<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<2>, 3, 2>,
+    S: Spec<F, 3, 2>,
+>(
+    chip: PoseidonChip,
+    mut layouter: impl Layouter<F>,
+    left: AssignedCell<F, F>,
+    right: AssignedCell<F, F>,
+) -> Result<AssignedCell<F, F>, Error> {
+    let hasher = Hash::<_, _, S, ConstantLength<2>, 3, 2>::init(
+        chip,
+        layouter.namespace(|| "init"),
+    )?;
+    hasher.hash(layouter.namespace(|| "hash"), [left, right])
+}
+
+/// Computes the root of each tree in `leaves`, all of which must have `2^depth` leaves.
+///
+/// The trees are reduced one level at a time, across all trees, with every compression
+/// sharing the configuration of `chip`.
+pub fn batch_merkle_roots<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<2>, 3, 2> + Clone,
+    S: Spec<F, 3, 2>,
+>(
+    chip: &PoseidonChip,
+    mut layouter: impl Layouter<F>,
+    leaves: Vec<Vec<AssignedCell<F, F>>>,
+    depth: usize,
+) -> Result<Vec<AssignedCell<F, F>>, Error> {
+    for (tree, tree_leaves) in leaves.iter().enumerate() {
+        assert_eq!(
+            tree_leaves.len(),
+            1 << depth,
+            "tree {} must have 2^{} leaves",
+            tree,
+            depth
+        );
+    }
+
+    let mut levels = leaves;
+    for level in 0..depth {
+        levels = levels
+            .into_iter()
+            .enumerate()
+            .map(|(tree, nodes)| {
+                nodes
+                    .chunks(2)
+                    .enumerate()
+                    .map(|(i, pair)| {
+                        compress::<_, _, S>(
+                            chip.clone(),
+                            layouter.namespace(|| {
+                                format!("tree {} level {} node {}", tree, level, i)
+                            }),
+                            pair[0].clone(),
+                            pair[1].clone(),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+    }
+
+    Ok(levels
+        .into_iter()
+        .map(|mut root| root.pop().expect("a tree reduces to one root"))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use ff::{Field, FromUniformBytes};
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::batch_merkle_roots;
+    use crate::base::primitives::{self, ConstantLength, Domain};
+    use crate::base::P128Pow5T3;
+    use crate::circuit::poseidon::Hash;
+    use crate::circuit::pow5::{Pow5Chip, Pow5Config};
+
+    const DEPTH: usize = 2;
+
+    fn merkle_root(leaves: &[Fp]) -> Fp {
+        let mut nodes = leaves.to_vec();
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| {
+                    primitives::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+                        .hash([pair[0], pair[1]], Fp::ZERO)
+                })
+                .collect();
+        }
+        nodes[0]
+    }
+
+    #[derive(Clone, Debug)]
+    struct MerkleConfig {
+        pow5: Pow5Config<Fp, 3, 2>,
+        leaves: Column<Advice>,
+        instance: Column<Instance>,
+    }
+
+    /// Computes the root of each tree and binds them to the instance column.
+    struct MerkleForestCircuit {
+        trees: Vec<Vec<Value<Fp>>>,
+    }
+
+    impl Circuit<Fp> for MerkleForestCircuit {
+        type Config = MerkleConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            MerkleForestCircuit {
+                trees: self
+                    .trees
+                    .iter()
+                    .map(|tree| vec![Value::unknown(); tree.len()])
+                    .collect(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MerkleConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let leaves = meta.advice_column();
+            meta.enable_equality(leaves);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            MerkleConfig {
+                pow5: Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                leaves,
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: MerkleConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let leaves = self
+                .trees
+                .iter()
+                .enumerate()
+                .map(|(tree, values)| {
+                    layouter.assign_region(
+                        || format!("load leaves of tree {}", tree),
+                        |mut region| {
+                            values
+                                .iter()
+                                .enumerate()
+                                .map(|(i, value)| {
+                                    region.assign_advice(
+                                        || format!("leaf_{}", i),
+                                        config.leaves,
+                                        i,
+                                        || *value,
+                                    )
+                                })
+                                .collect::<Result<Vec<_>, Error>>()
+                        },
+                    )
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let roots = batch_merkle_roots::<_, _, P128Pow5T3<Fp>>(
+                &chip,
+                layouter.namespace(|| "roots"),
+                leaves,
+                DEPTH,
+            )?;
+
+            for (i, root) in roots.iter().enumerate() {
+                layouter.constrain_instance(root.cell(), config.instance, i)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn batch_merkle_roots_match_reference() {
+        let trees = (0..4)
+            .map(|tree| {
+                (0..1 << DEPTH)
+                    .map(|leaf| Fp::from((tree * 10 + leaf) as u64))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let roots = trees.iter().map(|tree| merkle_root(tree)).collect::<Vec<_>>();
+
+        let circuit = MerkleForestCircuit {
+            trees: trees
+                .iter()
+                .map(|tree| tree.iter().copied().map(Value::known).collect())
+                .collect(),
+        };
+
+        let k = 10;
+        let prover = MockProver::run(k, &circuit, vec![roots.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let mut wrong = roots;
+        wrong.swap(0, 1);
+        let prover = MockProver::run(k, &circuit, vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Two-word messages hashed with the capacity element of [`ConstantLength<2>`] plus
+    /// `TAG`, so that nodes of different kinds never hash alike.
+    #[derive(Clone, Copy, Debug)]
+    struct NodeDomain<const TAG: u64>;
+
+    /// The domain of the leaves of a sparse Merkle tree, hashing `[key, value]`.
+    type LeafDomain = NodeDomain<1>;
+    /// The domain of the internal nodes of a sparse Merkle tree, hashing
+    /// `[left, right]`.
+    type InternalDomain = NodeDomain<2>;
+
+    impl<F: FromUniformBytes<64> + Ord, const RATE: usize, const TAG: u64> Domain<F, RATE>
+        for NodeDomain<TAG>
+    {
+        type Padding = iter::Take<iter::Repeat<F>>;
+
+        fn name() -> String {
+            format!("NodeDomain<{}>", TAG)
+        }
+
+        fn initial_capacity_element() -> F {
+            F::from_u128((2 << 64) + u128::from(TAG))
+        }
+
+        fn padding(input_len: usize) -> Self::Padding {
+            assert_eq!(input_len, 2);
+            iter::repeat(F::ZERO).take((RATE - 2 % RATE) % RATE)
+        }
+    }
+
+    fn hash_node<D: Domain<Fp, 2>>(left: Fp, right: Fp) -> Fp {
+        primitives::Hash::<_, P128Pow5T3<Fp>, D, 3, 2>::init().hash_padded(&[left, right])
+    }
+
+    /// The root of a sparse tree of depth 2 with the leaves `(slot, key, value)`, where
+    /// empty slots are zero.
+    fn sparse_merkle_root(leaves: &[(usize, Fp, Fp)]) -> Fp {
+        let mut nodes = [Fp::ZERO; 4];
+        for &(slot, key, value) in leaves {
+            nodes[slot] = hash_node::<LeafDomain>(key, value);
+        }
+        hash_node::<InternalDomain>(
+            hash_node::<InternalDomain>(nodes[0], nodes[1]),
+            hash_node::<InternalDomain>(nodes[2], nodes[3]),
+        )
+    }
+
+    /// Computes the root of a sparse tree of depth 2 holding `leaves`, with leaf and
+    /// internal hashes in their own domains on chips sharing one config.
+    struct SparseMerkleCircuit {
+        leaves: Vec<(usize, Value<Fp>, Value<Fp>)>,
+    }
+
+    impl Circuit<Fp> for SparseMerkleCircuit {
+        type Config = MerkleConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            SparseMerkleCircuit {
+                leaves: self
+                    .leaves
+                    .iter()
+                    .map(|&(slot, _, _)| (slot, Value::unknown(), Value::unknown()))
+                    .collect(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> MerkleConfig {
+            MerkleForestCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: MerkleConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let mut nodes = layouter.assign_region(
+                || "empty slots",
+                |mut region| {
+                    (0..4)
+                        .map(|slot| {
+                            region.assign_advice_from_constant(
+                                || format!("empty slot_{}", slot),
+                                config.leaves,
+                                slot,
+                                Fp::ZERO,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            for &(slot, key, value) in &self.leaves {
+                let message = layouter.assign_region(
+                    || format!("load leaf {}", slot),
+                    |mut region| {
+                        Ok([
+                            region.assign_advice(|| "key", config.leaves, 0, || key)?,
+                            region.assign_advice(|| "value", config.leaves, 1, || value)?,
+                        ])
+                    },
+                )?;
+                let hasher = Hash::<_, _, P128Pow5T3<Fp>, LeafDomain, 3, 2>::init(
+                    chip.clone(),
+                    layouter.namespace(|| format!("init leaf {}", slot)),
+                )?;
+                nodes[slot] = hasher
+                    .hash_padded(layouter.namespace(|| format!("leaf {}", slot)), &message)?;
+            }
+
+            while nodes.len() > 1 {
+                let level = nodes.len();
+                nodes = nodes
+                    .chunks(2)
+                    .enumerate()
+                    .map(|(i, pair)| {
+                        let hasher = Hash::<_, _, P128Pow5T3<Fp>, InternalDomain, 3, 2>::init(
+                            chip.clone(),
+                            layouter.namespace(|| format!("init node {} of {}", i, level)),
+                        )?;
+                        let node = hasher.hash_padded(
+                            layouter.namespace(|| format!("node {} of {}", i, level)),
+                            pair,
+                        )?;
+                        Ok(node)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+            }
+
+            layouter.constrain_instance(nodes[0].cell(), config.instance, 0)
+        }
+    }
+
+    #[test]
+    fn sparse_merkle_root_separates_leaf_and_internal_domains() {
+        let (a, b) = (Fp::from(3), Fp::from(5));
+        assert_ne!(
+            hash_node::<LeafDomain>(a, b),
+            hash_node::<InternalDomain>(a, b)
+        );
+
+        let leaves = [
+            (1, Fp::from(1), Fp::from(10)),
+            (2, Fp::from(2), Fp::from(20)),
+        ];
+        let root = sparse_merkle_root(&leaves);
+        let circuit = SparseMerkleCircuit {
+            leaves: leaves
+                .iter()
+                .map(|&(slot, key, value)| (slot, Value::known(key), Value::known(value)))
+                .collect(),
+        };
+        let prover = MockProver::run(10, &circuit, vec![vec![root]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // A leaf hashed as an internal node gives another root.
+        let mut swapped = [Fp::ZERO; 4];
+        swapped[1] = hash_node::<InternalDomain>(leaves[0].1, leaves[0].2);
+        swapped[2] = hash_node::<LeafDomain>(leaves[1].1, leaves[1].2);
+        let wrong = hash_node::<InternalDomain>(
+            hash_node::<InternalDomain>(swapped[0], swapped[1]),
+            hash_node::<InternalDomain>(swapped[2], swapped[3]),
+        );
+        let prover = MockProver::run(10, &circuit, vec![vec![wrong]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -3,4 +3,11 @@ pub mod utils;
 pub mod params_bn254;
 pub mod params;
 pub mod poseidon;
-pub mod hash;
\ No newline at end of file
+pub mod hash;
+pub mod hash_config;
+pub mod transcript;
+pub mod merkle;
+pub mod duplex;
+pub mod range_check;
+#[cfg(any(test, feature = "layout-json"))]
+pub mod layout;
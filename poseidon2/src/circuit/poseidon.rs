@@ -7,19 +7,51 @@ use std::marker::PhantomData;
 use ff::{PrimeField, FromUniformBytes};
 use ff::Field;
 use halo2_proofs::{
-    circuit::{AssignedCell, Chip, Layouter},
-    plonk::{Error, ConstraintSystem},
+    circuit::{AssignedCell, Chip, Layouter, Value},
+    plonk::{Column, Error, ConstraintSystem, Instance},
 };
 use std::fmt::Debug as DebugT;
-use crate::base::primitives::{Absorbing, ConstantLength, Domain, Spec, SpongeMode, Squeezing, State};
+use super::range_check::RangeCheckChip;
+use crate::base::primitives::{
+    Absorbing, ConstantLength, Domain, DomainError, Spec, SpongeMode, Squeezing, State,
+    VariableLength,
+};
 
 /// A word from the padded input to a Poseidon sponge.
 #[derive(Clone, Debug)]
 pub enum PaddedWord<F: Field> {
     /// A message word provided by the prover.
+    ///
+    /// The word is copied into the sponge's region, so its column must be
+    /// equality-enabled.
     Message(AssignedCell<F, F>),
     /// A padding word, that will be fixed in the circuit parameters.
     Padding(F),
+    /// A message word fixed in the circuit parameters, loaded as a constant.
+    Constant(F),
+    /// A message word read from the given row of an equality-enabled instance column.
+    Instance(Column<Instance>, usize),
+}
+
+/// A message word which may be constant, witnessed by the prover, or public.
+#[derive(Clone, Debug)]
+pub enum SpongeInput<F: Field> {
+    /// A constant, loaded with `assign_advice_from_constant`.
+    Constant(F),
+    /// A word witnessed by the prover, in an equality-enabled column.
+    Witnessed(AssignedCell<F, F>),
+    /// The given row of an equality-enabled instance column.
+    Instance(Column<Instance>, usize),
+}
+
+impl<F: Field> From<SpongeInput<F>> for PaddedWord<F> {
+    fn from(input: SpongeInput<F>) -> Self {
+        match input {
+            SpongeInput::Constant(value) => PaddedWord::Constant(value),
+            SpongeInput::Witnessed(cell) => PaddedWord::Message(cell),
+            SpongeInput::Instance(column, row) => PaddedWord::Instance(column, row),
+        }
+    }
 }
 
 /// The set of circuit instructions required to use the Poseidon permutation.
@@ -86,6 +118,10 @@ pub trait PoseidonSpongeInstructions<
         -> Result<State<Self::Word, T>, Error>;
 
     /// Adds the given input to the state.
+    ///
+    /// Message words are copied from the regions that assigned them, so their columns
+    /// must be equality-enabled; otherwise synthesis fails with
+    /// [`Error::ColumnNotInPermutation`].
     fn add_input(
         &self,
         layouter: &mut impl Layouter<F>,
@@ -95,6 +131,23 @@ pub trait PoseidonSpongeInstructions<
 
     /// Extracts sponge output from the given state.
     fn get_output(state: &State<Self::Word, T>) -> Squeezing<Self::Word, RATE>;
+
+    /// Returns every word of the given state as a cell, capacity included, e.g. to
+    /// chain the state into another sponge without permuting it again.
+    ///
+    /// The words are in state order, so the rate words among them are those of
+    /// [`Self::get_output`], at the indices given by [`Spec::state_endianness`].
+    fn get_full_state(state: &State<Self::Word, T>) -> [AssignedCell<F, F>; T] {
+        state.clone().map(Into::into)
+    }
+
+    /// Returns every rate word of the given state, in the order of [`Self::get_output`],
+    /// e.g. for a hash with `RATE` outputs.
+    fn squeeze_rate(state: &State<Self::Word, T>) -> [AssignedCell<F, F>; RATE] {
+        Self::get_output(state)
+            .0
+            .map(|word| word.expect("get_output fills every rate word").into())
+    }
 }
 
 /// A word over which the Poseidon permutation operates.
@@ -162,6 +215,9 @@ pub struct Sponge<
     chip: PoseidonChip,
     mode: M,
     state: State<PoseidonChip::Word, T>,
+    /// Whether a partial last block is zero-padded when the sponge finishes absorbing,
+    /// as for a sponge resumed after squeezing.
+    zero_pad: bool,
     _marker: PhantomData<D>,
 }
 
@@ -175,8 +231,31 @@ impl<
     > Sponge<F, PoseidonChip, S, Absorbing<PaddedWord<F>, RATE>, D, T, RATE>
 {
     /// Constructs a new duplex sponge for the given Poseidon specification.
+    ///
+    /// The domain's [`Domain::prefix`], if any, is absorbed first as a fixed word.
     pub fn new(chip: PoseidonChip, mut layouter: impl Layouter<F>) -> Result<Self, Error> {
         chip.initial_state(&mut layouter).map(|state| Sponge {
+            chip,
+            mode: match D::prefix() {
+                Some(prefix) => Absorbing::init_with(PaddedWord::Padding(prefix)),
+                None => Absorbing(
+                    (0..RATE)
+                        .map(|_| None)
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                ),
+            },
+            state,
+            zero_pad: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Constructs a sponge that absorbs on top of `state`, such as a keyed initial state,
+    /// instead of the initial state of its domain.
+    pub fn with_state(chip: PoseidonChip, state: State<PoseidonChip::Word, T>) -> Self {
+        Sponge {
             chip,
             mode: Absorbing(
                 (0..RATE)
@@ -186,8 +265,9 @@ impl<
                     .unwrap(),
             ),
             state,
+            zero_pad: false,
             _marker: PhantomData,
-        })
+        }
     }
 
     /// Absorbs an element into the sponge.
@@ -215,23 +295,108 @@ impl<
         Ok(())
     }
 
+    /// Constrains `cell` to be less than `2^bits` with `range_check`, and then absorbs it.
+    pub fn absorb_bounded(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        range_check: &RangeCheckChip<F>,
+        cell: AssignedCell<F, F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        range_check.range_check(layouter.namespace(|| "range check"), &cell, bits)?;
+        self.absorb(layouter, PaddedWord::Message(cell))
+    }
+
+    /// Absorbs `value` and a boolean `tag`, such as a sign, packed into the single word
+    /// `2 * value + tag` by `range_check`, which also constrains `tag` to be boolean.
+    ///
+    /// [`pack_tagged`] packs them the same way off-circuit. The packing is only
+    /// injective if `value` is below `(p - 1) / 2`, which the caller must ensure, e.g.
+    /// with a range check.
+    ///
+    /// [`pack_tagged`]: crate::base::primitives::pack_tagged
+    pub fn absorb_tagged(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        range_check: &RangeCheckChip<F>,
+        value: &AssignedCell<F, F>,
+        tag: Value<bool>,
+    ) -> Result<(), Error> {
+        let packed = range_check.pack_tag(layouter.namespace(|| "pack tag"), value, tag)?;
+        self.absorb(layouter, PaddedWord::Message(packed))
+    }
+
+    /// Absorbs a 256-bit word given as its low and high 128-bit limbs, low limb first,
+    /// after constraining each limb to 128 bits with `range_check`.
+    ///
+    /// The range checks make `(lo, hi)` the unique decomposition of the word, even if
+    /// it exceeds the field modulus; [`u256_limbs`] splits a word the same way
+    /// off-circuit.
+    ///
+    /// [`u256_limbs`]: crate::base::primitives::u256_limbs
+    pub fn absorb_u256(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        range_check: &RangeCheckChip<F>,
+        lo: AssignedCell<F, F>,
+        hi: AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        self.absorb_bounded(layouter.namespace(|| "absorb lo"), range_check, lo, 128)?;
+        self.absorb_bounded(layouter.namespace(|| "absorb hi"), range_check, hi, 128)
+    }
+
+    /// Absorbs the given cells, in order, from whichever columns they were assigned in.
+    ///
+    /// Each cell is copied into the sponge's region, so its column must be
+    /// equality-enabled; otherwise synthesis fails with
+    /// [`Error::ColumnNotInPermutation`] naming that column.
+    pub fn absorb_cells(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<(), Error> {
+        for (i, cell) in cells.iter().enumerate() {
+            self.absorb(
+                layouter.namespace(|| format!("absorb_{}", i)),
+                PaddedWord::Message(cell.clone()),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fills the unused words of the current block with `padding`, so that the sponge
+    /// can finish absorbing after any number of words.
+    pub fn pad_block(&mut self, padding: F) {
+        for entry in self.mode.0.iter_mut().filter(|entry| entry.is_none()) {
+            *entry = Some(PaddedWord::Padding(padding));
+        }
+    }
+
     /// Transitions the sponge into its squeezing state.
+    ///
+    /// If nothing was absorbed, as for an empty message, the state is permuted as is,
+    /// which is equivalent to absorbing a block of zeros.
     #[allow(clippy::type_complexity)]
     pub fn finish_absorbing(
         mut self,
         mut layouter: impl Layouter<F>,
     ) -> Result<Sponge<F, PoseidonChip, S, Squeezing<PoseidonChip::Word, RATE>, D, T, RATE>, Error>
     {   
+        if self.zero_pad {
+            self.pad_block(F::ZERO);
+        }
+        let input = Some(&self.mode).filter(|mode| mode.0.iter().any(Option::is_some));
         let mode = poseidon_sponge(
             &self.chip,
             layouter.namespace(|| "PoseidonSponge"),
             &mut self.state,
-            Some(&self.mode),
+            input,
         )?;
         Ok(Sponge {
             chip: self.chip,
             mode,
             state: self.state,
+            zero_pad: self.zero_pad,
             _marker: PhantomData::default(),
         })
     }
@@ -264,7 +429,8 @@ impl<
         }
     }
 
-    /// Transitions the sponge into its squeezing state.
+    /// Transitions the sponge back into its absorbing state, with `squeezed_cell` as
+    /// the first word absorbed.
     #[allow(clippy::type_complexity)]
     pub fn finish_squeezing(
         self,
@@ -276,9 +442,83 @@ impl<
             chip: self.chip,
             mode,
             state: self.state,
+            zero_pad: self.zero_pad,
             _marker: PhantomData,
         })
     }
+
+    /// Transitions the sponge back into its absorbing state, e.g. to absorb the next
+    /// message of an interactive protocol after squeezing a challenge, as a
+    /// [`Duplex`] does.
+    ///
+    /// Squeezed words not yet returned are dropped. Nothing is permuted at the
+    /// transition: the words absorbed next start a new block on top of the state they
+    /// were squeezed from, which is permuted once it is full or the sponge finishes
+    /// absorbing. A partial last block is then padded with zeros, as a [`Duplex`] pads
+    /// it.
+    ///
+    /// [`Duplex`]: crate::base::primitives::Duplex
+    #[allow(clippy::type_complexity)]
+    pub fn resume_absorbing(
+        self,
+    ) -> Sponge<F, PoseidonChip, S, Absorbing<PaddedWord<F>, RATE>, D, T, RATE> {
+        Sponge {
+            zero_pad: true,
+            ..Sponge::with_state(self.chip, self.state)
+        }
+    }
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord + PrimeField<Repr = [u8; 32]>,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+        S: Spec<F, T, RATE>,
+        D: Domain<F, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > Sponge<F, PoseidonChip, S, Squeezing<PoseidonChip::Word, RATE>, D, T, RATE>
+{
+    /// Squeezes an element and reduces it into `[0, modulus)` with
+    /// [`RangeCheckChip::reduce`], e.g. to derive the indices of a shuffle from the
+    /// sponge's stream.
+    ///
+    /// [`reduce_index`] derives the same index off-circuit.
+    ///
+    /// [`reduce_index`]: crate::base::primitives::reduce_index
+    pub fn squeeze_index(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        range_check: &RangeCheckChip<F>,
+        modulus: u64,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let squeezed = self.squeeze(layouter.namespace(|| "squeeze"))?;
+        range_check.reduce(layouter.namespace(|| "reduce"), &squeezed, modulus)
+    }
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+        S: Spec<F, T, RATE>,
+        M: SpongeMode,
+        D: Domain<F, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > Sponge<F, PoseidonChip, S, M, D, T, RATE>
+{
+    /// Resets the sponge to the initial state of its domain, to hash another message
+    /// with the same chip, as [`Sponge::new`] would.
+    ///
+    /// The initial state is assigned anew, and whatever was absorbed but not yet
+    /// permuted, or is left to squeeze, is dropped.
+    #[allow(clippy::type_complexity)]
+    pub fn reset(
+        self,
+        layouter: impl Layouter<F>,
+    ) -> Result<Sponge<F, PoseidonChip, S, Absorbing<PaddedWord<F>, RATE>, D, T, RATE>, Error>
+    {
+        Sponge::new(self.chip, layouter)
+    }
 }
 
 /// A Poseidon hash function, built around a sponge.
@@ -307,6 +547,56 @@ impl<
     pub fn init(chip: PoseidonChip, layouter: impl Layouter<F>) -> Result<Self, Error> {
         Sponge::new(chip, layouter).map(|sponge| Hash { sponge })
     }
+
+    /// Hashes `message` followed by the domain's [`Domain::padding`], as
+    /// [`crate::base::primitives::Hash::hash_padded`] does.
+    ///
+    /// The length of `message` is checked with [`Domain::validate_len`] before any of it
+    /// is absorbed, and a length the domain rejects is returned as
+    /// [`HashError::Length`]. Only the initial state, assigned by [`Hash::init`], is
+    /// then in the circuit.
+    pub fn hash_padded(
+        mut self,
+        mut layouter: impl Layouter<F>,
+        message: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, HashError> {
+        D::validate_len(message.len())?;
+
+        self.sponge
+            .absorb_cells(layouter.namespace(|| "absorb message"), message)?;
+        for (i, padding) in D::padding(message.len()).into_iter().enumerate() {
+            self.sponge.absorb(
+                layouter.namespace(|| format!("absorb padding_{}", i)),
+                PaddedWord::Padding(padding),
+            )?;
+        }
+        let output = self
+            .sponge
+            .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+            .squeeze(layouter.namespace(|| "squeeze"))?;
+        Ok(output)
+    }
+}
+
+/// Why [`Hash::hash_padded`] failed.
+#[derive(Debug, thiserror::Error)]
+pub enum HashError {
+    /// The domain does not hash messages of this length.
+    #[error(transparent)]
+    Length(#[from] DomainError),
+    /// Synthesizing the hash failed.
+    #[error(transparent)]
+    Synthesis(#[from] Error),
+}
+
+/// A rejected length becomes [`Error::Synthesis`], for `synthesize` to return.
+impl From<HashError> for Error {
+    fn from(err: HashError) -> Self {
+        match err {
+            HashError::Length(_) => Error::Synthesis,
+            HashError::Synthesis(err) => err,
+        }
+    }
 }
 
 impl<
@@ -320,13 +610,41 @@ impl<
 {
     /// Hashes the given input.
     pub fn hash(
-        mut self,
+        self,
+        layouter: impl Layouter<F>,
+        message: [AssignedCell<F, F>; L],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.hash_inputs(layouter, message.map(SpongeInput::Witnessed))
+    }
+
+    /// Hashes the given input and constrains the output to equal `expected`, e.g. to
+    /// open a committed hash without exposing its preimage.
+    ///
+    /// `expected` is copied, so its column must be equality-enabled.
+    pub fn verify_hash(
+        self,
         mut layouter: impl Layouter<F>,
         message: [AssignedCell<F, F>; L],
+        expected: &AssignedCell<F, F>,
+    ) -> Result<(), Error> {
+        let output = self.hash(layouter.namespace(|| "hash"), message)?;
+        layouter.assign_region(
+            || "verify hash",
+            |mut region| region.constrain_equal(output.cell(), expected.cell()),
+        )
+    }
+
+    /// Hashes the given input, whose words may be constant, witnessed or public.
+    ///
+    /// Constant words require a constant-enabled fixed column in the circuit.
+    pub fn hash_inputs(
+        mut self,
+        mut layouter: impl Layouter<F>,
+        message: [SpongeInput<F>; L],
     ) -> Result<AssignedCell<F, F>, Error> {
         for (i, value) in message
             .into_iter()
-            .map(PaddedWord::Message)
+            .map(PaddedWord::from)
             .chain(<ConstantLength<L> as Domain<F, RATE>>::padding(L).map(PaddedWord::Padding))
             .enumerate()
         {   
@@ -338,3 +656,884 @@ impl<
             .squeeze(layouter.namespace(|| "squeeze"))
     }
 }
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, VariableLength, T, RATE>,
+        S: Spec<F, T, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > Hash<F, PoseidonChip, S, VariableLength, T, RATE>
+{
+    /// Hashes any number of `children`, e.g. the hashes of the children of a node in a
+    /// wide tree, as [`crate::base::primitives::Hash::combine_n`] does.
+    ///
+    /// The number of children is absorbed first, as a fixed word, and the children
+    /// then take as many permutations as their blocks need.
+    pub fn combine_n(
+        mut self,
+        mut layouter: impl Layouter<F>,
+        children: &[AssignedCell<F, F>],
+    ) -> Result<AssignedCell<F, F>, Error> {
+        self.sponge.absorb(
+            layouter.namespace(|| "absorb count"),
+            PaddedWord::Padding(F::from(children.len() as u64)),
+        )?;
+        self.sponge
+            .absorb_cells(layouter.namespace(|| "absorb children"), children)?;
+        let padding = <VariableLength as Domain<F, RATE>>::padding(children.len() + 1);
+        for (i, padding) in padding.enumerate() {
+            self.sponge.absorb(
+                layouter.namespace(|| format!("absorb padding_{}", i)),
+                PaddedWord::Padding(padding),
+            )?;
+        }
+        self.sponge
+            .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+            .squeeze(layouter.namespace(|| "squeeze"))
+    }
+}
+
+/// Hashes two words at width 3, i.e. `[a, b]` in the [`ConstantLength<2>`] domain.
+pub fn hash2<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<2>, 3, 2>,
+    S: Spec<F, 3, 2>,
+>(
+    mut layouter: impl Layouter<F>,
+    chip: PoseidonChip,
+    a: AssignedCell<F, F>,
+    b: AssignedCell<F, F>,
+) -> Result<AssignedCell<F, F>, Error> {
+    let hasher = Hash::<_, _, S, ConstantLength<2>, 3, 2>::init(
+        chip,
+        layouter.namespace(|| "init"),
+    )?;
+    hasher.hash(layouter.namespace(|| "hash"), [a, b])
+}
+
+/// Hashes four words at width 5, i.e. `message` in the [`ConstantLength<4>`] domain.
+///
+/// All four words are absorbed in a single permutation.
+pub fn hash4<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<4>, 5, 4>,
+    S: Spec<F, 5, 4>,
+>(
+    mut layouter: impl Layouter<F>,
+    chip: PoseidonChip,
+    message: [AssignedCell<F, F>; 4],
+) -> Result<AssignedCell<F, F>, Error> {
+    let hasher = Hash::<_, _, S, ConstantLength<4>, 5, 4>::init(
+        chip,
+        layouter.namespace(|| "init"),
+    )?;
+    hasher.hash(layouter.namespace(|| "hash"), message)
+}
+
+/// Hashes `message` in the [`ConstantLength<L>`] domain, for any `L`.
+///
+/// The message is absorbed `RATE` words at a time, so it takes `L.div_ceil(RATE)`
+/// permutations, the last one over a zero-padded block.
+pub fn hash_fixed<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<L>, T, RATE>,
+    S: Spec<F, T, RATE>,
+    const T: usize,
+    const RATE: usize,
+    const L: usize,
+>(
+    mut layouter: impl Layouter<F>,
+    chip: PoseidonChip,
+    message: [AssignedCell<F, F>; L],
+) -> Result<AssignedCell<F, F>, Error> {
+    let hasher = Hash::<_, _, S, ConstantLength<L>, T, RATE>::init(
+        chip,
+        layouter.namespace(|| "init"),
+    )?;
+    hasher.hash(layouter.namespace(|| "hash"), message)
+}
+
+/// The position of a hash in a [`HashQueue`], which indexes its output once the queue
+/// is flushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueuedHash(usize);
+
+impl QueuedHash {
+    /// The index of this hash's output in the cells returned by [`HashQueue::flush`].
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Hashes of [`ConstantLength<L>`] messages recorded while a circuit is synthesized,
+/// and assigned together when the queue is flushed.
+///
+/// Queuing lets a circuit collect its hashes from wherever it needs them, and assign
+/// them together once the regions their inputs depend on are assigned, instead of
+/// interleaving them with its other regions.
+///
+/// Flushing neither reorders nor packs the hashes: each is assigned in its own regions,
+/// exactly as an eager [`Hash::hash_inputs`] would, and the layouter's floor planner
+/// places those regions. A queue therefore uses as many rows as eager hashes do.
+#[derive(Debug)]
+pub struct HashQueue<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<L>, T, RATE> + Clone,
+    S: Spec<F, T, RATE>,
+    const T: usize,
+    const RATE: usize,
+    const L: usize,
+> {
+    chip: PoseidonChip,
+    messages: Vec<[SpongeInput<F>; L]>,
+    _marker: PhantomData<S>,
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, ConstantLength<L>, T, RATE> + Clone,
+        S: Spec<F, T, RATE>,
+        const T: usize,
+        const RATE: usize,
+        const L: usize,
+    > HashQueue<F, PoseidonChip, S, T, RATE, L>
+{
+    /// An empty queue, whose hashes will be assigned with `chip`.
+    pub fn new(chip: PoseidonChip) -> Self {
+        HashQueue {
+            chip,
+            messages: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Queues the hash of `message`.
+    pub fn push(&mut self, message: [AssignedCell<F, F>; L]) -> QueuedHash {
+        self.push_inputs(message.map(SpongeInput::Witnessed))
+    }
+
+    /// Queues the hash of `message`, whose words may be constant, witnessed or public
+    /// as for [`Hash::hash_inputs`].
+    pub fn push_inputs(&mut self, message: [SpongeInput<F>; L]) -> QueuedHash {
+        self.messages.push(message);
+        QueuedHash(self.messages.len() - 1)
+    }
+
+    /// The number of queued hashes.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether no hash is queued.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Assigns every queued hash, one after another in the order they were queued, and
+    /// returns their outputs, indexed by [`QueuedHash::index`].
+    pub fn flush(self, mut layouter: impl Layouter<F>) -> Result<Vec<AssignedCell<F, F>>, Error> {
+        let chip = self.chip;
+        self.messages
+            .into_iter()
+            .enumerate()
+            .map(|(i, message)| {
+                let mut layouter = layouter.namespace(|| format!("queued hash {}", i));
+                Hash::<_, _, S, ConstantLength<L>, T, RATE>::init(
+                    chip.clone(),
+                    layouter.namespace(|| "init"),
+                )?
+                .hash_inputs(layouter.namespace(|| "hash"), message)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use ff::Field;
+    use halo2_proofs::{
+        circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{
+        hash2, hash4, hash_fixed, Hash, HashError, HashQueue, PaddedWord, Sponge, SpongeInput,
+    };
+    use crate::base::primitives::{
+        self as poseidon, ConstantLength, Domain, DomainError, Mds, Spec, VariableLength,
+    };
+    use crate::base::test_vectors::{test_spec, PrefixedLength};
+    use crate::base::P128Pow5T3;
+    use crate::circuit::pow5::{Pow5Chip, Pow5Config};
+
+    lazy_static::lazy_static! {
+        static ref WIDTH5_ROUND_CONSTANTS: Vec<[Fp; 5]> = (0..64u64)
+            .map(|round| [0u64, 1, 2, 3, 4].map(|i| Fp::from(round * 5 + i + 1).square()))
+            .collect();
+        static ref WIDTH5_EXTERNAL: Mds<Fp, 5> = [
+            [3u64, 1, 1, 1, 1],
+            [1, 4, 1, 1, 1],
+            [1, 1, 5, 1, 1],
+            [1, 1, 1, 6, 1],
+            [1, 1, 1, 1, 7],
+        ]
+        .map(|row| row.map(Fp::from));
+        static ref WIDTH5_INTERNAL: Mds<Fp, 5> = [
+            [2u64, 1, 1, 1, 1],
+            [1, 3, 1, 1, 1],
+            [1, 1, 4, 1, 1],
+            [1, 1, 1, 5, 1],
+            [1, 1, 1, 1, 6],
+        ]
+        .map(|row| row.map(Fp::from));
+    }
+
+    test_spec! {
+        /// A width-5 spec for exercising [`hash4`]; its constants are only meant for
+        /// testing.
+        struct Width5Spec;
+        impl Spec<5, 4> {
+            round_constants: Cow::Borrowed(&WIDTH5_ROUND_CONSTANTS[..]),
+            internal_matrix: Cow::Borrowed(&WIDTH5_INTERNAL),
+            external_matrix: Cow::Borrowed(&WIDTH5_EXTERNAL),
+        }
+    }
+
+    /// The columns of a [`GadgetCircuit`].
+    #[derive(Clone, Debug)]
+    struct GadgetConfig<const WIDTH: usize, const RATE: usize> {
+        pow5: Pow5Config<Fp, WIDTH, RATE>,
+        /// An advice column outside the permutation, to load words that cannot be copied.
+        plain: Column<Advice>,
+        instance: Column<Instance>,
+    }
+
+    /// What a [`GadgetCircuit`] does with its message once it is loaded.
+    trait Gadget<const WIDTH: usize, const RATE: usize>: Clone {
+        /// The spec the chip is configured for.
+        type Spec: Spec<Fp, WIDTH, RATE>;
+
+        /// The column word `i` of the message is loaded into; the chip's first state
+        /// column unless overridden.
+        fn message_column(
+            &self,
+            config: &GadgetConfig<WIDTH, RATE>,
+            _i: usize,
+        ) -> Column<Advice> {
+            config.pow5.state[0]
+        }
+
+        /// Runs the gadget under test, returning the words to bind to the instance
+        /// column, in order.
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, WIDTH, RATE>,
+            layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error>;
+    }
+
+    /// Loads a message, runs a [`Gadget`] on it with a fresh [`Pow5Chip`], and binds
+    /// the words it returns to the instance column.
+    struct GadgetCircuit<G, const WIDTH: usize, const RATE: usize> {
+        gadget: G,
+        message: Vec<Value<Fp>>,
+    }
+
+    impl<G: Gadget<WIDTH, RATE>, const WIDTH: usize, const RATE: usize>
+        GadgetCircuit<G, WIDTH, RATE>
+    {
+        fn new(gadget: G, message: &[Fp]) -> Self {
+            GadgetCircuit {
+                gadget,
+                message: message.iter().copied().map(Value::known).collect(),
+            }
+        }
+    }
+
+    impl<G: Gadget<WIDTH, RATE>, const WIDTH: usize, const RATE: usize> Circuit<Fp>
+        for GadgetCircuit<G, WIDTH, RATE>
+    {
+        type Config = GadgetConfig<WIDTH, RATE>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            GadgetCircuit {
+                gadget: self.gadget.clone(),
+                message: vec![Value::unknown(); self.message.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; WIDTH].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; WIDTH].map(|_| meta.fixed_column());
+            let pad_fixed = [0; WIDTH].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            GadgetConfig {
+                pow5: Pow5Chip::configure::<G::Spec>(meta, state, partial_sbox, rc_a, pad_fixed),
+                plain: meta.advice_column(),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    self.message
+                        .iter()
+                        .enumerate()
+                        .map(|(i, value)| {
+                            let column = self.gadget.message_column(&config, i);
+                            region.assign_advice(|| format!("message_{}", i), column, i, || *value)
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5);
+            let outputs = self
+                .gadget
+                .synthesize(chip, layouter.namespace(|| "gadget"), message)?;
+            for (row, output) in outputs.iter().enumerate() {
+                layouter.constrain_instance(output.cell(), config.instance, row)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Hashes `message` in the [`ConstantLength`] domain off-circuit.
+    fn hash<const L: usize>(message: [Fp; L]) -> Fp {
+        poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<L>, 3, 2>::init()
+            .hash(message, Fp::ZERO)
+    }
+
+    /// Hashes its two-word message with [`hash2`].
+    #[derive(Clone)]
+    struct Hash2;
+
+    impl Gadget<3, 2> for Hash2 {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let [a, b]: [_; 2] = message.try_into().unwrap();
+            hash2::<_, _, P128Pow5T3<Fp>>(layouter, chip, a, b).map(|output| vec![output])
+        }
+    }
+
+    /// Hashes its four-word message with [`hash4`].
+    #[derive(Clone)]
+    struct Hash4;
+
+    impl Gadget<5, 4> for Hash4 {
+        type Spec = Width5Spec;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 5, 4>,
+            layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let message = message.try_into().unwrap();
+            hash4::<_, _, Width5Spec>(layouter, chip, message).map(|output| vec![output])
+        }
+    }
+
+    /// Absorbs a two-word message through [`Sponge::absorb_cells`], loading the second
+    /// word into either another equality-enabled column or one without equality.
+    #[derive(Clone)]
+    struct AbsorbCells {
+        second_has_equality: bool,
+    }
+
+    impl Gadget<3, 2> for AbsorbCells {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn message_column(&self, config: &GadgetConfig<3, 2>, i: usize) -> Column<Advice> {
+            match i {
+                0 => config.pow5.state[0],
+                _ if self.second_has_equality => config.pow5.state[1],
+                _ => config.plain,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_cells(layouter.namespace(|| "absorb"), &message)?;
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+            Ok(vec![output])
+        }
+    }
+
+    #[test]
+    fn absorb_cells_from_two_columns() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let expected = hash(message);
+
+        let gadget = AbsorbCells {
+            second_has_equality: true,
+        };
+        let circuit = GadgetCircuit::new(gadget, &message);
+        let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let gadget = AbsorbCells {
+            second_has_equality: false,
+        };
+        let circuit = GadgetCircuit::new(gadget, &message);
+        assert!(matches!(
+            MockProver::run(8, &circuit, vec![vec![expected]]),
+            Err(Error::ColumnNotInPermutation(_))
+        ));
+    }
+
+    /// Checks the first two words of its message against the third, a witnessed hash,
+    /// with [`Hash::verify_hash`].
+    #[derive(Clone)]
+    struct VerifyHash;
+
+    impl Gadget<3, 2> for VerifyHash {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let [a, b, expected]: [_; 3] = message.try_into().unwrap();
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            hasher.verify_hash(layouter.namespace(|| "verify"), [a, b], &expected)?;
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn verify_hash_of_matching_and_mismatching_inputs() {
+        let expected = hash([Fp::from(3), Fp::from(5)]);
+
+        let circuit = GadgetCircuit::new(VerifyHash, &[Fp::from(3), Fp::from(5), expected]);
+        let prover = MockProver::run(8, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = GadgetCircuit::new(VerifyHash, &[Fp::from(3), Fp::from(6), expected]);
+        let prover = MockProver::run(8, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn hash2_matches_reference() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let circuit = GadgetCircuit::new(Hash2, &message);
+        let prover = MockProver::run(8, &circuit, vec![vec![hash(message)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn hash4_matches_reference() {
+        let message = [Fp::from(3), Fp::from(5), Fp::from(7), Fp::from(11)];
+        let expected = poseidon::Hash::<_, Width5Spec, ConstantLength<4>, 5, 4>::init()
+            .hash(message, Fp::ZERO);
+
+        let circuit = GadgetCircuit::new(Hash4, &message);
+        let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Hashes its `L`-word message with [`hash_fixed`].
+    #[derive(Clone)]
+    struct HashFixed<const L: usize>;
+
+    impl<const L: usize> Gadget<3, 2> for HashFixed<L> {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let message: [_; L] = message.try_into().unwrap();
+            hash_fixed::<_, _, P128Pow5T3<Fp>, 3, 2, L>(layouter, chip, message)
+                .map(|output| vec![output])
+        }
+    }
+
+    fn check_hash_fixed<const L: usize>() {
+        let message: [Fp; L] = core::array::from_fn(|i| Fp::from(2 * i as u64 + 3));
+        let circuit = GadgetCircuit::new(HashFixed::<L>, &message);
+        let prover = MockProver::run(9, &circuit, vec![vec![hash(message)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn hash_fixed_matches_reference() {
+        // One block, then two and three blocks ending in a padded one.
+        check_hash_fixed::<2>();
+        check_hash_fixed::<3>();
+        check_hash_fixed::<5>();
+    }
+
+    #[test]
+    fn absorb_block_sponge_matches_circuit() {
+        let message = [3u64, 5, 7].map(Fp::from);
+        let endianness = P128Pow5T3::<Fp>::state_endianness();
+        let mut state = [Fp::ZERO; 3];
+        state[endianness.capacity_index(3)] =
+            <ConstantLength<3> as Domain<Fp, 2>>::initial_capacity_element();
+        poseidon::absorb_block::<_, P128Pow5T3<Fp>, 3, 2>(&mut state, &message[..2], []);
+        poseidon::absorb_block::<_, P128Pow5T3<Fp>, 3, 2>(
+            &mut state,
+            &message[2..],
+            <ConstantLength<3> as Domain<Fp, 2>>::padding(3),
+        );
+        let output = state[endianness.rate_index(3, 0)];
+
+        assert_eq!(output, hash(message));
+        let circuit = GadgetCircuit::new(HashFixed::<3>, &message);
+        let prover = MockProver::run(9, &circuit, vec![vec![output]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn constant_length_block_boundaries() {
+        // A full block takes no padding, and a partial one is padded with zeros; the
+        // capacity element tells the two apart.
+        assert_eq!(<ConstantLength<2> as Domain<Fp, 2>>::padding(2).count(), 0);
+        assert_eq!(
+            <ConstantLength<1> as Domain<Fp, 2>>::padding(1).collect::<Vec<_>>(),
+            vec![Fp::ZERO]
+        );
+
+        // The zero pad alone would make [x] collide with [x, 0].
+        let x = Fp::from(3);
+        assert_ne!(hash([x]), hash([x, Fp::ZERO]));
+
+        check_hash_fixed::<1>();
+        check_hash_fixed::<2>();
+    }
+
+    /// Hashes the two halves of its four-word message with one sponge, reset in
+    /// between.
+    #[derive(Clone)]
+    struct ResetSponge;
+
+    impl Gadget<3, 2> for ResetSponge {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let (first, second) = message.split_at(2);
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_cells(layouter.namespace(|| "absorb first"), first)?;
+            let mut squeezing = sponge.finish_absorbing(layouter.namespace(|| "finish first"))?;
+            let first = squeezing.squeeze(layouter.namespace(|| "squeeze first"))?;
+
+            let mut sponge = squeezing.reset(layouter.namespace(|| "reset"))?;
+            sponge.absorb_cells(layouter.namespace(|| "absorb second"), second)?;
+            let second = sponge
+                .finish_absorbing(layouter.namespace(|| "finish second"))?
+                .squeeze(layouter.namespace(|| "squeeze second"))?;
+            Ok(vec![first, second])
+        }
+    }
+
+    #[test]
+    fn reset_sponge_hashes_independently() {
+        let messages = [[Fp::from(3), Fp::from(5)], [Fp::from(7), Fp::from(11)]];
+        let circuit = GadgetCircuit::new(ResetSponge, messages.concat().as_slice());
+        let prover = MockProver::run(8, &circuit, vec![messages.map(hash).to_vec()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Hashes its two-word message through a [`Sponge`] in a [`PrefixedLength`] domain.
+    #[derive(Clone)]
+    struct PrefixedHash<const TAG: u64>;
+
+    impl<const TAG: u64> Gadget<3, 2> for PrefixedHash<TAG> {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, PrefixedLength<2, TAG>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            for (i, word) in message
+                .into_iter()
+                .map(PaddedWord::Message)
+                .chain(
+                    <PrefixedLength<2, TAG> as Domain<Fp, 2>>::padding(2).map(PaddedWord::Padding),
+                )
+                .enumerate()
+            {
+                sponge.absorb(layouter.namespace(|| format!("absorb_{}", i)), word)?;
+            }
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+            Ok(vec![output])
+        }
+    }
+
+    #[test]
+    fn prefixed_domains_diverge() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let expected = [1, 2].map(|tag| hash([Fp::from(tag), message[0], message[1]]));
+        assert_ne!(expected[0], expected[1]);
+
+        let circuit = GadgetCircuit::new(PrefixedHash::<1>, &message);
+        let prover = MockProver::run(9, &circuit, vec![vec![expected[0]]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        let prover = MockProver::run(9, &circuit, vec![vec![expected[1]]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        let circuit = GadgetCircuit::new(PrefixedHash::<2>, &message);
+        let prover = MockProver::run(9, &circuit, vec![vec![expected[1]]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Hashes its message, of any length, with [`Hash::hash_padded`] in the
+    /// [`ConstantLength<3>`] domain.
+    #[derive(Clone)]
+    struct HashPadded;
+
+    impl Gadget<3, 2> for HashPadded {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<3>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = match hasher.hash_padded(layouter.namespace(|| "hash"), &message) {
+                Err(HashError::Length(err)) => {
+                    assert_eq!(
+                        err,
+                        DomainError::WrongLength {
+                            domain: <ConstantLength<3> as Domain<Fp, 2>>::name(),
+                            expected: 3,
+                            actual: message.len(),
+                        }
+                    );
+                    return Err(HashError::Length(err).into());
+                }
+                output => output?,
+            };
+            Ok(vec![output])
+        }
+    }
+
+    #[test]
+    fn hash_padded_validates_len() {
+        let message = [Fp::from(3), Fp::from(5), Fp::from(7)];
+        let expected = hash(message);
+        let circuit = GadgetCircuit::new(HashPadded, &message);
+        let prover = MockProver::run(9, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // A message one word short is rejected, with the lengths, before it is absorbed.
+        let circuit = GadgetCircuit::new(HashPadded, &message[..2]);
+        assert!(matches!(
+            MockProver::run(9, &circuit, vec![vec![expected]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    /// Absorbs the first two words of its message, squeezes, absorbs the rest and
+    /// squeezes again through one [`Sponge`], returning both squeezed words.
+    #[derive(Clone)]
+    struct AbsorbAfterSqueeze;
+
+    impl Gadget<3, 2> for AbsorbAfterSqueeze {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let (first, second) = message.split_at(2);
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, VariableLength, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_cells(layouter.namespace(|| "absorb first"), first)?;
+            let mut sponge = sponge.finish_absorbing(layouter.namespace(|| "finish first"))?;
+            let challenge = sponge.squeeze(layouter.namespace(|| "squeeze first"))?;
+
+            let mut sponge = sponge.resume_absorbing();
+            sponge.absorb_cells(layouter.namespace(|| "absorb second"), second)?;
+            let mut sponge = sponge.finish_absorbing(layouter.namespace(|| "finish second"))?;
+            let next = sponge.squeeze(layouter.namespace(|| "squeeze second"))?;
+            Ok(vec![challenge, next])
+        }
+    }
+
+    /// Checks [`AbsorbAfterSqueeze`] against a [`poseidon::Duplex`] absorbing `first`
+    /// and `second` around a squeeze.
+    fn check_absorb_after_squeeze(first: [Fp; 2], second: &[Fp]) {
+        let mut duplex = poseidon::Duplex::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init();
+        duplex.absorb(&first);
+        let challenge = duplex.squeeze(1)[0];
+        duplex.absorb(second);
+        let next = duplex.squeeze(1)[0];
+
+        let circuit = GadgetCircuit::new(AbsorbAfterSqueeze, &[&first[..], second].concat());
+        let prover = MockProver::run(9, &circuit, vec![vec![challenge, next]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn absorb_after_squeeze_matches_duplex() {
+        check_absorb_after_squeeze([Fp::from(3), Fp::from(5)], &[Fp::from(7), Fp::from(11)]);
+    }
+
+    #[test]
+    fn absorb_partial_block_after_squeeze_matches_duplex() {
+        // One word at rate 2 leaves a partial block, which both zero-pad.
+        check_absorb_after_squeeze([Fp::from(3), Fp::from(5)], &[Fp::from(7)]);
+    }
+
+    /// Combines the child hashes of its message into their parent with
+    /// [`Hash::combine_n`].
+    #[derive(Clone)]
+    struct Combine;
+
+    impl Gadget<3, 2> for Combine {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, VariableLength, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let parent = hasher.combine_n(layouter.namespace(|| "combine"), &message)?;
+            Ok(vec![parent])
+        }
+    }
+
+    #[test]
+    fn combine_n_matches_reference() {
+        let children: [Fp; 5] =
+            std::array::from_fn(|i| hash([Fp::from(2 * i as u64), Fp::from(2 * i as u64 + 1)]));
+        let combine = |children: &[Fp]| {
+            poseidon::Hash::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init().combine_n(children)
+        };
+        let parent = combine(&children);
+
+        let circuit = GadgetCircuit::new(Combine, &children);
+        let prover = MockProver::run(9, &circuit, vec![vec![parent]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // The count keeps a child equal to the padding from standing in for it.
+        assert_ne!(combine(&children[..1]), combine(&[children[0], Fp::ONE]));
+    }
+
+    /// Queues three hashes over the two halves of its four-word message, and flushes
+    /// them together.
+    #[derive(Clone)]
+    struct QueueHashes;
+
+    impl Gadget<3, 2> for QueueHashes {
+        type Spec = P128Pow5T3<Fp>;
+
+        fn synthesize(
+            &self,
+            chip: Pow5Chip<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+            message: Vec<AssignedCell<Fp, Fp>>,
+        ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+            let mut queue = HashQueue::<_, _, P128Pow5T3<Fp>, 3, 2, 2>::new(chip);
+            assert!(queue.is_empty());
+
+            let [a, b, c, d]: [_; 4] = message.try_into().unwrap();
+            let first = queue.push([a, b]);
+            let second = queue.push([d.clone(), c]);
+            let third =
+                queue.push_inputs([SpongeInput::Witnessed(d), SpongeInput::Constant(Fp::ONE)]);
+            assert_eq!(queue.len(), 3);
+
+            let outputs = queue.flush(layouter.namespace(|| "flush"))?;
+            Ok([first, second, third]
+                .iter()
+                .map(|queued| outputs[queued.index()].clone())
+                .collect())
+        }
+    }
+
+    #[test]
+    fn hash_queue_flushes_every_hash() {
+        let messages = [[Fp::from(1), Fp::from(2)], [Fp::from(3), Fp::from(4)]];
+        let expected = vec![
+            hash(messages[0]),
+            hash([messages[1][1], messages[1][0]]),
+            hash([messages[1][1], Fp::ONE]),
+        ];
+
+        let circuit = GadgetCircuit::new(QueueHashes, messages.concat().as_slice());
+        let prover = MockProver::run(9, &circuit, vec![expected.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Swapping two outputs breaks the binding.
+        let mut swapped = expected;
+        swapped.swap(1, 2);
+        let prover = MockProver::run(9, &circuit, vec![swapped]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -1,12 +1,15 @@
+use std::borrow::Cow;
 use std::convert::TryInto;
 use std::iter;
+use std::marker::PhantomData;
 
 use ff::{FromUniformBytes, PrimeField};
 use halo2_proofs::{
     arithmetic::Field,
     circuit::{AssignedCell, Cell, Chip, Layouter, Region, Value},
     plonk::{
-        Advice, Any, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Selector,
+        Advice, Any, Column, ConstraintSystem, Constraints, Error, Expression, Fixed, Instance,
+        Selector, TableColumn, VirtualCells,
     },
     poly::Rotation,
 };
@@ -15,43 +18,747 @@ pub const WIDTH_CHOICES: [usize; 8] = [2, 3, 4, 8, 12, 16, 20, 24];
 
 use super::poseidon::{PoseidonInstructions, PoseidonSpongeInstructions, PaddedWord, PermuteChip};
 use super::utils::Var;
-use crate::base::primitives::{Absorbing, Domain, Mds, Spec, Squeezing, State};
+use crate::base::primitives::{
+    mat_mul, sbox, Absorbing, Domain, InternalMatrix, Mds, Spec, Sponge, Squeezing, State,
+    StateEndianness,
+};
+#[cfg(feature = "fingerprint")]
+use crate::base::primitives::FingerprintMismatch;
+
+/// The row of an add-input region holding the state the input is added to.
+const ADD_INPUT_STATE_ROW: usize = 0;
+/// The row of an add-input region holding the input words, where padding words are
+/// also assigned in `pad_fixed`.
+const ADD_INPUT_WORD_ROW: usize = 1;
+/// The row of an add-input region holding the resulting state.
+const ADD_INPUT_OUTPUT_ROW: usize = 2;
 
 /// Configuration for a [`Pow5Chip`].
 #[derive(Clone, Debug)]
-pub struct Pow5Config<F: PrimeField, const WIDTH: usize, const RATE: usize> {
+pub struct Pow5Config<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize = 1,
+> {
     pub state: [Column<Advice>; WIDTH],
-    partial_sbox: Column<Advice>,
-    rc_a: [Column<Fixed>; WIDTH],
+    partial_sbox: Option<Column<Advice>>,
+    /// The columns witnessing intermediate S-box powers, in low-degree mode.
+    sbox_powers: Option<SboxPowers<WIDTH>>,
+    rc_a: RoundConstantColumns<WIDTH>,
     pad_fixed: [Column<Fixed>; WIDTH],
     s_full: Selector,
+    /// Enables the final full round without its matrix, for specs that skip it.
+    s_last: Option<Selector>,
     s_first: Selector,
-    s_partial: Selector,
+    /// One selector per internal matrix, cycled through by the partial rounds.
+    s_partial: Vec<Selector>,
     s_pad_and_add: Selector,
 
-    half_full_rounds: usize,
-    full_partial_rounds: usize,
-    alpha: [u64; 4],
-    round_constants: Vec<[F; WIDTH]>,
-    mat_external: Mds<F, WIDTH>,
-    mat_internal: Mds<F, WIDTH>,
+    layout: RoundLayout,
+    alpha: u64,
+    round_constants: Cow<'static, [[F; WIDTH]]>,
+    mat_external: Cow<'static, Mds<F, WIDTH>>,
+    mat_internal: Vec<PartialMatrix<F, WIDTH>>,
+    gate_info: Vec<(String, usize)>,
+}
+
+impl<F: PrimeField, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Pow5Config<F, WIDTH, RATE, CAPACITY>
+{
+    /// The columns on which [`Pow5Chip`] enables equality: `state`, then `pad_fixed`.
+    pub fn equality_columns(&self) -> Vec<Column<Any>> {
+        equality_columns(&self.state, &self.pad_fixed)
+    }
+
+    /// The `(column, row)` at which the `i`-th word of an input block is assigned within
+    /// its add-input region, if it is a padding word.
+    ///
+    /// Padding word `i` is always assigned in `pad_fixed[i]`, on the row of the input
+    /// words, whatever the state's endianness, so that layouts (and with them verifying
+    /// keys) only change when the padding itself does.
+    pub fn padding_position(&self, i: usize) -> (Column<Fixed>, usize) {
+        (self.pad_fixed[i], ADD_INPUT_WORD_ROW)
+    }
+
+    /// The positions, as given by [`Self::padding_position`], of the padding words of
+    /// `input` in input order.
+    pub fn padding_layout(
+        &self,
+        input: &Absorbing<PaddedWord<F>, RATE>,
+    ) -> Vec<(Column<Fixed>, usize)> {
+        input
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| matches!(word, Some(PaddedWord::Padding(_))))
+            .map(|(i, _)| self.padding_position(i))
+            .collect()
+    }
+
+    /// The exponent `alpha` of the S-box enforced by the round gates.
+    pub fn sbox_exponent(&self) -> u64 {
+        self.alpha
+    }
+
+    /// The rows of a permutation's region.
+    pub fn round_layout(&self) -> RoundLayout {
+        self.layout
+    }
+
+    /// The steps of a permutation, in the order of the rows [`Pow5Chip`] assigns them.
+    ///
+    /// The first layer comes first, unless the spec is a Poseidon1 one, followed by one
+    /// step per round with the round constants of its row.
+    pub fn round_schedule(&self) -> impl Iterator<Item = RoundStep<F, WIDTH>> {
+        let layout = self.layout;
+        let round_constants = self.round_constants.clone();
+        let first = Some(RoundStep::First).filter(|_| layout.first_layer);
+
+        first.into_iter().chain((0..layout.rounds()).map(move |round| {
+            let constants = round_constants[round];
+            if layout.is_partial(round) {
+                RoundStep::Partial { round, constants }
+            } else {
+                RoundStep::Full { round, constants }
+            }
+        }))
+    }
+
+    /// The name and degree of each gate this config registered, in the order they were
+    /// created.
+    ///
+    /// Degrees are counted as the constraint system counts them, selector included, so
+    /// the largest is this chip's contribution to the circuit's degree. There is one
+    /// "partial rounds #i" gate per internal matrix, where `i` is the matrix's index in
+    /// [`Spec::internal_matrices`], and a "last full round" gate for specs that skip the
+    /// last matrix.
+    pub fn gate_info(&self) -> Vec<(String, usize)> {
+        self.gate_info.clone()
+    }
+
+    /// A human-readable summary of the parameters this config enforces, for audits.
+    ///
+    /// The matrices and round constants are summarized by 64-bit FNV-1a fingerprints of
+    /// the canonical encodings of their entries, in row-major order. These are stable
+    /// across builds, so they can be compared across deployments, but are not
+    /// collision resistant. Unlike [`Spec::constants_fingerprint`], which digests the
+    /// whole spec into one value to check against, they are short and per component,
+    /// so that two summaries show which constants differ, and need no `fingerprint`
+    /// feature.
+    pub fn describe(&self) -> String {
+        let internal = self
+            .mat_internal
+            .iter()
+            .map(|m| format!("{:016x}", fingerprint(m.to_dense().iter().flatten())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "Pow5Config: width={} rate={} capacity={} full_rounds={} partial_rounds={} \
+             alpha={} first_layer={}\n  \
+             external matrix: {:016x}\n  \
+             internal matrices: [{}]\n  \
+             round constants: {:016x}",
+            WIDTH,
+            RATE,
+            CAPACITY,
+            2 * self.layout.half_full_rounds,
+            self.layout.partial_rounds,
+            self.alpha,
+            self.layout.first_layer,
+            fingerprint(self.mat_external.iter().flatten()),
+            internal,
+            fingerprint(self.round_constants.iter().flatten()),
+        )
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Pow5Config<F, WIDTH, RATE, CAPACITY>
+{
+    /// Configures a chip for the spec `S` with the options and S-box of this one, on
+    /// newly allocated columns, selectors and, for a lookup config, table.
+    ///
+    /// A clone of a config is a handle on the same columns, which is what every region
+    /// of one chip needs, and chips assigned through a [`Layouter`] may even share
+    /// columns, as [`Pow5Chip::configure_sharing`] does. Reconfigure instead where two
+    /// sub-circuits need disjoint columns, e.g. because one lays out its regions by hand
+    /// at fixed offsets, or is later moved to a circuit of its own.
+    ///
+    /// `S` must be given again, as a config does not record the spec's type, only its
+    /// constants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constants of `S` differ from those this config was built with.
+    pub fn reconfigure<S: Spec<F, WIDTH, RATE>>(&self, meta: &mut ConstraintSystem<F>) -> Self {
+        let mat_internal = S::variant().partial_round_matrices::<F, S, WIDTH, RATE>();
+        assert!(
+            self.round_constants == S::round_constants()
+                && self.mat_external == S::external_matrix()
+                && self.mat_internal == PartialMatrix::from_spec(mat_internal),
+            "cannot reconfigure a chip for a spec with other constants"
+        );
+
+        match self.rc_a {
+            RoundConstantColumns::Lookup { .. } => {
+                let state = [(); WIDTH].map(|_| meta.advice_column());
+                let partial_sbox = meta.advice_column();
+                let rc_a = [(); WIDTH].map(|_| meta.advice_column());
+                let round_tag = meta.fixed_column();
+                let pad_fixed = [(); WIDTH].map(|_| meta.fixed_column());
+                Pow5Chip::<F, WIDTH, RATE, CAPACITY>::configure_with_lookup::<S>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    round_tag,
+                    pad_fixed,
+                )
+            }
+            RoundConstantColumns::Fixed(_) => Pow5ChipBuilder::new()
+                .low_degree(self.sbox_powers.is_some())
+                .compact(self.partial_sbox.is_none())
+                .build::<S>(meta),
+        }
+    }
+}
+
+/// The 64-bit FNV-1a hash of the canonical encodings of `words`.
+fn fingerprint<'a, F: PrimeField>(words: impl IntoIterator<Item = &'a F>) -> u64 {
+    words
+        .into_iter()
+        .flat_map(|word| word.to_repr().as_ref().to_vec())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+/// A matrix of the partial rounds, as a [`Pow5Config`] keeps it: only the diagonal of
+/// a matrix which has the Poseidon2 internal form, and the spec's matrix otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PartialMatrix<F: Clone + 'static, const WIDTH: usize> {
+    /// A matrix with ones off its diagonal.
+    Diagonal(InternalMatrix<F, WIDTH>),
+    /// Any other matrix.
+    Dense(Cow<'static, Mds<F, WIDTH>>),
+}
+
+impl<F: Field, const WIDTH: usize> PartialMatrix<F, WIDTH> {
+    /// The partial-round matrices of a spec, still borrowed where they were and not
+    /// diagonal.
+    fn from_spec(matrices: Cow<'static, [Mds<F, WIDTH>]>) -> Vec<Self> {
+        let matrix = |mat: Cow<'static, Mds<F, WIDTH>>| match InternalMatrix::from_dense(&mat) {
+            Some(internal) => PartialMatrix::Diagonal(internal),
+            None => PartialMatrix::Dense(mat),
+        };
+        match matrices {
+            Cow::Borrowed(matrices) => matrices.iter().map(Cow::Borrowed).map(matrix).collect(),
+            Cow::Owned(matrices) => matrices.into_iter().map(Cow::Owned).map(matrix).collect(),
+        }
+    }
+
+    /// The matrix with every entry written out.
+    fn to_dense(&self) -> Mds<F, WIDTH> {
+        match self {
+            PartialMatrix::Diagonal(internal) => internal.to_dense(),
+            PartialMatrix::Dense(mat) => **mat,
+        }
+    }
+}
+
+/// The rows of the region a [`Pow5Chip`] permutation assigns, relative to the row of
+/// its initial state.
+///
+/// The first layer, if any, is on the row of the initial state, and each round on the
+/// row after the previous step's, so each step's gate reads its row and writes the next.
+/// The final state is on the row after the last round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundLayout {
+    /// The number of full rounds on either side of the partial rounds.
+    pub half_full_rounds: usize,
+    /// The number of partial rounds.
+    pub partial_rounds: usize,
+    /// Whether the external matrix is applied before the first round, as in Poseidon2.
+    pub first_layer: bool,
+}
+
+impl RoundLayout {
+    /// The layout of a permutation for the spec `S`.
+    pub fn for_spec<
+        F: FromUniformBytes<64> + Ord,
+        S: Spec<F, T, RATE>,
+        const T: usize,
+        const RATE: usize,
+    >() -> Self {
+        RoundLayout {
+            half_full_rounds: S::full_rounds() / 2,
+            partial_rounds: S::partial_rounds(),
+            first_layer: S::variant().has_first_layer(),
+        }
+    }
+
+    /// The number of rounds, full and partial.
+    pub fn rounds(&self) -> usize {
+        2 * self.half_full_rounds + self.partial_rounds
+    }
+
+    /// Whether `round` is a partial round.
+    pub fn is_partial(&self, round: usize) -> bool {
+        (self.half_full_rounds..self.half_full_rounds + self.partial_rounds).contains(&round)
+    }
+
+    /// Whether `round` is the last round.
+    pub fn is_last(&self, round: usize) -> bool {
+        round + 1 == self.rounds()
+    }
+
+    /// The index of the partial round `round` among the partial rounds.
+    pub fn partial_index(&self, round: usize) -> usize {
+        debug_assert!(self.is_partial(round));
+        round - self.half_full_rounds
+    }
+
+    /// The row of the first layer, if the spec has one.
+    pub fn first_layer_row(&self) -> Option<usize> {
+        Some(0).filter(|_| self.first_layer)
+    }
+
+    /// The row of round `round`, which holds the state the round is applied to.
+    pub fn round_row(&self, round: usize) -> usize {
+        usize::from(self.first_layer) + round
+    }
+
+    /// The row of the state before rounds `[start, ..)`: the initial state for
+    /// `start == 0`, and the output of round `start - 1` otherwise.
+    pub fn start_row(&self, start: usize) -> usize {
+        if start == 0 {
+            0
+        } else {
+            self.round_row(start)
+        }
+    }
+
+    /// The number of rows of the region, from the initial to the final state.
+    pub fn rows(&self) -> usize {
+        self.round_row(self.rounds()) + 1
+    }
+}
+
+/// One row of a [`Pow5Chip`] permutation, as listed by [`Pow5Config::round_schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundStep<F, const WIDTH: usize> {
+    /// The external matrix applied to the loaded state, without round constants.
+    First,
+    /// A full round, adding `constants` to every word before its S-box.
+    Full {
+        /// The index of the round among all rounds.
+        round: usize,
+        /// The round constants of the round.
+        constants: [F; WIDTH],
+    },
+    /// A partial round; only the first word's constant feeds an S-box, the others are
+    /// assigned on the row but unused by the gate.
+    Partial {
+        /// The index of the round among all rounds.
+        round: usize,
+        /// The round constants of the round.
+        constants: [F; WIDTH],
+    },
+}
+
+fn equality_columns<const WIDTH: usize>(
+    state: &[Column<Advice>; WIDTH],
+    pad_fixed: &[Column<Fixed>; WIDTH],
+) -> Vec<Column<Any>> {
+    iter::empty()
+        .chain(state.iter().cloned().map(Column::<Any>::from))
+        .chain(pad_fixed.iter().cloned().map(Column::<Any>::from))
+        .collect()
+}
+
+/// The S-box of lane `idx` applied to `v`, with the constraints on any witnessed powers
+/// it is built from.
+fn sbox_expression<F: Field, const WIDTH: usize>(
+    meta: &mut VirtualCells<'_, F>,
+    sbox_powers: Option<SboxPowers<WIDTH>>,
+    alpha: u64,
+    idx: usize,
+    v: Expression<F>,
+) -> (Expression<F>, Vec<Expression<F>>) {
+    match sbox_powers {
+        Some(SboxPowers { squares, fourths }) => {
+            assert_eq!(alpha, 5, "low-degree mode decomposes an x^5 S-box");
+            let square = meta.query_advice(squares[idx], Rotation::cur());
+            let fourth = meta.query_advice(fourths[idx], Rotation::cur());
+            let checks = vec![
+                v.clone() * v.clone() - square.clone(),
+                square.clone() * square - fourth.clone(),
+            ];
+            (fourth * v, checks)
+        }
+        None => (sbox(v, alpha), vec![]),
+    }
+}
+
+/// Witnesses `q^2` and `q^4` for the S-box input `q` of lane `idx`, if the chip was
+/// configured in low-degree mode.
+fn assign_sbox_powers<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize,
+>(
+    region: &mut Region<F>,
+    config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+    round: usize,
+    offset: usize,
+    idx: usize,
+    q: Value<F>,
+) -> Result<(), Error> {
+    if let Some(SboxPowers { squares, fourths }) = config.sbox_powers {
+        let square = q.map(|q| q.square());
+        region.assign_advice(
+            || format!("round_{} square_{}", round, idx),
+            squares[idx],
+            offset,
+            || square,
+        )?;
+        region.assign_advice(
+            || format!("round_{} fourth_{}", round, idx),
+            fourths[idx],
+            offset,
+            || square.map(|square| square.square()),
+        )?;
+    }
+    Ok(())
+}
+
+/// The state after full round `round`, followed by the external matrix unless
+/// `apply_mds` is unset.
+fn full_round_values<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize,
+>(
+    config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+    round: usize,
+    mut state: [F; WIDTH],
+    apply_mds: bool,
+) -> [F; WIDTH] {
+    for (word, rc) in state.iter_mut().zip(config.round_constants[round].iter()) {
+        *word = sbox(*word + rc, config.alpha);
+    }
+    if apply_mds {
+        mat_mul(&mut state, &config.mat_external);
+    }
+    state
+}
+
+/// The S-box output of the first word in partial round `round`, which uses internal
+/// matrix `index`, and the state after the round.
+fn partial_round_values<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize,
+>(
+    config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+    round: usize,
+    index: usize,
+    mut state: [F; WIDTH],
+) -> (F, [F; WIDTH]) {
+    state[0] = sbox(state[0] + config.round_constants[round][0], config.alpha);
+    let next = match &config.mat_internal[index] {
+        PartialMatrix::Diagonal(internal) => internal.mul_vec(&state),
+        PartialMatrix::Dense(mat) => {
+            let mut next = state;
+            mat_mul(&mut next, mat);
+            next
+        }
+    };
+    (state[0], next)
+}
+
+/// Splits the value of a whole state into the values of its words.
+fn split_values<F: Field, const WIDTH: usize>(state: Value<[F; WIDTH]>) -> [Value<F>; WIDTH] {
+    std::array::from_fn(|i| state.map(|state| state[i]))
+}
+
+/// Builds a state from its words, failing on the first word that does, without
+/// collecting the words into a `Vec` first.
+fn try_state_from_fn<W, E, const WIDTH: usize>(
+    mut word: impl FnMut(usize) -> Result<W, E>,
+) -> Result<[W; WIDTH], E> {
+    let mut words: [Option<W>; WIDTH] = std::array::from_fn(|_| None);
+    for (i, slot) in words.iter_mut().enumerate() {
+        *slot = Some(word(i)?);
+    }
+    Ok(words.map(|word| word.expect("every word was assigned")))
+}
+
+/// The columns holding `v^2` and `v^4` for each S-box input `v`, so that its output
+/// `v^4 * v` is a degree-2 expression.
+#[derive(Clone, Copy, Debug)]
+struct SboxPowers<const WIDTH: usize> {
+    squares: [Column<Advice>; WIDTH],
+    fourths: [Column<Advice>; WIDTH],
+}
+
+/// The columns a [`Pow5Chip`] reads its round constants from.
+#[derive(Clone, Copy, Debug)]
+enum RoundConstantColumns<const WIDTH: usize> {
+    /// One fixed column per state word, assigned on every round row.
+    Fixed([Column<Fixed>; WIDTH]),
+    /// One advice column per state word, looked up by `round_tag * WIDTH + index` in a
+    /// table holding every round constant once.
+    Lookup {
+        values: [Column<Advice>; WIDTH],
+        round_tag: Column<Fixed>,
+        table: [TableColumn; 2],
+    },
+}
+
+impl<const WIDTH: usize> RoundConstantColumns<WIDTH> {
+    fn query<F: Field>(&self, meta: &mut VirtualCells<F>, idx: usize) -> Expression<F> {
+        match self {
+            Self::Fixed(rc_a) => meta.query_fixed(rc_a[idx], Rotation::cur()),
+            Self::Lookup { values, .. } => meta.query_advice(values[idx], Rotation::cur()),
+        }
+    }
+}
+
+/// The columns of a config that a [`Pow5ChipBuilder`] reuses for another chip.
+#[derive(Clone, Copy, Debug)]
+struct SharedColumns<const WIDTH: usize> {
+    state: [Column<Advice>; WIDTH],
+    partial_sbox: Option<Column<Advice>>,
+    rc_a: RoundConstantColumns<WIDTH>,
+    pad_fixed: [Column<Fixed>; WIDTH],
+    sbox_powers: Option<SboxPowers<WIDTH>>,
+}
+
+/// Builds a [`Pow5Config`] from a set of options, allocating the columns those options
+/// need.
+///
+/// The capacity is the `CAPACITY` parameter of the builder, as it is of the config it
+/// builds, and whether the permutation starts with a first layer is up to
+/// [`Spec::variant`].
+///
+/// ```
+/// # use halo2_proofs::plonk::ConstraintSystem;
+/// # use halo2curves::bn256::Fr;
+/// # use poseidon2::base::P128Pow5T3;
+/// # use poseidon2::circuit::pow5::Pow5ChipBuilder;
+/// let mut meta = ConstraintSystem::<Fr>::default();
+/// let config = Pow5ChipBuilder::<Fr, 3, 2>::new()
+///     .low_degree(true)
+///     .compact(true)
+///     .build::<P128Pow5T3<Fr>>(&mut meta);
+/// assert!(meta.degree() <= 3);
+///
+/// // A second chip on the same columns.
+/// let shared = Pow5ChipBuilder::<Fr, 3, 2>::new()
+///     .shared_columns(&config)
+///     .build::<P128Pow5T3<Fr>>(&mut meta);
+/// assert_eq!(shared.state, config.state);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pow5ChipBuilder<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize = 1,
+> {
+    low_degree: bool,
+    compact: bool,
+    shared: Option<SharedColumns<WIDTH>>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Default for Pow5ChipBuilder<F, WIDTH, RATE, CAPACITY>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Pow5ChipBuilder<F, WIDTH, RATE, CAPACITY>
+{
+    /// A builder for the config [`Pow5Chip::configure`] would build: the spec's S-box,
+    /// with a `partial_sbox` column, on newly allocated columns.
+    pub fn new() -> Self {
+        Pow5ChipBuilder {
+            low_degree: false,
+            compact: false,
+            shared: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Whether to witness S-box powers so that every gate has degree at most 3, as
+    /// [`Pow5Chip::configure_low_degree`] does. This requires an $x^5$ S-box.
+    pub fn low_degree(mut self, low_degree: bool) -> Self {
+        self.low_degree = low_degree;
+        self
+    }
+
+    /// Whether to leave out the `partial_sbox` column, as
+    /// [`Pow5Chip::configure_compact`] does.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Reuses the columns of `shared` instead of allocating new ones, under the same
+    /// conditions as [`Pow5Chip::configure_sharing`]. Columns the options need but
+    /// `shared` lacks are still allocated, and those it has but the options do not
+    /// need are left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shared` looks its round constants up in a table.
+    pub fn shared_columns<const R: usize, const C: usize>(
+        mut self,
+        shared: &Pow5Config<F, WIDTH, R, C>,
+    ) -> Self {
+        assert!(
+            matches!(shared.rc_a, RoundConstantColumns::Fixed(_)),
+            "cannot share the round constant lookup of another chip"
+        );
+        self.shared = Some(SharedColumns {
+            state: shared.state,
+            partial_sbox: shared.partial_sbox,
+            rc_a: shared.rc_a,
+            pad_fixed: shared.pad_fixed,
+            sbox_powers: shared.sbox_powers,
+        });
+        self
+    }
+
+    /// Configures a [`Pow5Chip`] for the spec `S` with the chosen options.
+    ///
+    /// Columns are allocated in a fixed order: the state, the partial S-box column, the
+    /// round constants, the padding, then the squares and fourths of a low-degree S-box,
+    /// skipping those that are shared or not needed. The constraint system, and so the
+    /// verifying key, thus depends only on the options, `S` and what `meta` held before.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the cases [`Pow5Chip::configure`] does.
+    pub fn build<S: Spec<F, WIDTH, RATE>>(
+        self,
+        meta: &mut ConstraintSystem<F>,
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        let shared = self.shared;
+        let state = match shared {
+            Some(shared) => shared.state,
+            None => [(); WIDTH].map(|_| meta.advice_column()),
+        };
+        let partial_sbox = if self.compact {
+            None
+        } else {
+            let shared = shared.and_then(|shared| shared.partial_sbox);
+            Some(shared.unwrap_or_else(|| meta.advice_column()))
+        };
+        let rc_a = match shared {
+            Some(shared) => shared.rc_a,
+            None => RoundConstantColumns::Fixed([(); WIDTH].map(|_| meta.fixed_column())),
+        };
+        let pad_fixed = match shared {
+            Some(shared) => shared.pad_fixed,
+            None => [(); WIDTH].map(|_| meta.fixed_column()),
+        };
+        let sbox_powers = if self.low_degree {
+            let shared = shared.and_then(|shared| shared.sbox_powers);
+            Some(shared.unwrap_or_else(|| SboxPowers {
+                squares: [(); WIDTH].map(|_| meta.advice_column()),
+                fourths: [(); WIDTH].map(|_| meta.advice_column()),
+            }))
+        } else {
+            None
+        };
+
+        Pow5Chip::<F, WIDTH, RATE, CAPACITY>::configure_with_constants::<S>(
+            meta,
+            state,
+            partial_sbox,
+            rc_a,
+            pad_fixed,
+            sbox_powers,
+        )
+    }
 }
 
 /// A Poseidon chip using an $x^5$ S-Box.
 ///
-/// The chip is implemented using a single round per row for full rounds, and two rounds
-/// per row for partial rounds.
+/// The chip is implemented using a single round per row, full or partial.
+///
+/// The chip requires `F: PrimeField` because each state word is a single cell, and cell
+/// values live in the proof system's scalar field, which is prime. Hashing over an
+/// extension of `F` would need each word split into its coefficients across several
+/// cells, with the S-box and matrix gates emulating extension-field multiplication;
+/// none of the gates here do that.
 #[derive(Clone, Debug)]
-pub struct Pow5Chip<F: PrimeField, const WIDTH: usize, const RATE: usize> {
-    config: Pow5Config<F, WIDTH, RATE>,
+pub struct Pow5Chip<
+    F: PrimeField,
+    const WIDTH: usize,
+    const RATE: usize,
+    const CAPACITY: usize = 1,
+> {
+    config: Pow5Config<F, WIDTH, RATE, CAPACITY>,
+    namespace: Option<String>,
 }
 
-impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Pow5Chip<F, WIDTH, RATE> {
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Pow5Chip<F, WIDTH, RATE, CAPACITY>
+{
     /// Configures this chip for use in a circuit.
     ///
     /// # Side-effects
     ///
     /// All columns in `state` will be equality-enabled.
+    ///
+    /// # Compile-time checks
+    ///
+    /// The rate and capacity must add up to the width, or configuring the chip fails to
+    /// compile:
+    ///
+    /// ```compile_fail
+    /// # use std::borrow::Cow;
+    /// # use halo2_proofs::plonk::ConstraintSystem;
+    /// # use halo2curves::bn256::Fr;
+    /// # use poseidon2::base::primitives::{Mds, Spec};
+    /// # use poseidon2::circuit::pow5::Pow5Chip;
+    /// # #[derive(Debug)]
+    /// # struct Width4Spec;
+    /// # impl Spec<Fr, 4, 2> for Width4Spec {
+    /// #     fn full_rounds() -> usize { 8 }
+    /// #     fn partial_rounds() -> usize { 56 }
+    /// #     fn alpha() -> u64 { 5 }
+    /// #     fn secure_mds() -> usize { unimplemented!() }
+    /// #     fn round_constants() -> Cow<'static, [[Fr; 4]]> { unimplemented!() }
+    /// #     fn internal_matrix() -> Cow<'static, Mds<Fr, 4>> { unimplemented!() }
+    /// #     fn external_matrix() -> Cow<'static, Mds<Fr, 4>> { unimplemented!() }
+    /// # }
+    /// let mut meta = ConstraintSystem::<Fr>::default();
+    /// let state = [0; 4].map(|_| meta.advice_column());
+    /// let partial_sbox = meta.advice_column();
+    /// let rc_a = [0; 4].map(|_| meta.fixed_column());
+    /// let pad_fixed = [0; 4].map(|_| meta.fixed_column());
+    ///
+    /// // A rate of 2 and the default capacity of 1 leave a word of the state unused.
+    /// Pow5Chip::<Fr, 4, 2>::configure::<Width4Spec>(
+    ///     &mut meta,
+    ///     state,
+    ///     partial_sbox,
+    ///     rc_a,
+    ///     pad_fixed,
+    /// );
+    /// ```
     //
     // TODO: Does the rate need to be hard-coded here, or only the width? It probably
     // needs to be known wherever we implement the hashing gadget, but it isn't strictly
@@ -62,36 +769,247 @@ impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Pow5C
         partial_sbox: Column<Advice>,
         rc_a: [Column<Fixed>; WIDTH],
         pad_fixed: [Column<Fixed>; WIDTH],
-    ) -> Pow5Config<F, WIDTH, RATE> {
-        assert_eq!(RATE, WIDTH - 1);
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        Self::configure_with_constants::<S>(
+            meta,
+            state,
+            Some(partial_sbox),
+            RoundConstantColumns::Fixed(rc_a),
+            pad_fixed,
+            None,
+        )
+    }
+
+    /// Configures this chip with every gate of degree at most 3, for provers that cap
+    /// the constraint degree.
+    ///
+    /// Each S-box input `v` has `v^2` and `v^4` witnessed in `squares` and `fourths`
+    /// on its round's row, which costs `2 * WIDTH` extra advice columns but no rows.
+    /// Partial rounds only use the first column of each.
+    ///
+    /// This is the way to lower the degree of the S-box; it cannot be looked up in an
+    /// `(x, x^5)` table instead, as its inputs range over the whole field, which no
+    /// table can hold.
+    pub fn configure_low_degree<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        partial_sbox: Column<Advice>,
+        rc_a: [Column<Fixed>; WIDTH],
+        pad_fixed: [Column<Fixed>; WIDTH],
+        squares: [Column<Advice>; WIDTH],
+        fourths: [Column<Advice>; WIDTH],
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        Self::configure_with_constants::<S>(
+            meta,
+            state,
+            Some(partial_sbox),
+            RoundConstantColumns::Fixed(rc_a),
+            pad_fixed,
+            Some(SboxPowers { squares, fourths }),
+        )
+    }
+
+    /// Configures this chip without a `partial_sbox` column, for circuits that are
+    /// bound by their number of advice columns.
+    ///
+    /// The partial-round gate then applies the S-box to `state[0]` inline instead of
+    /// reading its output from `partial_sbox`. That raises the gate to the degree of the
+    /// full-round gate, so the circuit degree is unchanged, and as the chip already
+    /// assigns one round per row this saves the column without costing any rows.
+    pub fn configure_compact<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        rc_a: [Column<Fixed>; WIDTH],
+        pad_fixed: [Column<Fixed>; WIDTH],
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        Self::configure_with_constants::<S>(
+            meta,
+            state,
+            None,
+            RoundConstantColumns::Fixed(rc_a),
+            pad_fixed,
+            None,
+        )
+    }
+
+    /// Configures this chip like [`Pow5Chip::configure`], after checking that the
+    /// [`Spec::constants_fingerprint`] of `S` is `expected`, e.g. the fingerprint of
+    /// the published parameters the circuit is meant to use.
+    ///
+    /// Nothing is allocated in `meta` if the fingerprints differ.
+    #[cfg(feature = "fingerprint")]
+    pub fn configure_with_expected_fingerprint<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        partial_sbox: Column<Advice>,
+        rc_a: [Column<Fixed>; WIDTH],
+        pad_fixed: [Column<Fixed>; WIDTH],
+        expected: &[u8; 32],
+    ) -> Result<Pow5Config<F, WIDTH, RATE, CAPACITY>, FingerprintMismatch> {
+        let actual = S::constants_fingerprint();
+        if actual != *expected {
+            return Err(FingerprintMismatch {
+                expected: *expected,
+                actual,
+            });
+        }
+        Ok(Self::configure::<S>(meta, state, partial_sbox, rc_a, pad_fixed))
+    }
+
+    /// Configures this chip to witness its round constants in the advice columns
+    /// `rc_a` and look them up in a table, rather than assigning them in fixed columns.
+    ///
+    /// Each permutation then only assigns the fixed `round_tag` column, i.e. one fixed
+    /// cell per round instead of `WIDTH`, at the cost of `WIDTH` extra advice columns
+    /// and a table of `(R_F + R_P + 1) * WIDTH` rows which must be loaded once with
+    /// [`Pow5Chip::load_round_constant_table`].
+    pub fn configure_with_lookup<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        partial_sbox: Column<Advice>,
+        rc_a: [Column<Advice>; WIDTH],
+        round_tag: Column<Fixed>,
+        pad_fixed: [Column<Fixed>; WIDTH],
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        let table = [0; 2].map(|_| meta.lookup_table_column());
+
+        // Rows outside of a round have a zero tag and unassigned (zero) constants,
+        // which hit the `index -> 0` entries loaded for tag 0.
+        for (idx, column) in rc_a.iter().enumerate() {
+            meta.lookup("round constant", |meta| {
+                let round_tag = meta.query_fixed(round_tag, Rotation::cur());
+                let value = meta.query_advice(*column, Rotation::cur());
+                let key = round_tag * Expression::Constant(F::from(WIDTH as u64))
+                    + Expression::Constant(F::from(idx as u64));
+
+                vec![(key, table[0]), (value, table[1])]
+            });
+        }
+
+        Self::configure_with_constants::<S>(
+            meta,
+            state,
+            Some(partial_sbox),
+            RoundConstantColumns::Lookup {
+                values: rc_a,
+                round_tag,
+                table,
+            },
+            pad_fixed,
+            None,
+        )
+    }
+
+    /// Configures this chip for the spec `S` on the columns of an existing config, so
+    /// that several chips of the same width cost the columns of one.
+    ///
+    /// The new chip reuses the `state`, `partial_sbox`, `rc_a` and `pad_fixed` columns
+    /// of `shared`, as well as any low-degree S-box columns, but gets its own selectors
+    /// and gates, so `S` may differ from the spec `shared` was configured for, as may
+    /// the rate and capacity.
+    ///
+    /// Sharing is sound because each gate is only enabled on the rows its own chip
+    /// assigned, and every round constant is assigned by the region of the permutation
+    /// that uses it. This relies on the regions of the two chips not overlapping in the
+    /// shared columns, which the floor planner guarantees for regions assigned through
+    /// a [`Layouter`]; regions laid out by hand at fixed offsets must keep to separate
+    /// rows themselves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shared` looks its round constants up in a table, as that table only
+    /// holds the constants of the spec it was loaded for.
+    pub fn configure_sharing<S: Spec<F, WIDTH, RATE>, const R: usize, const C: usize>(
+        meta: &mut ConstraintSystem<F>,
+        shared: &Pow5Config<F, WIDTH, R, C>,
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        assert!(
+            matches!(shared.rc_a, RoundConstantColumns::Fixed(_)),
+            "cannot share the round constant lookup of another chip"
+        );
+        Self::configure_with_constants::<S>(
+            meta,
+            shared.state,
+            shared.partial_sbox,
+            shared.rc_a,
+            shared.pad_fixed,
+            shared.sbox_powers,
+        )
+    }
+
+    /// Evaluated when a chip is configured, so that a chip whose rate and capacity do
+    /// not add up to its width fails to compile rather than to configure.
+    const RATE_AND_CAPACITY_FILL_WIDTH: () = assert!(
+        RATE + CAPACITY == WIDTH,
+        "rate and capacity must add up to the width"
+    );
+
+    fn configure_with_constants<S: Spec<F, WIDTH, RATE>>(
+        meta: &mut ConstraintSystem<F>,
+        state: [Column<Advice>; WIDTH],
+        partial_sbox: Option<Column<Advice>>,
+        rc_a: RoundConstantColumns<WIDTH>,
+        pad_fixed: [Column<Fixed>; WIDTH],
+        sbox_powers: Option<SboxPowers<WIDTH>>,
+    ) -> Pow5Config<F, WIDTH, RATE, CAPACITY> {
+        // A single word would leave no capacity, so the "hash" would be the permutation
+        // of its input alone.
+        assert!(WIDTH >= 2, "Pow5Chip needs a width of at least 2, got {}", WIDTH);
+        assert!(CAPACITY >= 1, "Pow5Chip needs at least one capacity element");
+        let () = Self::RATE_AND_CAPACITY_FILL_WIDTH;
+        // Big-endian states place a single capacity element before the rate.
+        assert!(
+            CAPACITY == 1 || S::state_endianness() == StateEndianness::Little,
+            "big-endian states have a single capacity element"
+        );
         // Generate constants for the Poseidon permutation.
-        // This gadget requires R_F and R_P to be even.
+        // This gadget requires R_F to be even.
         assert!(S::full_rounds() & 1 == 0);
-        assert!(S::partial_rounds() & 1 == 0);
-        let half_full_rounds = S::full_rounds() / 2;
-        let full_partial_rounds = S::partial_rounds();
-        let (round_constants, mat_internal, mat_external) = S::constants();
+        let layout = RoundLayout::for_spec::<F, S, WIDTH, RATE>();
+        let round_constants = S::round_constants();
+        let alpha = S::alpha();
+        let variant = S::variant();
+        let mat_internal = variant.partial_round_matrices::<F, S, WIDTH, RATE>();
+        let mat_external = S::external_matrix();
+        assert!(!mat_internal.is_empty(), "spec must have an internal matrix");
+        // `Mds` fixes both matrices to WIDTH x WIDTH, and each row of round constants to
+        // WIDTH entries, but the rows are a slice indexed by round, which would otherwise
+        // only fail once the rounds are assigned. A table built for another width and
+        // regrouped into rows of WIDTH has the wrong number of rows, so one row per round
+        // is required exactly.
+        assert!(
+            round_constants.len() == S::full_rounds() + S::partial_rounds(),
+            "spec has round constants for {} rounds, but needs {}",
+            round_constants.len(),
+            S::full_rounds() + S::partial_rounds()
+        );
+        // A Poseidon1 spec plugged in here would use one MDS for every round, which
+        // still yields a valid-looking, but different, permutation.
+        if S::is_poseidon2() {
+            assert!(
+                mat_internal.iter().all(|m| *m != *mat_external),
+                "Poseidon2 spec must use distinct internal and external matrices"
+            );
+        }
 
         // This allows state words to be initialized (by constraining them equal to fixed
         // values), and used in a permutation from an arbitrary region. rc_a is used in
         // every permutation round.
-        for column in iter::empty()
-            .chain(state.iter().cloned().map(Column::<Any>::from))
-            .chain(pad_fixed.iter().cloned().map(Column::<Any>::from))
-        {
+        for column in equality_columns(&state, &pad_fixed) {
             meta.enable_equality(column);
         }
 
         let s_full = meta.selector();
+        let s_last = if S::skip_last_mds() {
+            Some(meta.selector())
+        } else {
+            None
+        };
         let s_first = meta.selector();
-        let s_partial = meta.selector();
+        let s_partial: Vec<_> = mat_internal.iter().map(|_| meta.selector()).collect();
         let s_pad_and_add = meta.selector();
 
-        let alpha = [5, 0, 0, 0];
-        let pow_5 = |v: Expression<F>| {
-            let v2 = v.clone() * v.clone();
-            v2.clone() * v2 * v
-        };
+        let first_gate = meta.gates().len();
 
         meta.create_gate("first layer", |meta| {
             let s_first = meta.query_selector(s_first);
@@ -116,63 +1034,114 @@ impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Pow5C
 
         meta.create_gate("full round", |meta| {
             let s_full = meta.query_selector(s_full);
+            let (sboxes, checks): (Vec<_>, Vec<_>) = (0..WIDTH)
+                .map(|idx| {
+                    let state_cur = meta.query_advice(state[idx], Rotation::cur());
+                    let rc_a = rc_a.query(meta, idx);
+                    sbox_expression(meta, sbox_powers, alpha, idx, state_cur + rc_a)
+                })
+                .unzip();
 
             Constraints::with_selector(
                 s_full,
-                (0..WIDTH)
-                    .map(|next_idx| {
+                checks
+                    .into_iter()
+                    .flatten()
+                    .chain((0..WIDTH).map(|next_idx| {
                         let state_next = meta.query_advice(state[next_idx], Rotation::next());
-                        let expr = (0..WIDTH)
-                            .map(|idx| {
-                                let state_cur = meta.query_advice(state[idx], Rotation::cur());
-                                let rc_a = meta.query_fixed(rc_a[idx], Rotation::cur());
-                                pow_5(state_cur + rc_a) * mat_external[next_idx][idx]
-                            })
+                        let expr = sboxes
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, sbox)| sbox.clone() * mat_external[next_idx][idx])
                             .reduce(|acc, term| acc + term)
                             .expect("WIDTH > 0");
                         expr - state_next
-                    })
+                    }))
                     .collect::<Vec<_>>(),
             )
         });
 
-        meta.create_gate("partial rounds", |meta| {
-            let cur_0 = meta.query_advice(state[0], Rotation::cur());
-            let mid_0 = meta.query_advice(partial_sbox, Rotation::cur());
-            let rc_a0 = meta.query_fixed(rc_a[0], Rotation::cur());
-            let s_partial = meta.query_selector(s_partial);
-
-            use halo2_proofs::plonk::VirtualCells;
-            let mid = |idx: usize, meta: &mut VirtualCells<F>| {
-                let mid = mid_0.clone() * mat_internal[idx][0];
-                (1..WIDTH).fold(mid, |acc, cur_idx| {
-                    let cur = meta.query_advice(state[cur_idx], Rotation::cur());
-                    acc + cur * mat_internal[idx][cur_idx]
-                })
-            };
+        if let Some(s_last) = s_last {
+            meta.create_gate("last full round", |meta| {
+                let s_last = meta.query_selector(s_last);
 
-            let next = |idx: usize, meta: &mut VirtualCells<F>| {
-                meta.query_advice(state[idx], Rotation::next())
-            };
+                Constraints::with_selector(
+                    s_last,
+                    (0..WIDTH)
+                        .flat_map(|idx| {
+                            let state_cur = meta.query_advice(state[idx], Rotation::cur());
+                            let state_next = meta.query_advice(state[idx], Rotation::next());
+                            let rc_a = rc_a.query(meta, idx);
+                            let (sbox, checks) = sbox_expression(meta, sbox_powers, alpha, idx, state_cur + rc_a);
+                            checks.into_iter().chain(Some(sbox - state_next))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            });
+        }
 
-            let partial_round_linear = |idx: usize, meta: &mut VirtualCells<F>| {
-                mid(idx, meta)
-            };
+        let partial_matrices = PartialMatrix::from_spec(mat_internal.clone());
+        for (index, (&s_partial, matrix)) in s_partial.iter().zip(&partial_matrices).enumerate() {
+            meta.create_gate(format!("partial rounds #{}", index), |meta| {
+                let cur_0 = meta.query_advice(state[0], Rotation::cur());
+                let rc_a0 = rc_a.query(meta, 0);
+                let s_partial = meta.query_selector(s_partial);
 
-            Constraints::with_selector(
-                s_partial,
-                std::iter::empty()
-                    .chain(Some(pow_5(cur_0 + rc_a0) - mid_0.clone()))
-                    .chain((0..WIDTH).map(|idx| partial_round_linear(idx, meta) - next(idx, meta)))
-                    .collect::<Vec<_>>(),
-            )
-        });
+                // Without a partial_sbox column, the S-box output is used inline.
+                let (sbox_0, mut sbox_checks) = sbox_expression(meta, sbox_powers, alpha, 0, cur_0 + rc_a0);
+                let mid_0 = match partial_sbox {
+                    Some(partial_sbox) => {
+                        let mid_0 = meta.query_advice(partial_sbox, Rotation::cur());
+                        sbox_checks.push(sbox_0 - mid_0.clone());
+                        mid_0
+                    }
+                    None => sbox_0,
+                };
+
+                let words: Vec<_> = iter::once(mid_0)
+                    .chain((1..WIDTH).map(|idx| meta.query_advice(state[idx], Rotation::cur())))
+                    .collect();
+                // A diagonal internal matrix adds one product per word to their sum.
+                let mid = |idx: usize| match matrix {
+                    PartialMatrix::Diagonal(internal) => {
+                        let sum = words.iter().cloned().reduce(|acc, word| acc + word);
+                        sum.expect("WIDTH > 0") + words[idx].clone() * internal.diagonal[idx]
+                    }
+                    PartialMatrix::Dense(mat) => words
+                        .iter()
+                        .enumerate()
+                        .map(|(cur_idx, word)| word.clone() * mat[idx][cur_idx])
+                        .reduce(|acc, term| acc + term)
+                        .expect("WIDTH > 0"),
+                };
+
+                let next = |idx: usize, meta: &mut VirtualCells<F>| {
+                    meta.query_advice(state[idx], Rotation::next())
+                };
+
+                Constraints::with_selector(
+                    s_partial,
+                    std::iter::empty()
+                        .chain(sbox_checks)
+                        .chain((0..WIDTH).map(|idx| mid(idx) - next(idx, meta)))
+                        .collect::<Vec<_>>(),
+                )
+            });
+        }
 
         meta.create_gate("pad-and-add", |meta| {
-            let initial_state_rate = meta.query_advice(state[RATE], Rotation::prev());
-            let output_state_rate = meta.query_advice(state[RATE], Rotation::next());
+            let endianness = S::state_endianness();
+            let rate: Vec<_> = (0..RATE).map(|i| endianness.rate_index(WIDTH, i)).collect();
+            let capacity: Vec<_> = (0..WIDTH).filter(|idx| !rate.contains(idx)).collect();
             let s_pad_and_add = meta.query_selector(s_pad_and_add);
 
+            // The capacity elements are never altered by the input.
+            let keep = |idx: usize, meta: &mut VirtualCells<F>| {
+                meta.query_advice(state[idx], Rotation::prev())
+                    - meta.query_advice(state[idx], Rotation::next())
+            };
+            let keep_capacity: Vec<_> = capacity.iter().map(|&idx| keep(idx, meta)).collect();
+
             let pad_and_add = |idx: usize| {
                 let initial_state = meta.query_advice(state[idx], Rotation::prev());
                 let input = meta.query_advice(state[idx], Rotation::cur());
@@ -185,40 +1154,451 @@ impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Pow5C
 
             Constraints::with_selector(
                 s_pad_and_add,
-                (0..RATE)
-                    .map(pad_and_add)
-                    // The capacity element is never altered by the input.
-                    .chain(Some(initial_state_rate - output_state_rate))
+                rate.iter()
+                    .map(|&idx| pad_and_add(idx))
+                    .chain(keep_capacity)
                     .collect::<Vec<_>>(),
             )
         });
 
+        let gate_info = meta.gates()[first_gate..]
+            .iter()
+            .map(|gate| {
+                let degree = gate.polynomials().iter().map(|poly| poly.degree()).max();
+                (gate.name().to_string(), degree.unwrap_or(0))
+            })
+            .collect();
+
+        #[cfg(feature = "log-schedule")]
+        log::info!(
+            "Pow5Chip schedule: width={} rate={} full_rounds={} partial_rounds={} \
+             first_layer={} mat_external={}x{} mat_internal={}x{}",
+            WIDTH,
+            RATE,
+            2 * layout.half_full_rounds,
+            layout.partial_rounds,
+            layout.first_layer,
+            mat_external.len(),
+            WIDTH,
+            mat_internal[0].len(),
+            WIDTH,
+        );
+
         Pow5Config {
             state,
             partial_sbox,
+            sbox_powers,
             rc_a,
             pad_fixed,
             s_full,
+            s_last,
             s_first,
             s_partial,
             s_pad_and_add,
-            half_full_rounds,
-            full_partial_rounds,
+            layout,
             alpha,
             round_constants,
             mat_external,
-            mat_internal,
+            mat_internal: partial_matrices,
+            gate_info,
         }
     }
 
     /// Construct a [`Pow5Chip`].
-    pub fn construct(config: Pow5Config<F, WIDTH, RATE>) -> Self {
-        Pow5Chip { config }
+    pub fn construct(config: Pow5Config<F, WIDTH, RATE, CAPACITY>) -> Self {
+        Pow5Chip {
+            config,
+            namespace: None,
+        }
+    }
+
+    /// Prefixes the names of the regions assigned by this chip with `namespace`, so
+    /// that several hashers sharing one config can be told apart in layouts.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    fn region_name(&self, name: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}: {}", namespace, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// Loads the round-constant table of a chip configured with
+    /// [`Pow5Chip::configure_with_lookup`]. This must be called exactly once per
+    /// circuit, and does nothing for a chip using fixed round-constant columns.
+    pub fn load_round_constant_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
+        let config = self.config();
+        let table = match config.rc_a {
+            RoundConstantColumns::Fixed(_) => return Ok(()),
+            RoundConstantColumns::Lookup { table, .. } => table,
+        };
+
+        layouter.assign_table(
+            || "round constants",
+            |mut table_region| {
+                // Tag 0 marks rows which are not a round, where every constant is 0.
+                let entries = (0..WIDTH).map(|idx| (idx, F::ZERO)).chain(
+                    config
+                        .round_constants
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(round, rc)| {
+                            rc.iter()
+                                .enumerate()
+                                .map(move |(idx, value)| ((round + 1) * WIDTH + idx, *value))
+                        }),
+                );
+
+                for (offset, (key, value)) in entries.enumerate() {
+                    table_region.assign_cell(
+                        || "round constant key",
+                        table[0],
+                        offset,
+                        || Value::known(F::from(key as u64)),
+                    )?;
+                    table_region.assign_cell(
+                        || "round constant value",
+                        table[1],
+                        offset,
+                        || Value::known(value),
+                    )?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    /// Loads the value at `row` of `instance` into the `index`-th state column, so that
+    /// public inputs can be absorbed by the sponge.
+    ///
+    /// `instance` must be equality-enabled by the caller.
+    pub fn load_from_instance(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        instance: Column<Instance>,
+        row: usize,
+        index: usize,
+    ) -> Result<StateWord<F>, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || self.region_name(&format!("load instance_{}", row)),
+            |mut region| {
+                region
+                    .assign_advice_from_instance(
+                        || format!("instance_{}", row),
+                        instance,
+                        row,
+                        config.state[index],
+                        0,
+                    )
+                    .map(StateWord)
+            },
+        )
+    }
+
+    /// Loads the `WIDTH` consecutive rows of `instance` starting at `start_row` into the
+    /// state columns, in order, e.g. for a state received as public input.
+    ///
+    /// `instance` must be equality-enabled by the caller.
+    pub fn load_state_from_instance(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        instance: Column<Instance>,
+        start_row: usize,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || self.region_name(&format!("load state from instance_{}", start_row)),
+            |mut region| {
+                try_state_from_fn(|i| {
+                    region
+                        .assign_advice_from_instance(
+                            || format!("state_{}", i),
+                            instance,
+                            start_row + i,
+                            config.state[i],
+                            0,
+                        )
+                        .map(StateWord)
+                })
+            },
+        )
+    }
+
+    /// Returns an initial state whose capacity element is the witnessed `key`, instead
+    /// of a domain's constant capacity element, for keyed hashing (a PRF or MAC).
+    ///
+    /// The key takes the place of the domain separator, so messages of different
+    /// domains absorbed under the same key are only told apart by their padding; use a
+    /// key per domain. The key should be uniformly random over `F` and never revealed,
+    /// and its column must be equality-enabled, as it is copied into the state.
+    pub fn initial_state_with_key<S: Spec<F, WIDTH, RATE>>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        key: &AssignedCell<F, F>,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let config = self.config();
+        layouter.assign_region(
+            || self.region_name("keyed initial state"),
+            |mut region| {
+                let capacity = S::state_endianness().capacity_index(WIDTH);
+                try_state_from_fn(|i| {
+                    let var = if i == capacity {
+                        key.copy_advice(|| "key", &mut region, config.state[i], 0)?
+                    } else {
+                        region.assign_advice_from_constant(
+                            || format!("state_{}", i),
+                            config.state[i],
+                            0,
+                            F::ZERO,
+                        )?
+                    };
+                    Ok(StateWord(var))
+                })
+            },
+        )
+    }
+
+    /// Applies only rounds `[start, end)` of the permutation to `initial_state`, and
+    /// returns the intermediate state.
+    ///
+    /// Rounds are numbered `0..R_F + R_P` in schedule order; the initial linear layer
+    /// is applied with round 0. Permuting `[0, k)` and then `[k, R_F + R_P)` is
+    /// equivalent to a full permutation, so a permutation can be split across regions
+    /// or proofs.
+    pub fn permute_rounds(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+        start: usize,
+        end: usize,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        self.assign_rounds(layouter, initial_state, start, end, None)
+    }
+
+    /// Permutes `initial_state` like [`PoseidonInstructions::permute`], in a region
+    /// named `"permute state #id"`, so that a failure in a circuit permuting in a loop
+    /// names the iteration it happened in.
+    pub fn permute_indexed(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+        id: usize,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let rounds = self.config().layout.rounds();
+        self.assign_rounds(layouter, initial_state, 0, rounds, Some(id))
+    }
+
+    /// Assigns rounds `[start, end)` in one region, whose name ends in `#id` if given.
+    fn assign_rounds(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+        start: usize,
+        end: usize,
+        id: Option<usize>,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let config = self.config();
+        let rounds = config.layout.rounds();
+        assert!(
+            start <= end && end <= rounds,
+            "invalid round range {}..{} for {} rounds",
+            start,
+            end,
+            rounds
+        );
+
+        let name = if start == 0 && end == rounds {
+            "permute state".to_string()
+        } else {
+            format!("permute rounds {}..{}", start, end)
+        };
+        let name = match id {
+            Some(id) => format!("{} #{}", name, id),
+            None => name,
+        };
+
+        layouter.assign_region(
+            || self.region_name(&name),
+            |mut region| {
+                // Load the initial state into this region.
+                let state = Pow5State::load(&mut region, config, initial_state)?;
+                let (state, _) = state.rounds(&mut region, config, start, end, 0)?;
+                Ok(state.0)
+            },
+        )
+    }
+
+    /// Permutes `initial_state` inside the caller's `region`, starting at row `offset`,
+    /// and returns the final state.
+    ///
+    /// A gate can only query rows of its own region, so a state produced by another
+    /// gadget can be used in place only when the permutation is laid out in the region
+    /// that assigned it. Each word already in `region` at `state[i]` and row `offset` is
+    /// used as is; any other word is copied there.
+    pub fn permute_borrowed(
+        &self,
+        region: &mut Region<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+        offset: usize,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let config = self.config();
+        let rounds = config.layout.rounds();
+
+        let state = Pow5State(initial_state.clone());
+        let (state, _) = state.rounds(region, config, 0, rounds, offset)?;
+
+        // The rounds were just assigned in `region`, which identifies it.
+        let region_index = state.0[0].0.cell().region_index;
+        for (i, word) in initial_state.iter().enumerate() {
+            let cell = word.0.cell();
+            let in_place = cell.region_index == region_index
+                && cell.column == config.state[i].into()
+                && cell.row_offset == offset;
+            if !in_place {
+                word.0.copy_advice(
+                    || format!("load state_{}", i),
+                    region,
+                    config.state[i],
+                    offset,
+                )?;
+            }
+        }
+
+        Ok(state.0)
+    }
+
+    /// Permutes `initial_state` like [`PoseidonInstructions::permute`], and also returns
+    /// the witnessed state after the first layer, if the spec has one, and after each
+    /// round, in order.
+    ///
+    /// This is meant for pinpointing the round at which a chip diverges from the
+    /// reference permutation, e.g. for a mistranscribed spec.
+    #[cfg(feature = "permute-trace")]
+    #[allow(clippy::type_complexity)]
+    pub fn permute_with_trace(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+    ) -> Result<(State<StateWord<F>, WIDTH>, Vec<[Value<F>; WIDTH]>), Error> {
+        let config = self.config();
+        let layout = config.layout;
+        let values = |state: &Pow5State<F, WIDTH>| state.0.clone().map(|word| word.value());
+
+        layouter.assign_region(
+            || self.region_name("permute state with trace"),
+            |mut region| {
+                let mut state = Pow5State::load(&mut region, config, initial_state)?;
+                let mut trace = Vec::with_capacity(layout.rounds() + 1);
+                if let Some(row) = layout.first_layer_row() {
+                    state = state.first_layer(&mut region, config, row)?;
+                    trace.push(values(&state));
+                }
+                for round in 0..layout.rounds() {
+                    state = state.round_at(&mut region, config, round, layout.round_row(round))?;
+                    trace.push(values(&state));
+                }
+                Ok((state.0, trace))
+            },
+        )
+    }
+
+    /// Applies the permutation `count` times to `initial_state` within a single
+    /// region, and returns the final state.
+    ///
+    /// The output row of each permutation is the input row of the next, so the
+    /// intermediate states are neither copied nor re-loaded.
+    pub fn permute_chain(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        initial_state: &State<StateWord<F>, WIDTH>,
+        count: usize,
+    ) -> Result<State<StateWord<F>, WIDTH>, Error> {
+        let config = self.config();
+        let rounds = config.layout.rounds();
+
+        layouter.assign_region(
+            || self.region_name(&format!("permute chain of {}", count)),
+            |mut region| {
+                let mut state = Pow5State::load(&mut region, config, initial_state)?;
+                let mut offset = 0;
+                for _ in 0..count {
+                    (state, offset) = state.rounds(&mut region, config, 0, rounds, offset)?;
+                }
+                Ok(state.0)
+            },
+        )
+    }
+
+    /// The number of rows in the region assigned by one permutation: the initial state,
+    /// the first layer if the spec has one, and one row per round.
+    pub fn permute_rows<S: Spec<F, WIDTH, RATE>>() -> usize {
+        RoundLayout::for_spec::<F, S, WIDTH, RATE>().rows()
+    }
+
+    /// The number of rows assigned by a sponge absorbing `input_len` words in domain `D`
+    /// and squeezing one output: the initial state, and for each padded block, prefix
+    /// included, an add-input region of three rows followed by a permutation. An empty
+    /// input only permutes the initial state.
+    pub fn sponge_rows<S: Spec<F, WIDTH, RATE>, D: Domain<F, RATE>>(input_len: usize) -> usize {
+        let padded_len = usize::from(D::prefix().is_some())
+            + input_len
+            + D::padding(input_len).into_iter().count();
+        match padded_len / RATE {
+            0 => 1 + Self::permute_rows::<S>(),
+            blocks => 1 + blocks * (3 + Self::permute_rows::<S>()),
+        }
+    }
+
+    /// The smallest `k` whose `2^k` rows fit a sponge absorbing `input_len` words in
+    /// domain `D`, together with the rows halo2 reserves for blinding and a small margin
+    /// for the floor planner.
+    ///
+    /// The reserved rows are those of the config [`Pow5Chip::configure`] builds for `S`.
+    pub fn min_k<S: Spec<F, WIDTH, RATE>, D: Domain<F, RATE>>(input_len: usize) -> u32 {
+        // Left for the caller's own small regions, such as loading the message.
+        const FLOOR_PLANNER_MARGIN: usize = 4;
+
+        let mut meta = ConstraintSystem::default();
+        Pow5ChipBuilder::<F, WIDTH, RATE, CAPACITY>::new().build::<S>(&mut meta);
+        // The blinding rows and the unusable row after them.
+        let reserved = meta.blinding_factors() + 1;
+
+        let rows = Self::sponge_rows::<S, D>(input_len) + reserved + FLOOR_PLANNER_MARGIN;
+        rows.next_power_of_two().trailing_zeros()
+    }
+
+    /// Computes the hash of `inputs` in domain `D` off-circuit, without assigning any
+    /// cells, e.g. to witness a value needed before the constraining region exists.
+    pub fn hash_value<S: Spec<F, WIDTH, RATE>, D: Domain<F, RATE>>(
+        inputs: &[Value<F>],
+    ) -> Value<F> {
+        let inputs: Value<Vec<F>> = inputs.iter().copied().collect();
+        inputs.map(|inputs| {
+            let mut sponge = Sponge::<F, S, _, WIDTH, RATE>::new(
+                D::initial_capacity_element(),
+                D::layout(WIDTH),
+            );
+            for value in D::prefix()
+                .into_iter()
+                .chain(inputs.iter().copied())
+                .chain(D::padding(inputs.len()))
+            {
+                sponge.absorb(value);
+            }
+            sponge.finish_absorbing().squeeze()
+        })
     }
 }
 
-impl<F:FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize> Chip<F> for Pow5Chip<F, WIDTH, RATE> {
-    type Config = Pow5Config<F, WIDTH, RATE>;
+impl<F: FromUniformBytes<64> + Ord, const WIDTH: usize, const RATE: usize, const CAPACITY: usize>
+    Chip<F> for Pow5Chip<F, WIDTH, RATE, CAPACITY>
+{
+    type Config = Pow5Config<F, WIDTH, RATE, CAPACITY>;
     type Loaded = ();
 
     fn config(&self) -> &Self::Config {
@@ -234,16 +1614,20 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, 3, 2>> PermuteChip<F, S, 3, 2>
     for Pow5Chip<F, 3, 2>
 {
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
-        let state = [0; 3].map(|_| meta.advice_column());
+        const WIDTH: usize = 3;
+        let state = [0; WIDTH].map(|_| meta.advice_column());
         let partial_sbox = meta.advice_column();
-        let constants = [0; 6].map(|_| meta.fixed_column());
+        // One fixed column per state word for its round constant, and one for its
+        // padding.
+        let fixed = [0; 2 * WIDTH].map(|_| meta.fixed_column());
+        let (rc_a, pad_fixed) = fixed.split_at(WIDTH);
 
         Pow5Chip::configure::<S>(
             meta,
             state,
             partial_sbox,
-            constants[..3].try_into().unwrap(), //rc_a
-            constants[3..].try_into().unwrap(), //rc_b
+            rc_a.try_into().unwrap(),
+            pad_fixed.try_into().unwrap(),
         )
     }
 
@@ -252,8 +1636,13 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, 3, 2>> PermuteChip<F, S, 3, 2>
     }
 }
 
-impl<F: FromUniformBytes<64> + Ord, S: Spec<F, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>
-    PoseidonInstructions<F, S, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE>
+impl<
+        F: FromUniformBytes<64> + Ord,
+        S: Spec<F, WIDTH, RATE>,
+        const WIDTH: usize,
+        const RATE: usize,
+        const CAPACITY: usize,
+    > PoseidonInstructions<F, S, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE, CAPACITY>
 {
     type Word = StateWord<F>;
 
@@ -263,42 +1652,8 @@ impl<F: FromUniformBytes<64> + Ord, S: Spec<F, WIDTH, RATE>, const WIDTH: usize,
         initial_state: &State<Self::Word, WIDTH>,
     ) -> Result<State<Self::Word, WIDTH>, Error> {
         let config = self.config();
-
-        layouter.assign_region(
-            || "permute state",
-            |mut region| {
-                // Load the initial state into this region.
-                let state = Pow5State::load(&mut region, config, initial_state)?;
-                let state = state.first_layer(&mut region, config)?;
-                let state = (0..config.half_full_rounds).fold(Ok(state), |res, r| {
-                    res.and_then(|state| state.full_round(&mut region, config, r, r + 1))
-                })?;
-
-                let state = (0..config.full_partial_rounds).fold(Ok(state), |res, r| {
-                    res.and_then(|state| {
-                        state.partial_round(
-                            &mut region,
-                            config,
-                            config.half_full_rounds + r,
-                            config.half_full_rounds + r + 1,
-                        )
-                    })
-                })?;
-
-                let state = (0..config.half_full_rounds).fold(Ok(state), |res, r| {
-                    res.and_then(|state| {
-                        state.full_round(
-                            &mut region,
-                            config,
-                            config.half_full_rounds + config.full_partial_rounds + r,
-                            config.half_full_rounds + config.full_partial_rounds + r + 1,
-                        )
-                    })
-                })?;
-
-                Ok(state.0)
-            },
-        )
+        let rounds = config.layout.rounds();
+        self.permute_rounds(layouter, initial_state, 0, rounds)
     }
 }
 
@@ -308,39 +1663,34 @@ impl<
         D: Domain<F, RATE>,
         const WIDTH: usize,
         const RATE: usize,
-    > PoseidonSpongeInstructions<F, S, D, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE>
+        const CAPACITY: usize,
+    > PoseidonSpongeInstructions<F, S, D, WIDTH, RATE> for Pow5Chip<F, WIDTH, RATE, CAPACITY>
 {
     fn initial_state(
         &self,
         layouter: &mut impl Layouter<F>,
     ) -> Result<State<Self::Word, WIDTH>, Error> {
         let config = self.config();
-        let state = layouter.assign_region(
-            || format!("initial state for domain {}", D::name()),
+        layouter.assign_region(
+            || self.region_name(&format!("initial state for domain {}", D::name())),
             |mut region| {
-                let mut state = Vec::with_capacity(WIDTH);
-                let mut load_state_word = |i: usize, value: F| -> Result<_, Error> {
+                let capacity = S::state_endianness().capacity_index(WIDTH);
+                try_state_from_fn(|i| {
+                    let value = if i == capacity {
+                        D::initial_capacity_element()
+                    } else {
+                        F::ZERO
+                    };
                     let var = region.assign_advice_from_constant(
                         || format!("state_{}", i),
                         config.state[i],
                         0,
                         value,
                     )?;
-                    state.push(StateWord(var));
-
-                    Ok(())
-                };
-
-                for i in 0..RATE {
-                    load_state_word(i, F::ZERO)?;
-                }
-                load_state_word(RATE, D::initial_capacity_element())?;
-
-                Ok(state)
+                    Ok(StateWord(var))
+                })
             },
-        )?;
-
-        Ok(state.try_into().unwrap())
+        )
     }
 
     fn add_input(
@@ -351,9 +1701,9 @@ impl<
     ) -> Result<State<Self::Word, WIDTH>, Error> {
         let config = self.config();
         layouter.assign_region(
-            || format!("add input for domain {}", D::name()),
+            || self.region_name(&format!("add input for domain {}", D::name())),
             |mut region| {
-                config.s_pad_and_add.enable(&mut region, 1)?;
+                config.s_pad_and_add.enable(&mut region, ADD_INPUT_WORD_ROW)?;
                 // Load the initial state into this region.
                 let load_state_word = |i: usize| {
                     initial_state[i]
@@ -362,74 +1712,88 @@ impl<
                             || format!("load state_{}", i),
                             &mut region,
                             config.state[i],
-                            0,
+                            ADD_INPUT_STATE_ROW,
                         )
                         .map(StateWord)
                 };
-                let initial_state: Result<Vec<_>, Error> =
-                    (0..WIDTH).map(load_state_word).collect();
-                let initial_state = initial_state?;
+                let initial_state: [_; WIDTH] = try_state_from_fn(load_state_word)?;
+                let endianness = S::state_endianness();
                 // Load the input into this region.
                 let load_input_word = |i: usize| {
+                    let column = config.state[endianness.rate_index(WIDTH, i)];
                     let (cell, value) = match input.0[i].clone() {
                         Some(PaddedWord::Message(word)) => (word.cell(), word.value().copied()),
                         Some(PaddedWord::Padding(padding_value)) => {
+                            let (pad_column, pad_row) = config.padding_position(i);
                             let cell = region
                                 .assign_fixed(
                                     || format!("load pad_{}", i),
-                                    config.pad_fixed[i],
-                                    1,
+                                    pad_column,
+                                    pad_row,
                                     || Value::known(padding_value),
                                 )?
                                 .cell();
                             (cell, Value::known(padding_value))
                         }
+                        Some(PaddedWord::Constant(value)) => {
+                            let var = region.assign_advice_from_constant(
+                                || format!("load constant_{}", i),
+                                column,
+                                ADD_INPUT_WORD_ROW,
+                                value,
+                            )?;
+                            return Ok(StateWord(var));
+                        }
+                        Some(PaddedWord::Instance(instance, row)) => {
+                            let var = region.assign_advice_from_instance(
+                                || format!("load instance_{}", i),
+                                instance,
+                                row,
+                                column,
+                                ADD_INPUT_WORD_ROW,
+                            )?;
+                            return Ok(StateWord(var));
+                        }
                         _ => panic!("Input is not padded"),
                     };
                     let var = region.assign_advice(
                         || format!("load input_{}", i),
-                        config.state[i],
-                        1,
+                        column,
+                        ADD_INPUT_WORD_ROW,
                         || value,
                     )?;
+                    // Copying a message word from a column without equality enabled would
+                    // leave it unconstrained; halo2 rejects it with
+                    // `Error::ColumnNotInPermutation`, naming the message column.
                     region.constrain_equal(cell, var.cell())?;
 
                     Ok(StateWord(var))
                 };
-                let input: Result<Vec<_>, Error> = (0..RATE).map(load_input_word).collect();
-                let input = input?;
-                // Constrain the output.
+                let input = try_state_from_fn::<_, Error, RATE>(load_input_word)?;
+                // Constrain the output, computed as the reference sponge adds a block.
+                let mut output: State<Value<F>, WIDTH> =
+                    std::array::from_fn(|i| initial_state[i].0.value().copied());
+                endianness.add_to_rate(&mut output, input.iter().map(|word| word.0.value().copied()));
                 let constrain_output_word = |i: usize| {
-                    let value = initial_state[i].0.value().copied()
-                        + input
-                            .get(i)
-                            .map(|word| word.0.value().cloned())
-                            // The capacity element is never altered by the input.
-                            .unwrap_or_else(|| Value::known(F::ZERO));
                     region
                         .assign_advice(
                             || format!("load output_{}", i),
                             config.state[i],
-                            2,
-                            || value,
+                            ADD_INPUT_OUTPUT_ROW,
+                            || output[i],
                         )
                         .map(StateWord)
                 };
-                let output: Result<Vec<_>, Error> = (0..WIDTH).map(constrain_output_word).collect();
-                output.map(|output| output.try_into().unwrap())
+                try_state_from_fn(constrain_output_word)
             },
         )
     }
 
     fn get_output(state: &State<Self::Word, WIDTH>) -> Squeezing<Self::Word, RATE> {
-        Squeezing(
-            state[..RATE]
-                .iter()
-                .map(|word| Some(word.clone()))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        )
+        let endianness = S::state_endianness();
+        Squeezing(std::array::from_fn(|i| {
+            Some(state[endianness.rate_index(WIDTH, i)].clone())
+        }))
     }
 }
 
@@ -449,6 +1813,15 @@ impl<F: Field> From<AssignedCell<F, F>> for StateWord<F> {
     }
 }
 
+impl<F: PrimeField<Repr = [u8; 32]>> StateWord<F> {
+    /// The canonical encoding of this word, e.g. to compare a hash output with a digest
+    /// computed elsewhere. It is only known where the witness is, such as under
+    /// `MockProver`.
+    pub fn to_bytes(&self) -> Value<[u8; 32]> {
+        self.0.value().map(|value| value.to_repr())
+    }
+}
+
 impl<F: Field> Var<F> for StateWord<F> {
     fn cell(&self) -> Cell {
         self.0.cell()
@@ -464,9 +1837,9 @@ struct Pow5State<F: PrimeField, const WIDTH: usize>([StateWord<F>; WIDTH]);
 
 impl<F: PrimeField, const WIDTH: usize> Pow5State<F, WIDTH> {
 
-    fn load<const RATE: usize>(
+    fn load<const RATE: usize, const CAPACITY: usize>(
         region: &mut Region<F>,
-        config: &Pow5Config<F, WIDTH, RATE>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
         initial_state: &State<StateWord<F>, WIDTH>,
     ) -> Result<Self, Error> {
         let load_state_word = |i: usize| {
@@ -476,137 +1849,190 @@ impl<F: PrimeField, const WIDTH: usize> Pow5State<F, WIDTH> {
                 .map(StateWord)
         };
 
-        let state: Result<Vec<_>, _> = (0..WIDTH).map(load_state_word).collect();
-        state.map(|state| Pow5State(state.try_into().unwrap()))
+        try_state_from_fn(load_state_word).map(Pow5State)
     }
 
-    fn first_layer<const RATE: usize>(
+    fn first_layer<const RATE: usize, const CAPACITY: usize>(
         self,
         region: &mut Region<F>,
-        config: &Pow5Config<F, WIDTH, RATE>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+        offset: usize,
     ) -> Result<Self, Error> {
-        let offset = 0; // first layer
         config.s_first.enable(region, offset)?;
-            let q = self.0.iter().map(|word| {
-                word.0
-                    .value()
-                    .map(|v| *v)
-            });
-            let r: Value<Vec<F>> = q.collect();
-            let m = &config.mat_external;
-            let state = m.iter().map(|m_i| {
-                r.as_ref().map(|r| {
-                    r.iter()
-                        .enumerate()
-                        .fold(F::ZERO, |acc, (j, r_j)| acc + m_i[j] * r_j)
-                })
-            });
+        let state = split_values(self.values().map(|mut v| {
+            mat_mul(&mut v, &config.mat_external);
+            v
+        }));
+        let next_state_word = |i: usize| {
+            let value = state[i];
+            let var = region.assign_advice(
+                || format!("pre_round state_{}", i),
+                config.state[i],
+                offset + 1,
+                || value,
+            )?;
+            Ok(StateWord(var))
+        };
 
-            let state: [Value<F>; WIDTH] = state.collect::<Vec<_>>().try_into().unwrap();
-            let next_state_word = |i: usize| {
-                let value = state[i];
-                let var = region.assign_advice(
-                    || format!("pre_round state_{}", i),
-                    config.state[i],
-                    offset + 1,
-                    || value,
-                )?;
-                Ok(StateWord(var))
-            };
-    
-            let next_state: Result<Vec<_>, _> = (0..WIDTH).map(next_state_word).collect();
-            next_state.map(|next_state| Pow5State(next_state.try_into().unwrap()))
+        try_state_from_fn(next_state_word).map(Pow5State)
     }
 
-    fn full_round<const RATE: usize>(
+    /// Applies rounds `[start, end)` to the state at row `offset`, and returns the
+    /// resulting state together with the row it was assigned to.
+    fn rounds<const RATE: usize, const CAPACITY: usize>(
         self,
         region: &mut Region<F>,
-        config: &Pow5Config<F, WIDTH, RATE>,
-        round: usize,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+        start: usize,
+        end: usize,
         offset: usize,
-    ) -> Result<Self, Error> {
-        Self::round(region, config, round, offset, config.s_full, |_| {
-            let q = self.0.iter().enumerate().map(|(idx, word)| {
-                word.0
-                    .value()
-                    .map(|v| *v + config.round_constants[round][idx])
-            });
-            let r: Value<Vec<F>> = q.map(|q| q.map(|q| q.pow(config.alpha))).collect();
-            let m = &config.mat_external;
-            let state = m.iter().map(|m_i| {
-                r.as_ref().map(|r| {
-                    r.iter()
-                        .enumerate()
-                        .fold(F::ZERO, |acc, (j, r_j)| acc + m_i[j] * r_j)
-                })
-            });
+    ) -> Result<(Self, usize), Error> {
+        let layout = config.layout;
+        if start == end {
+            return Ok((self, offset));
+        }
+        // The layout's rows are relative to the initial state, and `offset` is that of
+        // the state before round `start`.
+        let at = |row: usize| offset + row - layout.start_row(start);
 
-            Ok((round + 1, state.collect::<Vec<_>>().try_into().unwrap()))
-        })
+        let mut state = self;
+        if start == 0 {
+            if let Some(row) = layout.first_layer_row() {
+                state = state.first_layer(region, config, at(row))?;
+            }
+        }
+        for round in start..end {
+            state = state.round_at(region, config, round, at(layout.round_row(round)))?;
+        }
+
+        Ok((state, at(layout.round_row(end))))
     }
 
-    fn partial_round<const RATE: usize>(
+    /// Applies round `round`, full or partial, to the state at row `offset`.
+    fn round_at<const RATE: usize, const CAPACITY: usize>(
         self,
         region: &mut Region<F>,
-        config: &Pow5Config<F, WIDTH, RATE>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
         round: usize,
         offset: usize,
     ) -> Result<Self, Error> {
-        Self::round(region, config, round, offset, config.s_partial, |region| {
-            let m = &config.mat_internal;
-            let p: Value<Vec<_>> = self.0.iter().map(|word| word.0.value().cloned()).collect();
-
-            let r: Value<Vec<_>> = p.map(|p| {
-                let r_0 = (p[0] + config.round_constants[round][0]).pow(config.alpha);
-                let r_i = p[1..]
-                    .iter()
-                    .copied();
-                std::iter::empty().chain(Some(r_0)).chain(r_i).collect()
-            });
+        if config.layout.is_partial(round) {
+            self.partial_round(region, config, round, offset)
+        } else {
+            self.full_round(region, config, round, offset)
+        }
+    }
 
-            region.assign_advice(
-                || format!("round_{} partial_sbox", round),
-                config.partial_sbox,
-                offset,
-                || r.as_ref().map(|r| r[0]),
-            )?;
+    fn full_round<const RATE: usize, const CAPACITY: usize>(
+        self,
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+        round: usize,
+        offset: usize,
+    ) -> Result<Self, Error> {
+        let last = config.layout.is_last(round);
+        let s_last = config.s_last.filter(|_| last);
+        let round_gate = s_last.unwrap_or(config.s_full);
 
-            let state: Vec<Value<_>> = m
-                .iter()
-                .map(|m_i| {
-                    r.as_ref().map(|r| {
-                        m_i.iter()
-                            .zip(r.iter())
-                            .fold(F::ZERO, |acc, (m_ij, r_j)| acc + *m_ij * r_j)
-                    })
-                })
-                .collect();
+        Self::round(region, config, round, offset, round_gate, |region| {
+            let values = self.values();
+            for idx in 0..WIDTH {
+                let q = values.map(|v| v[idx] + config.round_constants[round][idx]);
+                assign_sbox_powers(region, config, round, offset, idx, q)?;
+            }
+            let next = values.map(|v| full_round_values(config, round, v, s_last.is_none()));
 
-            Ok((round + 1, state.try_into().unwrap()))
+            Ok((round + 1, split_values(next)))
         })
     }
 
-    fn round<const RATE: usize>(
+    fn partial_round<const RATE: usize, const CAPACITY: usize>(
+        self,
         region: &mut Region<F>,
-        config: &Pow5Config<F, WIDTH, RATE>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
         round: usize,
         offset: usize,
-        round_gate: Selector,
-        round_fn: impl FnOnce(&mut Region<F>) -> Result<(usize, [Value<F>; WIDTH]), Error>,
     ) -> Result<Self, Error> {
-        // Enable the required gate.
-        round_gate.enable(region, offset)?;
-        // Load the round constants.
-        let mut load_round_constant = |i: usize| {
-            region.assign_fixed(
-                || format!("round_{} rc_{}", round, i),
-                config.rc_a[i],
-                offset,
-                || Value::known(config.round_constants[round][i]),
-            )
-        };
-        for i in 0..WIDTH {
-            load_round_constant(i)?;
+        let index = config.layout.partial_index(round) % config.mat_internal.len();
+        Self::round(region, config, round, offset, config.s_partial[index], |region| {
+            let values = self.values();
+            let q_0 = values.map(|v| v[0] + config.round_constants[round][0]);
+            assign_sbox_powers(region, config, round, offset, 0, q_0)?;
+            let next = values.map(|v| partial_round_values(config, round, index, v));
+
+            if let Some(partial_sbox) = config.partial_sbox {
+                region.assign_advice(
+                    || format!("round_{} partial_sbox", round),
+                    partial_sbox,
+                    offset,
+                    || next.map(|(r_0, _)| r_0),
+                )?;
+            }
+
+            Ok((round + 1, split_values(next.map(|(_, state)| state))))
+        })
+    }
+
+    /// The values of the state words, gathered without allocating.
+    fn values(&self) -> Value<[F; WIDTH]> {
+        self.0
+            .iter()
+            .enumerate()
+            .fold(Value::known([F::ZERO; WIDTH]), |acc, (i, word)| {
+                acc.zip(word.0.value()).map(|(mut values, value)| {
+                    values[i] = *value;
+                    values
+                })
+            })
+    }
+
+    fn round<const RATE: usize, const CAPACITY: usize>(
+        region: &mut Region<F>,
+        config: &Pow5Config<F, WIDTH, RATE, CAPACITY>,
+        round: usize,
+        offset: usize,
+        round_gate: Selector,
+        round_fn: impl FnOnce(&mut Region<F>) -> Result<(usize, [Value<F>; WIDTH]), Error>,
+    ) -> Result<Self, Error> {
+        // Enable the required gate.
+        round_gate.enable(region, offset)?;
+        // Load the round constants.
+        match config.rc_a {
+            // A gate only sees the row it is enabled on, and rotations from it, so each
+            // round row needs its own copy of the constants, even though they repeat from
+            // one permutation to the next; the rows of a shared copy would sit at a
+            // different rotation from every permutation. These assignments only cost
+            // anything at keygen: the prover's witness assignment ignores fixed cells
+            // without evaluating their closures. A chip configured with
+            // `configure_with_lookup` assigns one tag per row and the constants once.
+            RoundConstantColumns::Fixed(rc_a) => {
+                for (i, column) in rc_a.iter().enumerate() {
+                    region.assign_fixed(
+                        || format!("round_{} rc_{}", round, i),
+                        *column,
+                        offset,
+                        || Value::known(config.round_constants[round][i]),
+                    )?;
+                }
+            }
+            RoundConstantColumns::Lookup {
+                values, round_tag, ..
+            } => {
+                region.assign_fixed(
+                    || format!("round_{} tag", round),
+                    round_tag,
+                    offset,
+                    || Value::known(F::from(round as u64 + 1)),
+                )?;
+                for (i, column) in values.iter().enumerate() {
+                    region.assign_advice(
+                        || format!("round_{} rc_{}", round, i),
+                        *column,
+                        offset,
+                        || Value::known(config.round_constants[round][i]),
+                    )?;
+                }
+            }
         }
 
         // Compute the next round's state.
@@ -623,8 +2049,7 @@ impl<F: PrimeField, const WIDTH: usize> Pow5State<F, WIDTH> {
             Ok(StateWord(var))
         };
 
-        let next_state: Result<Vec<_>, _> = (0..WIDTH).map(next_state_word).collect();
-        next_state.map(|next_state| Pow5State(next_state.try_into().unwrap()))
+        try_state_from_fn(next_state_word).map(Pow5State)
     }
 }
 
@@ -634,18 +2059,32 @@ mod tests {
     use num_traits::Num;
     use ff::{Field, PrimeField};
     use halo2_proofs::{
-        circuit::{Layouter, SimpleFloorPlanner, Value},
+        circuit::{Layouter, Region, SimpleFloorPlanner, Value},
         dev::MockProver,
-        plonk::{Circuit, ConstraintSystem, Error},
+        plonk::{Advice, Any, Circuit, Column, ConstraintSystem, Error, Instance},
     };
     use halo2curves::bn256::Fr as Fp;
     //use rand::rngs::OsRng;
 
-    use crate::base::primitives::permute;
-    use crate::base::P128Pow5T3;
+    use crate::base::primitives::{permute, state_from_iter};
+    use crate::base::test_vectors::{
+        test_spec, BigEndianSpec, Poseidon1Spec, Width4Spec, WIDTH4_EXTERNAL,
+        WIDTH4_ROUND_CONSTANTS,
+    };
+    use crate::base::{P128Pow5T3, P128Pow5T3Constants, P128Pow5T8};
 
-    use super::{PoseidonInstructions, Pow5Chip, Pow5Config, StateWord};
-    use crate::base::primitives::{self as poseidon, ConstantLength, Spec}; // P128Pow5T3 as OrchardNullifier
+    use super::{
+        PartialMatrix, PermuteChip, PoseidonInstructions, Pow5Chip, Pow5ChipBuilder, Pow5Config,
+        Pow5State, RoundConstantColumns, RoundLayout, RoundStep, StateWord,
+    };
+    use crate::circuit::poseidon::{
+        Hash, PaddedWord, PoseidonSpongeInstructions, Sponge, SpongeInput,
+    };
+    use crate::base::primitives::{
+        self as poseidon, Absorbing, ConstantLength, Domain, InternalMatrix, Mds, Spec,
+        VariableLength,
+    }; // P128Pow5T3 as OrchardNullifier
+    use std::borrow::Cow;
     use std::convert::TryInto;
     use std::marker::PhantomData;
 
@@ -665,244 +2104,3237 @@ mod tests {
         }
 
         fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, WIDTH, RATE> {
-            let state = (0..WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
+            let state = [(); WIDTH].map(|_| meta.advice_column());
             let partial_sbox = meta.advice_column();
 
-            let rc_a = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
-            let pad_fixed = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
+            let rc_a = [(); WIDTH].map(|_| meta.fixed_column());
+            let pad_fixed = [(); WIDTH].map(|_| meta.fixed_column());
 
-            Pow5Chip::configure::<S>(
-                meta,
-                state.try_into().unwrap(),
-                partial_sbox,
-                rc_a.try_into().unwrap(),
-                pad_fixed.try_into().unwrap(),
-            )
+            Pow5Chip::configure::<S>(meta, state, partial_sbox, rc_a, pad_fixed)
         }
 
         fn synthesize(
             &self,
             config: Pow5Config<Fp, WIDTH, RATE>,
             mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_and_check::<S, WIDTH, RATE>(&config, &mut layouter)
+        }
+    }
+
+    /// Permutes the state `[0, 1, ..]` with the chip and constrains the result to the
+    /// reference permutation.
+    fn permute_and_check<S: Spec<Fp, WIDTH, RATE>, const WIDTH: usize, const RATE: usize>(
+        config: &Pow5Config<Fp, WIDTH, RATE>,
+        layouter: &mut impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let initial = state_from_iter((0..WIDTH).map(|idx| Fp::from(idx as u64)));
+        permute_state_and_check::<S, WIDTH, RATE>(config, layouter, initial)
+    }
+
+    /// Permutes `initial` with the chip and constrains the result to the reference
+    /// permutation.
+    fn permute_state_and_check<
+        S: Spec<Fp, WIDTH, RATE>,
+        const WIDTH: usize,
+        const RATE: usize,
+    >(
+        config: &Pow5Config<Fp, WIDTH, RATE>,
+        layouter: &mut impl Layouter<Fp>,
+        initial: [Fp; WIDTH],
+    ) -> Result<(), Error> {
+        let initial_state = layouter.assign_region(
+            || "prepare initial state",
+            |mut region| {
+                let state_word = |i: usize| {
+                    let value = Value::known(initial[i]);
+                    let var = region.assign_advice(
+                        || format!("load state_{}", i),
+                        config.state[i],
+                        0,
+                        || value,
+                    )?;
+                    Ok(StateWord(var))
+                };
+
+                let state: Result<Vec<_>, Error> = (0..WIDTH).map(state_word).collect();
+                Ok(state_from_iter(state?))
+            },
+        )?;
+
+        let chip = Pow5Chip::construct(config.clone());
+        let final_state = <Pow5Chip<_, WIDTH, RATE> as PoseidonInstructions<
+            Fp,
+            S,
+            WIDTH,
+            RATE,
+        >>::permute(&chip, layouter, &initial_state)?;
+
+        // For the purpose of this test, compute the real final state inline.
+        let mut expected_final_state = initial;
+        poseidon::permute::<_, S, WIDTH, RATE>(&mut expected_final_state);
+
+        layouter.assign_region(
+            || "constrain final state",
+            |mut region| {
+                let mut final_state_word = |i: usize| {
+                    let var = region.assign_advice(
+                        || format!("load final_state_{}", i),
+                        config.state[i],
+                        0,
+                        || Value::known(expected_final_state[i]),
+                    )?;
+                    region.constrain_equal(final_state[i].0.cell(), var.cell())
+                };
+
+                for i in 0..(WIDTH) {
+                    final_state_word(i)?;
+                }
+
+                Ok(())
+            },
+        )
+    }
+
+    #[test]
+    fn poseidon_permute() {
+        let k = 7;
+        let circuit = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>(PhantomData);
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    #[test]
+    fn poseidon_permute_width_8() {
+        // An odd number of partial rounds, one per row like the others.
+        assert_eq!(P128Pow5T8::<Fp>::partial_rounds(), 57);
+        let circuit = PermuteCircuit::<P128Pow5T8<Fp>, 8, 7>(PhantomData);
+        let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    #[cfg(feature = "layout-json")]
+    #[test]
+    fn layout_json_lists_permute_state() {
+        let circuit = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>(PhantomData);
+        let json = crate::circuit::layout::layout_json(&circuit).unwrap();
+        let cells: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+
+        // `PermuteCircuit` allocates the state columns first, so they are advice
+        // columns 0 to 2.
+        let state_cells = cells
+            .iter()
+            .filter(|cell| cell["region"] == "permute state")
+            .filter(|cell| cell["column"]["type"] == "advice")
+            .filter(|cell| cell["column"]["index"].as_u64().unwrap() < 3)
+            .count();
+        assert_eq!(
+            state_cells,
+            3 * Pow5Chip::<Fp, 3, 2>::permute_rows::<P128Pow5T3<Fp>>()
+        );
+    }
+
+    /// Permutes `[0, 1, 2]` twice in a row, in regions indexed by iteration.
+    struct IndexedPermuteCircuit;
+
+    impl Circuit<Fp> for IndexedPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            IndexedPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let mut state = layouter.assign_region(
+                || "initial state",
+                |mut region| {
+                    let words: Result<Vec<_>, Error> = (0..3)
+                        .map(|i| {
+                            region
+                                .assign_advice(
+                                    || format!("state_{}", i),
+                                    config.state[i],
+                                    0,
+                                    || Value::known(Fp::from(i as u64)),
+                                )
+                                .map(StateWord)
+                        })
+                        .collect();
+                    Ok(state_from_iter(words?))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config);
+            for id in 0..2 {
+                state = chip.permute_indexed(&mut layouter, &state, id)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn indexed_permutes_name_their_regions() {
+        let prover = MockProver::run(8, &IndexedPermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let cells = crate::circuit::layout::cell_assignments(&IndexedPermuteCircuit).unwrap();
+        let mut regions: Vec<_> = cells.into_iter().filter_map(|cell| cell.region).collect();
+        regions.dedup();
+        assert_eq!(
+            regions,
+            ["initial state", "permute state #0", "permute state #1"]
+        );
+    }
+
+    /// Permutes the same initial state twice and constrains both outputs equal.
+    struct RepeatedPermuteCircuit;
+
+    impl Circuit<Fp> for RepeatedPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            RepeatedPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
         ) -> Result<(), Error> {
             let initial_state = layouter.assign_region(
                 || "prepare initial state",
                 |mut region| {
                     let state_word = |i: usize| {
-                        let value = Value::known(Fp::from(i as u64));
                         let var = region.assign_advice(
                             || format!("load state_{}", i),
                             config.state[i],
                             0,
-                            || value,
+                            || Value::known(Fp::from(i as u64)),
                         )?;
                         Ok(StateWord(var))
                     };
 
-                    let state: Result<Vec<_>, Error> = (0..WIDTH).map(state_word).collect();
-                    Ok(state?.try_into().unwrap())
+                    let state: Result<Vec<_>, Error> = (0..3).map(state_word).collect();
+                    Ok(state_from_iter(state?))
                 },
             )?;
 
-            let chip = Pow5Chip::construct(config.clone());
-            let final_state = <Pow5Chip<_, WIDTH, RATE> as PoseidonInstructions<
+            let chip = Pow5Chip::construct(config);
+            let first = <Pow5Chip<_, 3, 2> as PoseidonInstructions<
                 Fp,
-                S,
-                WIDTH,
-                RATE,
-            >>::permute(&chip, &mut layouter, &initial_state)?;
-
-            // For the purpose of this test, compute the real final state inline.
-            let mut expected_final_state = (0..WIDTH)
-                .map(|idx| Fp::from(idx as u64))
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap();
-
-            poseidon::permute::<_, S, WIDTH, RATE>(
-                &mut expected_final_state
-            );
-
-            println!("expected:{:?}", expected_final_state);
+                P128Pow5T3<Fp>,
+                3,
+                2,
+            >>::permute(&chip, &mut layouter.namespace(|| "first"), &initial_state)?;
+            let second = <Pow5Chip<_, 3, 2> as PoseidonInstructions<
+                Fp,
+                P128Pow5T3<Fp>,
+                3,
+                2,
+            >>::permute(&chip, &mut layouter.namespace(|| "second"), &initial_state)?;
 
             layouter.assign_region(
-                || "constrain final state",
+                || "constrain outputs equal",
                 |mut region| {
-                    let mut final_state_word = |i: usize| {
-                        let var = region.assign_advice(
-                            || format!("load final_state_{}", i),
-                            config.state[i],
-                            0,
-                            || Value::known(expected_final_state[i]),
-                        )?;
-                        region.constrain_equal(final_state[i].0.cell(), var.cell())
-                    };
-
-                    for i in 0..(WIDTH) {
-                        final_state_word(i)?;
+                    for (a, b) in first.iter().zip(second.iter()) {
+                        region.constrain_equal(a.0.cell(), b.0.cell())?;
                     }
-
                     Ok(())
                 },
             )
         }
     }
 
+    /// Permutes a given width-3 state and checks it against the reference.
+    struct RandomPermuteCircuit {
+        initial: [Fp; 3],
+    }
+
+    impl Circuit<Fp> for RandomPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            RandomPermuteCircuit {
+                initial: self.initial,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_state_and_check::<P128Pow5T3<Fp>, 3, 2>(&config, &mut layouter, self.initial)
+        }
+    }
+
+    /// The number of random states to check, overridable with `POSEIDON_PROPTEST_CASES`.
+    fn proptest_cases() -> u32 {
+        std::env::var("POSEIDON_PROPTEST_CASES")
+            .ok()
+            .and_then(|cases| cases.parse().ok())
+            .unwrap_or(64)
+    }
+
     #[test]
-    fn poseidon_permute() {
-        let k = 7;
-        let circuit = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>(PhantomData);
+    fn poseidon_permute_random_states() {
+        use proptest::prelude::*;
+        use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
 
-        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        let config = Config {
+            cases: proptest_cases(),
+            ..Config::default()
+        };
+        // A fixed seed keeps failures reproducible across runs.
+        let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &[0x5e; 32]);
+        let mut runner = TestRunner::new_with_rng(config, rng);
+
+        runner
+            .run(&any::<u64>(), |seed| {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let initial = [0; 3].map(|_| Fp::random(&mut rng));
+
+                let prover = MockProver::run(7, &RandomPermuteCircuit { initial }, vec![]).unwrap();
+                prop_assert_eq!(prover.verify(), Ok(()));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /// Permutes with a chip configured without the `partial_sbox` column.
+    struct CompactPermuteCircuit;
+
+    impl Circuit<Fp> for CompactPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            CompactPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            Pow5Chip::configure_compact::<P128Pow5T3<Fp>>(meta, state, rc_a, pad_fixed)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&config, &mut layouter)
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_compact() {
+        // One advice column fewer than `PermuteCircuit`, in the same number of rows and
+        // at the same degree.
+        let mut meta = ConstraintSystem::<Fp>::default();
+        CompactPermuteCircuit::configure(&mut meta);
+        let mut full_meta = ConstraintSystem::<Fp>::default();
+        PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut full_meta);
+        assert_eq!(meta.num_advice_columns() + 1, full_meta.num_advice_columns());
+        assert_eq!(meta.degree(), full_meta.degree());
+
+        let k = 7;
+        let prover = MockProver::run(k, &CompactPermuteCircuit, vec![]).unwrap();
         assert_eq!(prover.verify(), Ok(()))
     }
 
-    // struct HashCircuit<
-    //     S: Spec<Fp, WIDTH, RATE>,
-    //     const WIDTH: usize,
-    //     const RATE: usize,
-    //     const L: usize,
-    // > {
-    //     message: Value<[Fp; L]>,
-    //     // For the purpose of this test, witness the result.
-    //     // TODO: Move this into an instance column.
-    //     output: Value<Fp>,
-    //     _spec: PhantomData<S>,
-    // }
-
-    // impl<S: Spec<Fp, WIDTH, RATE>, const WIDTH: usize, const RATE: usize, const L: usize>
-    //     Circuit<Fp> for HashCircuit<S, WIDTH, RATE, L>
-    // {
-    //     type Config = Pow5Config<Fp, WIDTH, RATE>;
-    //     type FloorPlanner = SimpleFloorPlanner;
-    //     // #[cfg(feature = "circuit-params")]
-    //     type Params = ();
-
-    //     fn without_witnesses(&self) -> Self {
-    //         Self {
-    //             message: Value::unknown(),
-    //             output: Value::unknown(),
-    //             _spec: PhantomData,
-    //         }
-    //     }
-
-    //     fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, WIDTH, RATE> {
-    //         let state = (0..WIDTH).map(|_| meta.advice_column()).collect::<Vec<_>>();
-    //         let partial_sbox = meta.advice_column();
-
-    //         let rc_a = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
-    //         let rc_b = (0..WIDTH).map(|_| meta.fixed_column()).collect::<Vec<_>>();
-
-    //         meta.enable_constant(rc_b[0]);
-
-    //         Pow5Chip::configure::<S>(
-    //             meta,
-    //             state.try_into().unwrap(),
-    //             partial_sbox,
-    //             rc_a.try_into().unwrap(),
-    //             rc_b.try_into().unwrap(),
-    //         )
-    //     }
-
-    //     fn synthesize(
-    //         &self,
-    //         config: Pow5Config<Fp, WIDTH, RATE>,
-    //         mut layouter: impl Layouter<Fp>,
-    //     ) -> Result<(), Error> {
-    //         let chip = Pow5Chip::construct(config.clone());
-
-    //         let message = layouter.assign_region(
-    //             || "load message",
-    //             |mut region| {
-    //                 let message_word = |i: usize| {
-    //                     let value = self.message.map(|message_vals| message_vals[i]);
-    //                     region.assign_advice(
-    //                         || format!("load message_{}", i),
-    //                         config.state[i],
-    //                         0,
-    //                         || value,
-    //                     )
-    //                 };
-
-    //                 let message: Result<Vec<_>, Error> = (0..L).map(message_word).collect();
-    //                 Ok(message?.try_into().unwrap())
-    //             },
-    //         )?;
-
-    //         let hasher = Hash::<_, _, S, ConstantLength<L>, WIDTH, RATE>::init(
-    //             chip,
-    //             layouter.namespace(|| "init"),
-    //         )?;
-    //         let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
-
-    //         layouter.assign_region(
-    //             || "constrain output",
-    //             |mut region| {
-    //                 let expected_var = region.assign_advice(
-    //                     || "load output",
-    //                     config.state[0],
-    //                     0,
-    //                     || self.output,
-    //                 )?;
-    //                 region.constrain_equal(output.cell(), expected_var.cell())
-    //             },
-    //         )
-    //     }
-    // }
+    /// Permutes `[0, 1, 2]` with a trace, and checks every traced state against
+    /// [`reference_trace`].
+    #[cfg(feature = "permute-trace")]
+    struct TracePermuteCircuit;
 
-//     #[test]
-//     fn poseidon_hash() {
-//         let rng = OsRng;
+    #[cfg(feature = "permute-trace")]
+    impl Circuit<Fp> for TracePermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
 
-//         let message = [Fp::random(rng), Fp::random(rng)];
-//         let output =
-//             poseidon::Hash::<_, OrchardNullifier, ConstantLength<2>, 3, 2>::init().hash(message);
+        fn without_witnesses(&self) -> Self {
+            TracePermuteCircuit
+        }
 
-//         let k = 6;
-//         let circuit = HashCircuit::<OrchardNullifier, 3, 2, 2> {
-//             message: Value::known(message),
-//             output: Value::known(output),
-//             _spec: PhantomData,
-//         };
-//         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
-//         assert_eq!(prover.verify(), Ok(()))
-//     }
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
 
-//     #[test]
-//     fn poseidon_hash_longer_input() {
-//         let rng = OsRng;
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial = [0, 1, 2].map(Fp::from);
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region
+                            .assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(initial[i]),
+                            )
+                            .map(StateWord)
+                    };
+                    Ok([load(0)?, load(1)?, load(2)?])
+                },
+            )?;
 
-//         let message = [Fp::random(rng), Fp::random(rng), Fp::random(rng)];
-//         let output =
-//             poseidon::Hash::<_, OrchardNullifier, ConstantLength<3>, 3, 2>::init().hash(message);
+            let chip = Pow5Chip::construct(config);
+            let (_, trace) = chip.permute_with_trace(&mut layouter, &initial_state)?;
 
-//         let k = 7;
-//         let circuit = HashCircuit::<OrchardNullifier, 3, 2, 3> {
-//             message: Value::known(message),
-//             output: Value::known(output),
-//             _spec: PhantomData,
-//         };
-//         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
-//         assert_eq!(prover.verify(), Ok(()))
-//     }
+            let expected = reference_trace(initial);
+            assert_eq!(trace.len(), expected.len());
+            for (step, (words, expected)) in trace.iter().zip(expected).enumerate() {
+                for (word, expected) in words.iter().zip(expected) {
+                    word.assert_if_known(|word| {
+                        assert_eq!(*word, expected, "trace diverges at step {}", step);
+                        true
+                    });
+                }
+            }
 
-//     #[test]
-//     fn poseidon_hash_longer_input_custom() {
-//         let rng = OsRng;
+            Ok(())
+        }
+    }
 
-//         let message = [Fp::random(rng), Fp::random(rng), Fp::random(rng), Fp::random(rng)];
-//         let output =
-//             poseidon::Hash::<_, OrchardNullifier, ConstantLength<4>, 3, 2>::init().hash(message);
+    /// The state after the first layer and after each round of the reference
+    /// permutation of `initial`.
+    #[cfg(feature = "permute-trace")]
+    fn reference_trace(initial: [Fp; 3]) -> Vec<[Fp; 3]> {
+        let mut state = initial;
+        poseidon::mat_mul(&mut state, &P128Pow5T3::<Fp>::external_matrix());
+        let mut trace = vec![state];
+        for (round, rc) in P128Pow5T3::<Fp>::round_constants().iter().enumerate() {
+            if (4..60).contains(&round) {
+                state[0] = P128Pow5T3::<Fp>::sbox(state[0] + rc[0]);
+                poseidon::mat_mul(&mut state, &P128Pow5T3::<Fp>::internal_matrix());
+            } else {
+                for (word, rc) in state.iter_mut().zip(rc) {
+                    *word = P128Pow5T3::<Fp>::sbox(*word + rc);
+                }
+                poseidon::mat_mul(&mut state, &P128Pow5T3::<Fp>::external_matrix());
+            }
+            trace.push(state);
+        }
+        trace
+    }
 
-//         let k = 7;
-//         let circuit = HashCircuit::<OrchardNullifier, 3, 2, 4> {
-//             message: Value::known(message),
-//             output: Value::known(output),
-//             _spec: PhantomData,
-//         };
-//         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
-//         assert_eq!(prover.verify(), Ok(()))
-//     }
+    #[cfg(feature = "permute-trace")]
+    #[test]
+    fn poseidon_permute_with_trace() {
+        let trace = reference_trace([0, 1, 2].map(Fp::from));
+        assert_eq!(trace.len(), 1 + 8 + 56);
+        let mut permuted = [0, 1, 2].map(Fp::from);
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut permuted);
+        assert_eq!(trace.last(), Some(&permuted));
+
+        let prover = MockProver::run(7, &TracePermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Permutes with a chip configured with every gate of degree at most 3.
+    struct LowDegreePermuteCircuit;
+
+    impl Circuit<Fp> for LowDegreePermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            LowDegreePermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+            let squares = [0; 3].map(|_| meta.advice_column());
+            let fourths = [0; 3].map(|_| meta.advice_column());
+
+            Pow5Chip::configure_low_degree::<P128Pow5T3<Fp>>(
+                meta,
+                state,
+                partial_sbox,
+                rc_a,
+                pad_fixed,
+                squares,
+                fourths,
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&config, &mut layouter)
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_low_degree() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        LowDegreePermuteCircuit::configure(&mut meta);
+        let mut full_meta = ConstraintSystem::<Fp>::default();
+        PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut full_meta);
+        assert!(meta.degree() <= 3, "degree {} exceeds 3", meta.degree());
+        assert!(full_meta.degree() > 3);
+
+        let k = 7;
+        let prover = MockProver::run(k, &LowDegreePermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    /// Permutes `[0, 1, 2]` in two halves and checks the result against the reference.
+    struct SplitPermuteCircuit {
+        split: usize,
+    }
+
+    impl Circuit<Fp> for SplitPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            SplitPermuteCircuit { split: self.split }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let state_word = |i: usize| {
+                        let var = region.assign_advice(
+                            || format!("load state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(Fp::from(i as u64)),
+                        )?;
+                        Ok(StateWord(var))
+                    };
+
+                    let state: Result<Vec<_>, Error> = (0..3).map(state_word).collect();
+                    Ok(state_from_iter(state?))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.clone());
+            let mid = chip.permute_rounds(&mut layouter, &initial_state, 0, self.split)?;
+            let final_state = chip.permute_rounds(&mut layouter, &mid, self.split, 64)?;
+
+            let mut expected = [Fp::from(0), Fp::from(1), Fp::from(2)];
+            poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+
+            layouter.assign_region(
+                || "constrain final state",
+                |mut region| {
+                    for (i, word) in final_state.iter().enumerate() {
+                        let var = region.assign_advice(
+                            || format!("load final_state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(expected[i]),
+                        )?;
+                        region.constrain_equal(word.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_in_two_halves() {
+        // Splits inside the first full rounds, the partial rounds, and at either end.
+        for split in [0, 2, 32, 64] {
+            let prover = MockProver::run(8, &SplitPermuteCircuit { split }, vec![]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "split at round {}", split);
+        }
+    }
+
+    /// Chains two permutations in one region, and constrains the result to equal two
+    /// separate permutations.
+    struct ChainPermuteCircuit;
+
+    impl Circuit<Fp> for ChainPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ChainPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let state_word = |i: usize| {
+                        let var = region.assign_advice(
+                            || format!("load state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(Fp::from(i as u64)),
+                        )?;
+                        Ok(StateWord(var))
+                    };
+
+                    let state: Result<Vec<_>, Error> = (0..3).map(state_word).collect();
+                    Ok(state_from_iter(state?))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.clone());
+            let chained = chip.permute_chain(&mut layouter, &initial_state, 2)?;
+
+            let mid = chip.permute(&mut layouter, &initial_state)?;
+            let separate = chip.permute(&mut layouter, &mid)?;
+
+            let mut expected = [Fp::from(0), Fp::from(1), Fp::from(2)];
+            poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+            poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+
+            layouter.assign_region(
+                || "constrain final state",
+                |mut region| {
+                    for (i, (chained, separate)) in chained.iter().zip(separate.iter()).enumerate()
+                    {
+                        let var = region.assign_advice(
+                            || format!("load final_state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(expected[i]),
+                        )?;
+                        region.constrain_equal(chained.0.cell(), var.cell())?;
+                        region.constrain_equal(separate.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    /// Adds a block made entirely of padding to the initial state.
+    struct PaddingOnlyInputCircuit {
+        padding: [Fp; 2],
+        expected: [Fp; 3],
+    }
+
+    impl Circuit<Fp> for PaddingOnlyInputCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            PaddingOnlyInputCircuit {
+                padding: self.padding,
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.clone());
+            let initial_state = <Pow5Chip<Fp, 3, 2> as PoseidonSpongeInstructions<
+                Fp,
+                P128Pow5T3<Fp>,
+                VariableLength,
+                3,
+                2,
+            >>::initial_state(&chip, &mut layouter)?;
+
+            let input = Absorbing(self.padding.map(|value| Some(PaddedWord::Padding(value))));
+            let state = <Pow5Chip<Fp, 3, 2> as PoseidonSpongeInstructions<
+                Fp,
+                P128Pow5T3<Fp>,
+                VariableLength,
+                3,
+                2,
+            >>::add_input(&chip, &mut layouter, &initial_state, &input)?;
+
+            layouter.assign_region(
+                || "constrain padded state",
+                |mut region| {
+                    for (i, word) in state.iter().enumerate() {
+                        let var = region.assign_advice(
+                            || format!("load expected_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(self.expected[i]),
+                        )?;
+                        region.constrain_equal(word.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn round_values_match_reference_permutation() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        let initial = [3, 5, 7].map(Fp::from);
+
+        let mut state = initial;
+        for step in config.round_schedule() {
+            state = match step {
+                RoundStep::First => {
+                    poseidon::mat_mul(&mut state, &config.mat_external);
+                    state
+                }
+                RoundStep::Full { round, .. } => {
+                    super::full_round_values(&config, round, state, true)
+                }
+                RoundStep::Partial { round, .. } => {
+                    super::partial_round_values(&config, round, 0, state).1
+                }
+            };
+        }
+
+        let mut expected = initial;
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+        assert_eq!(state, expected);
+    }
+
+    /// Permutes `[0, 1, 2]` `count` times, and constrains the result to the reference
+    /// permutation applied as many times.
+    struct ChainedPermuteCircuit {
+        count: usize,
+    }
+
+    impl Circuit<Fp> for ChainedPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ChainedPermuteCircuit { count: self.count }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let mut state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    super::try_state_from_fn(|i| {
+                        region
+                            .assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(Fp::from(i as u64)),
+                            )
+                            .map(StateWord)
+                    })
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.clone());
+            let mut expected = [Fp::from(0), Fp::from(1), Fp::from(2)];
+            for _ in 0..self.count {
+                state = <Pow5Chip<_, 3, 2> as PoseidonInstructions<
+                    Fp,
+                    P128Pow5T3<Fp>,
+                    3,
+                    2,
+                >>::permute(&chip, &mut layouter, &state)?;
+                poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+            }
+
+            layouter.assign_region(
+                || "constrain final state",
+                |mut region| {
+                    for (i, word) in state.iter().enumerate() {
+                        let var = region.assign_advice(
+                            || format!("load final_state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(expected[i]),
+                        )?;
+                        region.constrain_equal(word.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn repeated_permutes_match_reference() {
+        // Each permutation starts from the words the previous one assigned, so a word
+        // out of place anywhere on the witness path shows up in the final state.
+        let circuit = ChainedPermuteCircuit { count: 100 };
+        let prover = MockProver::run(13, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn try_state_from_fn_stops_at_first_error() {
+        let mut calls = 0;
+        let state: Result<[usize; 3], usize> = super::try_state_from_fn(|i| {
+            calls += 1;
+            if i == 1 {
+                Err(i)
+            } else {
+                Ok(i)
+            }
+        });
+        assert_eq!(state, Err(1));
+        assert_eq!(calls, 2);
+
+        let state: Result<[usize; 3], usize> = super::try_state_from_fn(|i| Ok(i * 2));
+        assert_eq!(state, Ok([0, 2, 4]));
+    }
+
+    /// Hashes the three words `[1, 2, 3]` as a constant-length message.
+    struct ConstantLength3HashCircuit;
+
+    impl Circuit<Fp> for ConstantLength3HashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ConstantLength3HashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            config.pow5.state[i],
+                            0,
+                            || Value::known(Fp::from(i as u64 + 1)),
+                        )
+                    };
+                    Ok([load(0)?, load(1)?, load(2)?])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5);
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<3>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            hasher.hash(layouter.namespace(|| "hash"), message)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn padding_layout_of_constant_length_3() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = InstanceHashCircuit::configure(&mut meta).pow5;
+
+        // Three message words and one padding word, in two blocks of two.
+        let words: Vec<_> = [1, 2, 3]
+            .into_iter()
+            .map(|word| PaddedWord::Constant(Fp::from(word)))
+            .chain(<ConstantLength<3> as Domain<Fp, 2>>::padding(3).map(PaddedWord::Padding))
+            .collect();
+        let layouts: Vec<_> = words
+            .chunks(2)
+            .map(|block| {
+                let block = Absorbing([Some(block[0].clone()), Some(block[1].clone())]);
+                config.padding_layout(&block)
+            })
+            .collect();
+        assert_eq!(layouts, vec![vec![], vec![(config.pad_fixed[1], 1)]]);
+
+        // The padding cells the chip actually assigns while hashing, per add-input
+        // region, with their rows relative to the region's first row.
+        let cells = crate::circuit::layout::cell_assignments(&ConstantLength3HashCircuit).unwrap();
+        let mut assigned: Vec<Vec<(Column<Any>, usize)>> = vec![];
+        let mut region = None;
+        let mut start = 0;
+        for cell in cells {
+            if cell.region != region {
+                region = cell.region.clone();
+                start = cell.row;
+                if region.as_deref().map_or(false, |name| name.contains("add input")) {
+                    assigned.push(vec![]);
+                }
+            }
+            if cell.annotation.starts_with("load pad_") {
+                let pads = assigned.last_mut().expect("padding is assigned in add_input");
+                pads.push((cell.column, cell.row - start));
+            }
+        }
+
+        let expected: Vec<Vec<_>> = layouts
+            .into_iter()
+            .map(|layout| {
+                layout
+                    .into_iter()
+                    .map(|(column, row)| (Column::<Any>::from(column), row))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(assigned, expected);
+    }
+
+    #[test]
+    fn add_input_of_padding_only() {
+        // The padding a variable-length message gets when its length is a multiple of
+        // the rate.
+        let padding = [Fp::ONE, Fp::ZERO];
+
+        let endianness = P128Pow5T3::<Fp>::state_endianness();
+        let mut expected = [Fp::ZERO; 3];
+        expected[endianness.capacity_index(3)] =
+            <VariableLength as Domain<Fp, 2>>::initial_capacity_element();
+        for (i, value) in padding.iter().enumerate() {
+            expected[endianness.rate_index(3, i)] += value;
+        }
+
+        let circuit = PaddingOnlyInputCircuit { padding, expected };
+        let prover = MockProver::run(6, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Padding at the wrong rate position is caught.
+        let circuit = PaddingOnlyInputCircuit {
+            padding: [Fp::ZERO, Fp::ONE],
+            expected,
+        };
+        let prover = MockProver::run(6, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn poseidon_permute_chain() {
+        let prover = MockProver::run(9, &ChainPermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Permutes a state with `permute_borrowed`, either in the region that assigned it
+    /// or from a separate region.
+    struct BorrowedPermuteCircuit {
+        in_place: bool,
+    }
+
+    impl Circuit<Fp> for BorrowedPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BorrowedPermuteCircuit {
+                in_place: self.in_place,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let load_state = |region: &mut Region<'_, Fp>| {
+                let state_word = |i: usize| {
+                    let var = region.assign_advice(
+                        || format!("load state_{}", i),
+                        config.state[i],
+                        0,
+                        || Value::known(Fp::from(i as u64)),
+                    )?;
+                    Ok(StateWord(var))
+                };
+
+                let state: Result<Vec<_>, Error> = (0..3).map(state_word).collect();
+                Ok::<[StateWord<Fp>; 3], Error>(state_from_iter(state?))
+            };
+
+            let chip = Pow5Chip::construct(config.clone());
+            let final_state = if self.in_place {
+                layouter.assign_region(
+                    || "load and permute",
+                    |mut region| {
+                        let initial_state = load_state(&mut region)?;
+                        chip.permute_borrowed(&mut region, &initial_state, 0)
+                    },
+                )?
+            } else {
+                let initial_state = layouter.assign_region(
+                    || "prepare initial state",
+                    |mut region| load_state(&mut region),
+                )?;
+                layouter.assign_region(
+                    || "permute",
+                    |mut region| chip.permute_borrowed(&mut region, &initial_state, 0),
+                )?
+            };
+
+            let mut expected = [Fp::from(0), Fp::from(1), Fp::from(2)];
+            poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut expected);
+
+            layouter.assign_region(
+                || "constrain final state",
+                |mut region| {
+                    for (i, word) in final_state.iter().enumerate() {
+                        let var = region.assign_advice(
+                            || format!("load final_state_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(expected[i]),
+                        )?;
+                        region.constrain_equal(word.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_borrowed() {
+        for in_place in [true, false] {
+            let circuit = BorrowedPermuteCircuit { in_place };
+            let prover = MockProver::run(8, &circuit, vec![]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "in_place: {}", in_place);
+        }
+    }
+
+    #[test]
+    fn configure_borrows_spec_constants() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+
+        assert!(std::ptr::eq(
+            &*config.round_constants,
+            <Fp as P128Pow5T3Constants>::round_constants()
+        ));
+        assert!(std::ptr::eq(
+            &*config.mat_external,
+            <Fp as P128Pow5T3Constants>::mds_external()
+        ));
+        // The internal matrix is diagonal, so only its diagonal is kept.
+        assert_eq!(
+            config.mat_internal,
+            vec![PartialMatrix::Diagonal(
+                InternalMatrix::from_dense(<Fp as P128Pow5T3Constants>::mds_internal()).unwrap()
+            )]
+        );
+
+        // A dense partial-round matrix is still borrowed.
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<Poseidon1Spec, 3, 2>::configure(&mut meta);
+        match &config.mat_internal[..] {
+            [PartialMatrix::Dense(Cow::Borrowed(mat))] => {
+                assert!(std::ptr::eq(*mat, <Fp as P128Pow5T3Constants>::mds_external()))
+            }
+            other => panic!("expected the borrowed external matrix, got {:?}", other),
+        }
+    }
+
+    /// Applies only the first (external matrix) layer to a known state.
+    struct FirstLayerCircuit {
+        initial: [Fp; 3],
+        expected: [Fp; 3],
+    }
+
+    impl Circuit<Fp> for FirstLayerCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FirstLayerCircuit {
+                initial: self.initial,
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let state_word = |i: usize| {
+                        region
+                            .assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(self.initial[i]),
+                            )
+                            .map(StateWord)
+                    };
+
+                    let state: Result<Vec<_>, Error> = (0..3).map(state_word).collect();
+                    Ok(state_from_iter(state?))
+                },
+            )?;
+
+            let state = layouter.assign_region(
+                || "first layer",
+                |mut region| {
+                    Pow5State::load(&mut region, &config, &initial_state)?
+                        .first_layer(&mut region, &config, 0)
+                },
+            )?;
+
+            layouter.assign_region(
+                || "constrain first layer",
+                |mut region| {
+                    for (i, word) in state.0.iter().enumerate() {
+                        let var = region.assign_advice(
+                            || format!("load expected_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(self.expected[i]),
+                        )?;
+                        region.constrain_equal(word.0.cell(), var.cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn first_layer_applies_external_matrix() {
+        let initial = [Fp::from(3), Fp::from(5), Fp::from(7)];
+
+        // The external matrix for width 3 is circ(2, 1, 1).
+        let mut expected = initial;
+        poseidon::mat_mul(&mut expected, &P128Pow5T3::<Fp>::external_matrix());
+        assert_eq!(expected, [Fp::from(18), Fp::from(20), Fp::from(22)]);
+
+        let circuit = FirstLayerCircuit { initial, expected };
+        let prover = MockProver::run(6, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // The internal matrix in its place would be caught.
+        let mut wrong = initial;
+        poseidon::mat_mul(&mut wrong, &P128Pow5T3::<Fp>::internal_matrix());
+        let circuit = FirstLayerCircuit {
+            initial,
+            expected: wrong,
+        };
+        let prover = MockProver::run(6, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Permutes with a chip configured by the width-3 `PermuteChip` impl.
+    struct AutoConfiguredPermuteCircuit;
+
+    impl Circuit<Fp> for AutoConfiguredPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            AutoConfiguredPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            <Pow5Chip<Fp, 3, 2> as PermuteChip<Fp, P128Pow5T3<Fp>, 3, 2>>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&config, &mut layouter)
+        }
+    }
+
+    #[test]
+    fn permute_chip_wires_fixed_columns() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = AutoConfiguredPermuteCircuit::configure(&mut meta);
+        match config.rc_a {
+            super::RoundConstantColumns::Fixed(rc_a) => {
+                assert!(rc_a.iter().all(|column| !config.pad_fixed.contains(column)))
+            }
+            _ => panic!("PermuteChip configures fixed round constants"),
+        }
+        assert_eq!(meta.num_fixed_columns(), 6);
+
+        let prover = MockProver::run(7, &AutoConfiguredPermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    #[test]
+    fn equality_columns_match_configure() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+
+        let columns = config.equality_columns();
+        assert_eq!(columns.len(), 6);
+        assert_eq!(columns, meta.permutation().get_columns());
+    }
+
+    #[test]
+    fn sbox_exponent_matches_spec() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+
+        assert_eq!(config.sbox_exponent(), 5);
+        let x = Fp::from(0x1234_5678);
+        assert_eq!(x.pow([config.sbox_exponent()]), P128Pow5T3::<Fp>::sbox(x));
+    }
+
+    #[test]
+    fn round_schedule_lists_every_row() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        let schedule: Vec<_> = config.round_schedule().collect();
+
+        // The first layer, then 4 full, 56 partial and 4 full rounds.
+        assert_eq!(schedule.len(), 1 + 8 + 56);
+        assert_eq!(schedule[0], RoundStep::First);
+        for (round, step) in schedule[1..].iter().enumerate() {
+            let constants = P128Pow5T3::<Fp>::round_constants()[round];
+            let expected = if (4..60).contains(&round) {
+                RoundStep::Partial { round, constants }
+            } else {
+                RoundStep::Full { round, constants }
+            };
+            assert_eq!(*step, expected);
+        }
+    }
+
+    #[test]
+    fn round_layout_rows_are_contiguous() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        let poseidon2 = RoundLayout::for_spec::<Fp, P128Pow5T3<Fp>, 3, 2>();
+        assert_eq!(config.round_layout(), poseidon2);
+        let poseidon1 = RoundLayout::for_spec::<Fp, Poseidon1Spec, 3, 2>();
+
+        for (layout, rows) in [(poseidon2, 66), (poseidon1, 65)] {
+            // One step per row from the initial state's row on, each writing the next.
+            let steps: Vec<_> = layout
+                .first_layer_row()
+                .into_iter()
+                .chain((0..layout.rounds()).map(|round| layout.round_row(round)))
+                .collect();
+            assert_eq!(steps, (0..steps.len()).collect::<Vec<_>>());
+            assert_eq!(layout.rows(), steps.len() + 1);
+            assert_eq!(layout.rows(), rows);
+        }
+        assert_eq!(
+            Pow5Chip::<Fp, 3, 2>::permute_rows::<P128Pow5T3<Fp>>(),
+            poseidon2.rows()
+        );
+        assert_eq!(config.round_schedule().count(), poseidon2.rows() - 1);
+    }
+
+    /// Two chips for different specs, configured on the same columns.
+    struct SharedColumnsCircuit;
+
+    impl Circuit<Fp> for SharedColumnsCircuit {
+        type Config = (Pow5Config<Fp, 3, 2>, Pow5Config<Fp, 3, 2>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            SharedColumnsCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let poseidon2 = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta);
+            let poseidon1 = Pow5Chip::configure_sharing::<Poseidon1Spec, 2, 1>(meta, &poseidon2);
+            (poseidon2, poseidon1)
+        }
+
+        fn synthesize(
+            &self,
+            (poseidon2, poseidon1): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&poseidon2, &mut layouter)?;
+            permute_and_check::<Poseidon1Spec, 3, 2>(&poseidon1, &mut layouter)?;
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&poseidon2, &mut layouter)
+        }
+    }
+
+    #[test]
+    fn chips_sharing_columns() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let (poseidon2, poseidon1) = SharedColumnsCircuit::configure(&mut meta);
+        assert_eq!(poseidon1.state, poseidon2.state);
+        assert_eq!(meta.num_advice_columns(), 4);
+        assert_eq!(meta.num_fixed_columns(), 6);
+
+        let prover = MockProver::run(9, &SharedColumnsCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    test_spec! {
+        /// [`P128Pow5T3`] with an $x^7$ S-box.
+        struct Alpha7Spec;
+        impl Spec<3, 2> {
+            alpha: 7,
+        }
+    }
+
+    /// Permutes the state `[0, 1, ..]` with a chip built by [`Pow5ChipBuilder`] with the
+    /// options given as parameters, and checks the result against the reference
+    /// permutation.
+    struct BuiltPermuteCircuit<
+        S,
+        const WIDTH: usize,
+        const RATE: usize,
+        const CAPACITY: usize,
+        const LOW_DEGREE: bool,
+        const COMPACT: bool,
+    >(PhantomData<S>);
+
+    impl<
+            S: Spec<Fp, WIDTH, RATE>,
+            const WIDTH: usize,
+            const RATE: usize,
+            const CAPACITY: usize,
+            const LOW_DEGREE: bool,
+            const COMPACT: bool,
+        > Circuit<Fp> for BuiltPermuteCircuit<S, WIDTH, RATE, CAPACITY, LOW_DEGREE, COMPACT>
+    {
+        type Config = Pow5Config<Fp, WIDTH, RATE, CAPACITY>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BuiltPermuteCircuit(PhantomData)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            Pow5ChipBuilder::new()
+                .low_degree(LOW_DEGREE)
+                .compact(COMPACT)
+                .build::<S>(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial = state_from_iter((0..WIDTH).map(|idx| Fp::from(idx as u64)));
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let words = (0..WIDTH).map(|i| {
+                        region
+                            .assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(initial[i]),
+                            )
+                            .map(StateWord)
+                    });
+                    Ok(state_from_iter(words.collect::<Result<Vec<_>, Error>>()?))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config);
+            let final_state = PoseidonInstructions::<Fp, S, WIDTH, RATE>::permute(
+                &chip,
+                &mut layouter,
+                &initial_state,
+            )?;
+
+            let mut expected = initial;
+            poseidon::permute::<_, S, WIDTH, RATE>(&mut expected);
+            for (word, expected) in final_state.iter().zip(expected) {
+                word.0.value().assert_if_known(|value| **value == expected);
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn builder_option_combinations() {
+        fn check<C: Circuit<Fp>>(circuit: C, max_degree: usize) {
+            let mut meta = ConstraintSystem::<Fp>::default();
+            C::configure(&mut meta);
+            assert!(meta.degree() <= max_degree);
+
+            let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+
+        // The options of `configure`, `configure_low_degree` and `configure_compact`.
+        check(BuiltPermuteCircuit::<P128Pow5T3<Fp>, 3, 2, 1, false, false>(PhantomData), 6);
+        check(BuiltPermuteCircuit::<P128Pow5T3<Fp>, 3, 2, 1, true, false>(PhantomData), 3);
+        check(BuiltPermuteCircuit::<P128Pow5T3<Fp>, 3, 2, 1, false, true>(PhantomData), 6);
+        // Combined, or with another S-box or capacity.
+        check(BuiltPermuteCircuit::<P128Pow5T3<Fp>, 3, 2, 1, true, true>(PhantomData), 3);
+        check(BuiltPermuteCircuit::<Alpha7Spec, 3, 2, 1, false, false>(PhantomData), 8);
+        check(BuiltPermuteCircuit::<Width4Spec, 4, 2, 2, false, true>(PhantomData), 6);
+    }
+
+    #[test]
+    fn builder_shares_columns() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let first = Pow5ChipBuilder::<Fp, 3, 2>::new()
+            .low_degree(true)
+            .build::<P128Pow5T3<Fp>>(&mut meta);
+        let (advice, fixed) = (meta.num_advice_columns(), meta.num_fixed_columns());
+
+        let second = Pow5ChipBuilder::<Fp, 3, 2>::new()
+            .low_degree(true)
+            .shared_columns(&first)
+            .build::<Poseidon1Spec>(&mut meta);
+        assert_eq!(second.state, first.state);
+        assert_eq!(meta.num_advice_columns(), advice);
+        assert_eq!(meta.num_fixed_columns(), fixed);
+    }
+
+    #[test]
+    fn builder_takes_alpha_from_spec() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = Pow5ChipBuilder::<Fp, 3, 2>::new().build::<Alpha7Spec>(&mut meta);
+        assert_eq!(config.sbox_exponent(), 7);
+        let config = Pow5ChipBuilder::<Fp, 3, 2>::new().build::<P128Pow5T3<Fp>>(&mut meta);
+        assert_eq!(config.sbox_exponent(), 5);
+    }
+
+    #[test]
+    fn reconfigured_chips_have_disjoint_columns() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let first = Pow5ChipBuilder::<Fp, 3, 2>::new()
+            .compact(true)
+            .build::<P128Pow5T3<Fp>>(&mut meta);
+        let second = first.reconfigure::<P128Pow5T3<Fp>>(&mut meta);
+        let third = second.reconfigure::<P128Pow5T3<Fp>>(&mut meta);
+
+        let columns = |config: &Pow5Config<Fp, 3, 2>| {
+            let mut columns = config.equality_columns();
+            columns.extend(config.partial_sbox.map(Column::<Any>::from));
+            if let RoundConstantColumns::Fixed(rc_a) = config.rc_a {
+                columns.extend(rc_a.map(Column::<Any>::from));
+            }
+            columns
+        };
+        for (a, b) in [(&first, &second), (&first, &third), (&second, &third)] {
+            assert!(columns(a).iter().all(|column| !columns(b).contains(column)));
+        }
+
+        // The options and constants carry over.
+        assert!(third.partial_sbox.is_none());
+        assert_eq!(third.describe(), first.describe());
+    }
+
+    #[test]
+    fn configure_is_deterministic() {
+        // The pinned constraint system is what the verifying key commits to.
+        fn pinned(configure: impl Fn(&mut ConstraintSystem<Fp>)) -> String {
+            let mut meta = ConstraintSystem::<Fp>::default();
+            configure(&mut meta);
+            format!("{:?}", meta.pinned())
+        }
+
+        let configures: [fn(&mut ConstraintSystem<Fp>); 5] = [
+            |meta| {
+                <Pow5Chip<Fp, 3, 2> as PermuteChip<Fp, P128Pow5T3<Fp>, 3, 2>>::configure(meta);
+            },
+            |meta| {
+                PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta);
+            },
+            |meta| {
+                Pow5ChipBuilder::<Fp, 3, 2>::new()
+                    .low_degree(true)
+                    .compact(true)
+                    .build::<P128Pow5T3<Fp>>(meta);
+            },
+            |meta| {
+                let config = Pow5ChipBuilder::<Fp, 3, 2>::new().build::<P128Pow5T3<Fp>>(meta);
+                Pow5ChipBuilder::<Fp, 3, 2>::new()
+                    .shared_columns(&config)
+                    .build::<P128Pow5T3<Fp>>(meta);
+            },
+            |meta| {
+                let state = [(); 3].map(|_| meta.advice_column());
+                let partial_sbox = meta.advice_column();
+                let rc_a = [(); 3].map(|_| meta.advice_column());
+                let round_tag = meta.fixed_column();
+                let pad_fixed = [(); 3].map(|_| meta.fixed_column());
+                Pow5Chip::<Fp, 3, 2>::configure_with_lookup::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    round_tag,
+                    pad_fixed,
+                );
+            },
+        ];
+        for configure in configures {
+            assert_eq!(pinned(configure), pinned(configure));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot reconfigure a chip for a spec with other constants")]
+    fn reconfigure_rejects_other_constants() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = Pow5ChipBuilder::<Fp, 3, 2>::new().build::<P128Pow5T3<Fp>>(&mut meta);
+        config.reconfigure::<Poseidon1Spec>(&mut meta);
+    }
+
+    /// Permutes the state `[0, 1, 2]` and checks that the rate words of the full state,
+    /// and those of `squeeze_rate`, are the sponge output.
+    struct FullStateCircuit<S>(PhantomData<S>);
+
+    impl<S: Spec<Fp, 3, 2>> Circuit<Fp> for FullStateCircuit<S> {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FullStateCircuit(PhantomData)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            PermuteCircuit::<S, 3, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let initial_state = layouter.assign_region(
+                || "prepare initial state",
+                |mut region| {
+                    let words = (0..3).map(|i| {
+                        region
+                            .assign_advice(
+                                || format!("load state_{}", i),
+                                config.state[i],
+                                0,
+                                || Value::known(Fp::from(i as u64)),
+                            )
+                            .map(StateWord)
+                    });
+                    Ok(state_from_iter(words.collect::<Result<Vec<_>, Error>>()?))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config);
+            let state = PoseidonInstructions::<Fp, S, 3, 2>::permute(
+                &chip,
+                &mut layouter,
+                &initial_state,
+            )?;
+
+            let full_state = <Pow5Chip<Fp, 3, 2> as PoseidonSpongeInstructions<
+                Fp,
+                S,
+                ConstantLength<2>,
+                3,
+                2,
+            >>::get_full_state(&state);
+            let output = <Pow5Chip<Fp, 3, 2> as PoseidonSpongeInstructions<
+                Fp,
+                S,
+                ConstantLength<2>,
+                3,
+                2,
+            >>::get_output(&state);
+
+            let mut expected = [0, 1, 2].map(Fp::from);
+            poseidon::permute::<_, S, 3, 2>(&mut expected);
+            for (cell, expected) in full_state.iter().zip(expected) {
+                cell.value().assert_if_known(|value| **value == expected);
+            }
+            let rate = <Pow5Chip<Fp, 3, 2> as PoseidonSpongeInstructions<
+                Fp,
+                S,
+                ConstantLength<2>,
+                3,
+                2,
+            >>::squeeze_rate(&state);
+            for (i, word) in output.0.iter().enumerate() {
+                let word = word.as_ref().unwrap();
+                let index = S::state_endianness().rate_index(3, i);
+                assert_eq!(full_state[index].cell(), word.0.cell());
+                assert_eq!(rate[i].cell(), word.0.cell());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn full_state_contains_sponge_output() {
+        let prover = MockProver::run(7, &FullStateCircuit::<P128Pow5T3<Fp>>(PhantomData), vec![]);
+        assert_eq!(prover.unwrap().verify(), Ok(()));
+        let prover = MockProver::run(7, &FullStateCircuit::<BigEndianSpec>(PhantomData), vec![]);
+        assert_eq!(prover.unwrap().verify(), Ok(()));
+    }
+
+    /// Hashes a two-word message, i.e. an `initial_state`, an `add_input` and a
+    /// `permute`, under a gate requiring that no two of the chip's selectors are
+    /// enabled on the same row. With `overlap`, a region also enables two of them on
+    /// one row.
+    struct ExclusiveSelectorsCircuit {
+        overlap: bool,
+    }
+
+    impl Circuit<Fp> for ExclusiveSelectorsCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ExclusiveSelectorsCircuit {
+                overlap: self.overlap,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(meta);
+            assert!(config.s_last.is_none());
+
+            let selectors: Vec<_> = [config.s_first, config.s_full, config.s_pad_and_add]
+                .into_iter()
+                .chain(config.s_partial.iter().copied())
+                .collect();
+            meta.create_gate("selectors are exclusive", |meta| {
+                let selectors: Vec<_> =
+                    selectors.iter().map(|s| meta.query_selector(*s)).collect();
+                let mut products = vec![];
+                for (i, a) in selectors.iter().enumerate() {
+                    for b in &selectors[i + 1..] {
+                        products.push(a.clone() * b.clone());
+                    }
+                }
+                products
+            });
+
+            config
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            config.state[i],
+                            0,
+                            || Value::known(Fp::from(i as u64)),
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            if self.overlap {
+                // Both rounds hold on an all-zero row, so only the exclusivity gate
+                // can fail.
+                layouter.assign_region(
+                    || "overlapping selectors",
+                    |mut region| {
+                        config.s_full.enable(&mut region, 0)?;
+                        config.s_partial[0].enable(&mut region, 0)?;
+                        for row in 0..2 {
+                            for (i, column) in config.state.iter().enumerate() {
+                                region.assign_advice(
+                                    || format!("state_{}", i),
+                                    *column,
+                                    row,
+                                    || Value::known(Fp::ZERO),
+                                )?;
+                            }
+                        }
+                        region.assign_advice(
+                            || "partial_sbox",
+                            config.partial_sbox.unwrap(),
+                            0,
+                            || Value::known(Fp::ZERO),
+                        )?;
+                        Ok(())
+                    },
+                )?;
+            }
+
+            let chip = Pow5Chip::construct(config);
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            hasher.hash(layouter.namespace(|| "hash"), message)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn selectors_are_exclusive() {
+        let circuit = ExclusiveSelectorsCircuit { overlap: false };
+        let prover = MockProver::run(8, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = ExclusiveSelectorsCircuit { overlap: true };
+        let prover = MockProver::run(8, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn gate_info_of_width_3_config() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        let gate_info = config.gate_info();
+
+        // Each gate's own degree, plus one for its selector: the first layer and
+        // pad-and-add are linear, and the rounds apply an x^5 S-box.
+        let expected = [
+            ("first layer", 2),
+            ("full round", 6),
+            ("partial rounds #0", 6),
+            ("pad-and-add", 2),
+        ];
+        assert_eq!(
+            gate_info,
+            expected.map(|(name, degree)| (name.to_string(), degree))
+        );
+        let max = gate_info.iter().map(|(_, degree)| *degree).max();
+        assert_eq!(max, Some(meta.degree()));
+
+        // One partial-round gate per internal matrix, told apart by its index.
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<AlternatingMatrixSpec, 3, 2>::configure(&mut meta);
+        let partial: Vec<_> = config
+            .gate_info()
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| name.starts_with("partial rounds"))
+            .collect();
+        assert_eq!(partial, ["partial rounds #0", "partial rounds #1"]);
+    }
+
+    #[test]
+    fn describe_width_3_config() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        let description = config.describe();
+
+        assert!(description.starts_with(
+            "Pow5Config: width=3 rate=2 capacity=1 full_rounds=8 partial_rounds=56 alpha=5 \
+             first_layer=true\n"
+        ));
+        // The fingerprints only depend on the spec, not on the columns.
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let _ = meta.advice_column();
+        let shifted = PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+        assert_eq!(shifted.describe(), description);
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let other = PermuteCircuit::<Poseidon1Spec, 3, 2>::configure(&mut meta);
+        let other = other.describe();
+        let line = |description: &str, prefix: &str| {
+            let line = description.lines().find(|line| line.trim().starts_with(prefix));
+            line.unwrap().to_owned()
+        };
+        assert_eq!(line(&other, "external"), line(&description, "external"));
+        assert_ne!(line(&other, "internal"), line(&description, "internal"));
+        assert_eq!(line(&other, "round"), line(&description, "round"));
+    }
+
+    #[cfg(feature = "log-schedule")]
+    #[test]
+    fn configure_logs_round_schedule() {
+        use std::sync::Mutex;
+
+        struct CaptureLogger(Mutex<Vec<String>>);
+
+        impl log::Log for CaptureLogger {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut meta);
+
+        let expected = "Pow5Chip schedule: width=3 rate=2 full_rounds=8 partial_rounds=56 \
+                        first_layer=true mat_external=3x3 mat_internal=3x3";
+        assert!(LOGGER.0.lock().unwrap().iter().any(|line| line == expected));
+    }
+
+    #[test]
+    fn poseidon_permute_twice() {
+        let k = 8;
+        let prover = MockProver::run(k, &RepeatedPermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    test_spec! {
+        /// A spec reusing the external matrix for the partial rounds, as a Poseidon1
+        /// spec would.
+        struct SingleMatrixSpec<const POSEIDON2: bool>;
+        impl Spec<3, 2> {
+            internal_matrix: P128Pow5T3::<Fp>::external_matrix(),
+            is_poseidon2: POSEIDON2,
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "distinct internal and external matrices")]
+    fn poseidon2_spec_with_single_matrix() {
+        let circuit = PermuteCircuit::<SingleMatrixSpec<true>, 3, 2>(PhantomData);
+        let _ = MockProver::run(7, &circuit, vec![]);
+    }
+
+    #[test]
+    fn poseidon1_spec_with_single_matrix() {
+        let circuit = PermuteCircuit::<SingleMatrixSpec<false>, 3, 2>(PhantomData);
+        let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    #[test]
+    fn poseidon1_permute() {
+        let circuit = PermuteCircuit::<Poseidon1Spec, 3, 2>(PhantomData);
+        let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Without a first layer, the rounds start right after the loaded state.
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = PermuteCircuit::<Poseidon1Spec, 3, 2>::configure(&mut meta);
+        let schedule: Vec<_> = config.round_schedule().collect();
+        assert_eq!(schedule.len(), 8 + 56);
+        assert!(!schedule.contains(&RoundStep::First));
+        assert_eq!(Pow5Chip::<Fp, 3, 2>::permute_rows::<Poseidon1Spec>(), 1 + 8 + 56);
+    }
+
+    #[cfg(feature = "fingerprint")]
+    lazy_static::lazy_static! {
+        static ref CORRUPTED_ROUND_CONSTANTS: Vec<[Fp; 3]> = {
+            let mut round_constants = P128Pow5T3::<Fp>::round_constants().to_vec();
+            round_constants[30][1] += Fp::ONE;
+            round_constants
+        };
+    }
+
+    #[cfg(feature = "fingerprint")]
+    test_spec! {
+        /// The width-3 spec, with one round constant off by one.
+        struct CorruptedSpec;
+        impl Spec<3, 2> {
+            round_constants: Cow::Borrowed(&CORRUPTED_ROUND_CONSTANTS[..]),
+        }
+    }
+
+    #[cfg(feature = "fingerprint")]
+    #[test]
+    fn configure_checks_constants_fingerprint() {
+        use crate::base::primitives::FingerprintMismatch;
+
+        // The SHA-256 of the constants of `P128Pow5T3` over BN254, as documented on
+        // `Spec::constants_fingerprint`.
+        let mut expected = [0u8; 32];
+        hex::decode_to_slice(
+            "48fc579eb7923ab9f0ee42fddfc42a7bf6574aeb8c71cff42356a224fde58f3d",
+            &mut expected,
+        )
+        .unwrap();
+        assert_eq!(P128Pow5T3::<Fp>::constants_fingerprint(), expected);
+
+        let configure = |meta: &mut ConstraintSystem<Fp>, corrupted: bool| {
+            let state = [(); 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [(); 3].map(|_| meta.fixed_column());
+            let pad_fixed = [(); 3].map(|_| meta.fixed_column());
+            if corrupted {
+                Pow5Chip::<Fp, 3, 2>::configure_with_expected_fingerprint::<CorruptedSpec>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                    &expected,
+                )
+            } else {
+                Pow5Chip::<Fp, 3, 2>::configure_with_expected_fingerprint::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                    &expected,
+                )
+            }
+        };
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        assert!(configure(&mut meta, false).is_ok());
+
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let err = configure(&mut meta, true).unwrap_err();
+        assert_eq!(
+            err,
+            FingerprintMismatch {
+                expected,
+                actual: CorruptedSpec::constants_fingerprint(),
+            }
+        );
+        assert_ne!(err.actual, expected);
+        assert_eq!(meta.gates().len(), 0);
+    }
+
+    lazy_static::lazy_static! {
+        static ref ALTERNATING_INTERNAL: [Mds<Fp, 3>; 2] = [
+            *P128Pow5T3::<Fp>::internal_matrix(),
+            [
+                [Fp::from(2), Fp::from(1), Fp::from(1)],
+                [Fp::from(1), Fp::from(3), Fp::from(1)],
+                [Fp::from(1), Fp::from(1), Fp::from(4)],
+            ],
+        ];
+    }
+
+    test_spec! {
+        /// The width-3 spec, with its partial rounds alternating between two internal
+        /// matrices.
+        struct AlternatingMatrixSpec;
+        impl Spec<3, 2> {
+            internal_matrix: Cow::Borrowed(&ALTERNATING_INTERNAL[0]),
+            internal_matrices: Cow::Borrowed(&*ALTERNATING_INTERNAL),
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_alternating_internal_matrices() {
+        let initial = [Fp::from(0), Fp::from(1), Fp::from(2)];
+        let mut expected = initial;
+        permute::<_, AlternatingMatrixSpec, 3, 2>(&mut expected);
+
+        // Spell out the schedule, switching matrix on every partial round.
+        let half_full_rounds = AlternatingMatrixSpec::full_rounds() / 2;
+        let partial_rounds = AlternatingMatrixSpec::partial_rounds();
+        let mut state = initial;
+        poseidon::mat_mul(&mut state, &AlternatingMatrixSpec::external_matrix());
+        for (round, rc) in AlternatingMatrixSpec::round_constants().iter().enumerate() {
+            let partial = (half_full_rounds..half_full_rounds + partial_rounds).contains(&round);
+            let words = if partial { 1 } else { 3 };
+            for (word, rc) in state.iter_mut().zip(rc.iter()).take(words) {
+                *word = AlternatingMatrixSpec::sbox(*word + rc);
+            }
+            let matrix = if partial {
+                ALTERNATING_INTERNAL[(round - half_full_rounds) % 2]
+            } else {
+                *AlternatingMatrixSpec::external_matrix()
+            };
+            poseidon::mat_mul(&mut state, &matrix);
+        }
+        assert_eq!(state, expected);
+
+        let mut shared = initial;
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut shared);
+        assert_ne!(shared, expected);
+
+        let circuit = PermuteCircuit::<AlternatingMatrixSpec, 3, 2>(PhantomData);
+        let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    /// Hashes a two-word message at width 4, and binds the output to the first
+    /// instance row.
+    struct Width4HashCircuit<const RATE: usize, const CAPACITY: usize> {
+        message: [Value<Fp>; 2],
+    }
+
+    impl<const RATE: usize, const CAPACITY: usize> Circuit<Fp>
+        for Width4HashCircuit<RATE, CAPACITY>
+    {
+        type Config = (Pow5Config<Fp, 4, RATE, CAPACITY>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Width4HashCircuit {
+                message: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; 4].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 4].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 4].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let pow5 =
+                Pow5Chip::configure::<Width4Spec>(meta, state, partial_sbox, rc_a, pad_fixed);
+            (pow5, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            config.state[0],
+                            i,
+                            || self.message[i],
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.clone());
+            let hasher = Hash::<_, _, Width4Spec, ConstantLength<2>, 4, RATE>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    fn width4_hash_matches_reference<const RATE: usize, const CAPACITY: usize>() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let expected = poseidon::Hash::<_, Width4Spec, ConstantLength<2>, 4, RATE>::init()
+            .hash(message, Fp::ZERO);
+
+        let circuit = Width4HashCircuit::<RATE, CAPACITY> {
+            message: message.map(Value::known),
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn poseidon_hash_width4_one_capacity_element() {
+        width4_hash_matches_reference::<3, 1>();
+    }
+
+    #[test]
+    fn poseidon_hash_width4_two_capacity_elements() {
+        width4_hash_matches_reference::<2, 2>();
+    }
+
+    lazy_static::lazy_static! {
+        static ref WIDTH24_ROUND_CONSTANTS: Vec<[Fp; 24]> = (0..64u64)
+            .map(|round| core::array::from_fn(|i| Fp::from(round * 24 + i as u64 + 1).square()))
+            .collect();
+        // `circ(2 M_4, M_4, .., M_4)`, the shape of the Poseidon2 external matrix for
+        // widths that are multiples of 4.
+        static ref WIDTH24_EXTERNAL: Mds<Fp, 24> = core::array::from_fn(|i| {
+            core::array::from_fn(|j| {
+                let m4 = WIDTH4_EXTERNAL[i % 4][j % 4];
+                if i / 4 == j / 4 {
+                    m4.double()
+                } else {
+                    m4
+                }
+            })
+        });
+        static ref WIDTH24_INTERNAL: Mds<Fp, 24> = core::array::from_fn(|i| {
+            core::array::from_fn(|j| Fp::from(if i == j { i as u64 + 2 } else { 1 }))
+        });
+    }
+
+    test_spec! {
+        /// A spec at the largest of the [`super::WIDTH_CHOICES`]; its constants are only
+        /// meant for testing.
+        struct Width24Spec;
+        impl Spec<24, 23> {
+            round_constants: Cow::Borrowed(&WIDTH24_ROUND_CONSTANTS[..]),
+            internal_matrix: Cow::Borrowed(&WIDTH24_INTERNAL),
+            external_matrix: Cow::Borrowed(&WIDTH24_EXTERNAL),
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_width_24() {
+        // Each full-round gate sums 24 S-box outputs, but the terms share the degree of
+        // a single one, so the widest config has the degree of the narrowest, and one
+        // round per row fits a permutation in the same 2^7 rows.
+        let mut narrow = ConstraintSystem::<Fp>::default();
+        PermuteCircuit::<P128Pow5T3<Fp>, 3, 2>::configure(&mut narrow);
+        let mut wide = ConstraintSystem::<Fp>::default();
+        PermuteCircuit::<Width24Spec, 24, 23>::configure(&mut wide);
+        assert_eq!(wide.degree(), narrow.degree());
+
+        let k = 7;
+        let circuit = PermuteCircuit::<Width24Spec, 24, 23>(PhantomData);
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        assert!(MockProver::run(k - 1, &circuit, vec![]).is_err());
+    }
+
+    /// Hashes a two-word message under a witnessed key, and binds the output to the
+    /// instance column.
+    struct KeyedHashCircuit {
+        key: Value<Fp>,
+        message: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for KeyedHashCircuit {
+        type Config = (Pow5Config<Fp, 3, 2>, Column<Advice>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            KeyedHashCircuit {
+                key: Value::unknown(),
+                message: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let witnesses = meta.advice_column();
+            meta.enable_equality(witnesses);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let pow5 = Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                meta,
+                state,
+                partial_sbox,
+                rc_a,
+                pad_fixed,
+            );
+            (pow5, witnesses, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, witnesses, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let (key, message) = layouter.assign_region(
+                || "load key and message",
+                |mut region| {
+                    let key = region.assign_advice(|| "key", witnesses, 0, || self.key)?;
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            witnesses,
+                            i + 1,
+                            || self.message[i],
+                        )
+                    };
+                    Ok((key, [load(0)?, load(1)?]))
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config);
+            let state = chip.initial_state_with_key::<P128Pow5T3<Fp>>(&mut layouter, &key)?;
+            let mut sponge =
+                Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::with_state(chip, state);
+            sponge.absorb_cells(layouter.namespace(|| "absorb"), &message)?;
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    #[test]
+    fn poseidon_hash_with_key() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let keyed = |key: Fp| {
+            poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init_with_key(key)
+                .hash(message, Fp::ZERO)
+        };
+        let keys = [Fp::from(0x1234), Fp::from(0x5678)];
+        assert_ne!(keyed(keys[0]), keyed(keys[1]));
+
+        for key in keys {
+            let circuit = KeyedHashCircuit {
+                key: Value::known(key),
+                message: message.map(Value::known),
+            };
+            let prover = MockProver::run(8, &circuit, vec![vec![keyed(key)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+
+        // The output depends on the key that was witnessed.
+        let circuit = KeyedHashCircuit {
+            key: Value::known(keys[0]),
+            message: message.map(Value::known),
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![keyed(keys[1])]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    test_spec! {
+        /// A degenerate spec over a single word.
+        struct WidthOneSpec;
+        impl Spec<1, 0> {
+            round_constants: unimplemented!(),
+            internal_matrix: unimplemented!(),
+            external_matrix: unimplemented!(),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Pow5Chip needs a width of at least 2, got 1")]
+    fn width_one_is_rejected() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let state = [meta.advice_column()];
+        let partial_sbox = meta.advice_column();
+        let rc_a = [meta.fixed_column()];
+        let pad_fixed = [meta.fixed_column()];
+
+        let _: Pow5Config<Fp, 1, 0> = Pow5Chip::configure::<WidthOneSpec>(
+            &mut meta,
+            state,
+            partial_sbox,
+            rc_a,
+            pad_fixed,
+        );
+    }
+
+    test_spec! {
+        /// The width-3 spec, with too few round constants for its rounds.
+        struct ShortConstantsSpec;
+        impl Spec<3, 2> {
+            round_constants: Cow::Borrowed(&<Fp as P128Pow5T3Constants>::round_constants()[..60]),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "spec has round constants for 60 rounds, but needs 64")]
+    fn short_round_constants_are_rejected() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let state = [0; 3].map(|_| meta.advice_column());
+        let partial_sbox = meta.advice_column();
+        let rc_a = [0; 3].map(|_| meta.fixed_column());
+        let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+        let _: Pow5Config<Fp, 3, 2> = Pow5Chip::configure::<ShortConstantsSpec>(
+            &mut meta,
+            state,
+            partial_sbox,
+            rc_a,
+            pad_fixed,
+        );
+    }
+
+    lazy_static::lazy_static! {
+        /// The width-4 round constants regrouped into rows of 3, as if a table built
+        /// for one width were reused at another.
+        static ref REGROUPED_ROUND_CONSTANTS: Vec<[Fp; 3]> = WIDTH4_ROUND_CONSTANTS
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>()
+            .chunks_exact(3)
+            .map(|row| [row[0], row[1], row[2]])
+            .collect();
+    }
+
+    test_spec! {
+        /// The width-3 spec, with round constants built for width 4.
+        struct RegroupedConstantsSpec;
+        impl Spec<3, 2> {
+            round_constants: Cow::Borrowed(&REGROUPED_ROUND_CONSTANTS[..]),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "spec has round constants for 85 rounds, but needs 64")]
+    fn regrouped_round_constants_are_rejected() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let state = [0; 3].map(|_| meta.advice_column());
+        let partial_sbox = meta.advice_column();
+        let rc_a = [0; 3].map(|_| meta.fixed_column());
+        let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+        let _: Pow5Config<Fp, 3, 2> = Pow5Chip::configure::<RegroupedConstantsSpec>(
+            &mut meta,
+            state,
+            partial_sbox,
+            rc_a,
+            pad_fixed,
+        );
+    }
+
+    test_spec! {
+        /// The width-3 spec, with the final full round's matrix multiplication omitted.
+        struct SkipLastMdsSpec;
+        impl Spec<3, 2> {
+            skip_last_mds: true,
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_skip_last_mds() {
+        // The reference differs from the full permutation only by the last matrix.
+        let mut skipped = [Fp::from(0), Fp::from(1), Fp::from(2)];
+        permute::<_, SkipLastMdsSpec, 3, 2>(&mut skipped);
+        let mut full = [Fp::from(0), Fp::from(1), Fp::from(2)];
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut full);
+        assert_ne!(skipped, full);
+        poseidon::mat_mul(&mut skipped, &P128Pow5T3::<Fp>::external_matrix());
+        assert_eq!(skipped, full);
+
+        let circuit = PermuteCircuit::<SkipLastMdsSpec, 3, 2>(PhantomData);
+        let prover = MockProver::run(7, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    struct LookupPermuteCircuit;
+
+    impl Circuit<Fp> for LookupPermuteCircuit {
+        type Config = Pow5Config<Fp, 3, 2>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            LookupPermuteCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Pow5Config<Fp, 3, 2> {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.advice_column());
+            let round_tag = meta.fixed_column();
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            Pow5Chip::configure_with_lookup::<P128Pow5T3<Fp>>(
+                meta,
+                state,
+                partial_sbox,
+                rc_a,
+                round_tag,
+                pad_fixed,
+            )
+        }
+
+        fn synthesize(
+            &self,
+            config: Pow5Config<Fp, 3, 2>,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            Pow5Chip::construct(config.clone()).load_round_constant_table(&mut layouter)?;
+            permute_and_check::<P128Pow5T3<Fp>, 3, 2>(&config, &mut layouter)
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_with_lookup() {
+        // The table holds (8 + 56 + 1) * 3 rows, hence the larger k.
+        let k = 8;
+        let prover = MockProver::run(k, &LookupPermuteCircuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()))
+    }
+
+    /// Permutes the state in the first three instance rows, and binds the result to the
+    /// next three.
+    struct InstanceStateCircuit;
+
+    impl Circuit<Fp> for InstanceStateCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            InstanceStateCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let state = chip.load_state_from_instance(&mut layouter, config.instance, 0)?;
+            let output = <Pow5Chip<_, 3, 2> as PoseidonInstructions<
+                Fp,
+                P128Pow5T3<Fp>,
+                3,
+                2,
+            >>::permute(&chip, &mut layouter, &state)?;
+
+            for (i, word) in output.iter().enumerate() {
+                layouter.constrain_instance(word.0.cell(), config.instance, 3 + i)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn poseidon_permute_state_from_instance() {
+        let initial = [Fp::from(6), Fp::from(42), Fp::from(7)];
+        let mut permuted = initial;
+        permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut permuted);
+
+        let instance: Vec<_> = initial.into_iter().chain(permuted).collect();
+        let prover = MockProver::run(7, &InstanceStateCircuit, vec![instance.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // The state is read from the instance column, not witnessed.
+        let mut wrong = instance;
+        wrong[1] += Fp::ONE;
+        let prover = MockProver::run(7, &InstanceStateCircuit, vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes the same instance-provided message with two hashers sharing one config.
+    struct NamespacedHashCircuit;
+
+    impl Circuit<Fp> for NamespacedHashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            NamespacedHashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            for namespace in ["left", "right"] {
+                let chip = Pow5Chip::construct(config.pow5.clone()).with_namespace(namespace);
+
+                let message = [
+                    chip.load_from_instance(&mut layouter, config.instance, 0, 0)?.into(),
+                    chip.load_from_instance(&mut layouter, config.instance, 1, 1)?.into(),
+                ];
+                let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                    chip,
+                    layouter.namespace(|| format!("{} init", namespace)),
+                )?;
+                let output =
+                    hasher.hash(layouter.namespace(|| format!("{} hash", namespace)), message)?;
+
+                layouter.constrain_instance(output.cell(), config.instance, 2)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn poseidon_namespaced_hashers() {
+        let config = {
+            let mut meta = ConstraintSystem::<Fp>::default();
+            InstanceHashCircuit::configure(&mut meta)
+        };
+        let left = Pow5Chip::construct(config.pow5.clone()).with_namespace("left");
+        let right = Pow5Chip::construct(config.pow5.clone()).with_namespace("right");
+        assert_eq!(left.region_name("permute state"), "left: permute state");
+        assert_ne!(
+            left.region_name("permute state"),
+            right.region_name("permute state")
+        );
+        assert_eq!(
+            Pow5Chip::construct(config.pow5).region_name("permute state"),
+            "permute state"
+        );
+
+        let message = [Fp::from(6), Fp::from(42)];
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash(message, Fp::ZERO);
+        let prover = MockProver::run(
+            9,
+            &NamespacedHashCircuit,
+            vec![vec![message[0], message[1], output]],
+        )
+        .unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // A wrong output fails, and the failure points into a namespaced region.
+        let prover = MockProver::run(
+            9,
+            &NamespacedHashCircuit,
+            vec![vec![message[0], message[1], output + Fp::ONE]],
+        )
+        .unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures.iter().any(|failure| {
+            let failure = failure.to_string();
+            failure.contains("'left: ") || failure.contains("'right: ")
+        }));
+    }
+
+    #[derive(Clone, Debug)]
+    struct InstanceHashConfig {
+        pow5: Pow5Config<Fp, 3, 2>,
+        instance: Column<Instance>,
+    }
+
+    /// Hashes the first two instance rows and binds the output to the third.
+    struct InstanceHashCircuit;
+
+    impl Circuit<Fp> for InstanceHashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            InstanceHashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            InstanceHashConfig {
+                pow5: Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+
+            let message = [
+                chip.load_from_instance(&mut layouter, config.instance, 0, 0)?.into(),
+                chip.load_from_instance(&mut layouter, config.instance, 1, 1)?.into(),
+            ];
+
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
+
+            layouter.constrain_instance(output.cell(), config.instance, 2)
+        }
+    }
+
+    /// Hashes a witnessed message with the gadget and constrains the output to the
+    /// value computed by `Pow5Chip::hash_value`.
+    struct HashValueCircuit {
+        message: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for HashValueCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            HashValueCircuit {
+                message: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let expected = Pow5Chip::<Fp, 3, 2>::hash_value::<P128Pow5T3<Fp>, ConstantLength<2>>(
+                &self.message,
+            );
+
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            config.pow5.state[i],
+                            0,
+                            || self.message[i],
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
+            output
+                .value()
+                .zip(expected)
+                .assert_if_known(|(output, expected)| **output == *expected);
+
+            layouter.assign_region(
+                || "constrain output",
+                |mut region| {
+                    let expected =
+                        region.assign_advice(|| "expected", config.pow5.state[0], 0, || expected)?;
+                    region.constrain_equal(output.cell(), expected.cell())
+                },
+            )
+        }
+    }
+
+    /// Absorbs one message word and one padding word into a variable-length sponge, and
+    /// binds the squeezed output to the first instance row.
+    struct PaddedSpongeCircuit {
+        message: Value<Fp>,
+        padding: Fp,
+    }
+
+    impl Circuit<Fp> for PaddedSpongeCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            PaddedSpongeCircuit {
+                message: Value::unknown(),
+                padding: self.padding,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    region.assign_advice(|| "message", config.pow5.state[0], 0, || self.message)
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, VariableLength, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb(layouter.namespace(|| "message"), PaddedWord::Message(message))?;
+            sponge.absorb(layouter.namespace(|| "padding"), PaddedWord::Padding(self.padding))?;
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+
+            layouter.constrain_instance(output.cell(), config.instance, 0)
+        }
+    }
+
+    #[test]
+    fn add_input_constrains_padding() {
+        // The padding word is loaded from `pad_fixed` and copied into the state, so
+        // this only verifies if equality is enabled on `pad_fixed`.
+        let message = Fp::from(42);
+        let expected = poseidon::Hash::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init()
+            .hash_with_cap(&[message], 0);
+
+        let circuit = PaddedSpongeCircuit {
+            message: Value::known(message),
+            padding: Fp::ONE,
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // The chip doesn't check the padding against the domain's: a zero padding word
+        // is absorbed just as faithfully, and the circuit fails only because the
+        // squeezed output no longer matches the instance.
+        let circuit = PaddedSpongeCircuit {
+            message: Value::known(message),
+            padding: Fp::ZERO,
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn hash_value_matches_gadget() {
+        let circuit = HashValueCircuit {
+            message: [Value::known(Fp::from(3)), Value::known(Fp::from(11))],
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    /// Hashes a message witnessed in a column without equality enabled.
+    struct NoEqualityHashCircuit;
+
+    impl Circuit<Fp> for NoEqualityHashCircuit {
+        type Config = (Pow5Config<Fp, 3, 2>, Column<Advice>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            NoEqualityHashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let message = meta.advice_column();
+            (InstanceHashCircuit::configure(meta).pow5, message)
+        }
+
+        fn synthesize(
+            &self,
+            (config, message_column): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("message_{}", i),
+                            message_column,
+                            i,
+                            || Value::known(Fp::from(i as u64)),
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config);
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            hasher.hash(layouter.namespace(|| "hash"), message)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn message_column_without_equality() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let (_, message_column) = NoEqualityHashCircuit::configure(&mut meta);
+        match MockProver::run(8, &NoEqualityHashCircuit, vec![vec![]]) {
+            Err(Error::ColumnNotInPermutation(column)) => {
+                assert_eq!(column, Column::<Any>::from(message_column))
+            }
+            other => panic!("expected the message column to be rejected, got {:?}", other.err()),
+        }
+    }
+
+    /// Hashes the empty message and binds the output to the first instance row.
+    struct EmptyHashCircuit;
+
+    impl Circuit<Fp> for EmptyHashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            EmptyHashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<0>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), [])?;
+
+            layouter.constrain_instance(output.cell(), config.instance, 0)
+        }
+    }
+
+    #[test]
+    fn poseidon_hash_empty_message() {
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<0>, 3, 2>::init()
+            .hash([], Fp::ZERO);
+
+        // The empty message hashes to the first word of the permuted initial state.
+        let mut iv = [Fp::ZERO; 3];
+        poseidon::permute::<_, P128Pow5T3<Fp>, 3, 2>(&mut iv);
+        assert_eq!(output, iv[0]);
+
+        let prover = MockProver::run(8, &EmptyHashCircuit, vec![vec![output]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn poseidon_hash_from_instance() {
+        let message = [Fp::from(6), Fp::from(42)];
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash(message, Fp::ZERO);
+
+        let k = 8;
+        let prover =
+            MockProver::run(k, &InstanceHashCircuit, vec![vec![message[0], message[1], output]])
+                .unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(
+            k,
+            &InstanceHashCircuit,
+            vec![vec![message[0], message[1], output + Fp::ONE]],
+        )
+        .unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes a constant, a witnessed word and the first instance row, and binds the
+    /// output to the second instance row.
+    struct MixedInputHashCircuit {
+        x: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for MixedInputHashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            MixedInputHashCircuit { x: Value::unknown() }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            InstanceHashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+
+            let x = layouter.assign_region(
+                || "load x",
+                |mut region| region.assign_advice(|| "x", config.pow5.state[0], 0, || self.x),
+            )?;
+
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<3>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash_inputs(
+                layouter.namespace(|| "hash"),
+                [
+                    SpongeInput::Constant(Fp::ONE),
+                    SpongeInput::Witnessed(x),
+                    SpongeInput::Instance(config.instance, 0),
+                ],
+            )?;
+
+            layouter.constrain_instance(output.cell(), config.instance, 1)
+        }
+    }
+
+    #[test]
+    fn hash_test_vectors() {
+        use crate::base::test_vectors::{self, from_hex};
+
+        for tv in test_vectors::hash() {
+            let message = tv.input.map(from_hex::<Fp>);
+            let output = from_hex::<Fp>(tv.output);
+
+            let prover =
+                MockProver::run(8, &InstanceHashCircuit, vec![vec![message[0], message[1], output]])
+                    .unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    /// `InstanceHashCircuit` over a spec with the capacity element in `state[0]`.
+    struct BigEndianHashCircuit;
+
+    impl Circuit<Fp> for BigEndianHashCircuit {
+        type Config = InstanceHashConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BigEndianHashCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> InstanceHashConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            InstanceHashConfig {
+                pow5: Pow5Chip::configure::<BigEndianSpec>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: InstanceHashConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let hasher = Hash::<_, _, BigEndianSpec, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash_inputs(
+                layouter.namespace(|| "hash"),
+                [
+                    SpongeInput::Instance(config.instance, 0),
+                    SpongeInput::Instance(config.instance, 1),
+                ],
+            )?;
+
+            layouter.constrain_instance(output.cell(), config.instance, 2)
+        }
+    }
+
+    #[test]
+    fn hash_big_endian_test_vectors() {
+        use crate::base::test_vectors::{self, from_hex};
+
+        for tv in test_vectors::hash_big_endian() {
+            let message = tv.input.map(from_hex::<Fp>);
+            let output = from_hex::<Fp>(tv.output);
+
+            let prover = MockProver::run(
+                8,
+                &BigEndianHashCircuit,
+                vec![vec![message[0], message[1], output]],
+            )
+            .unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn min_k_fits_hash_circuit() {
+        let k = Pow5Chip::<Fp, 3, 2>::min_k::<P128Pow5T3<Fp>, ConstantLength<3>>(3);
+        assert_eq!(k, 8);
+
+        let x = Fp::from(7);
+        let public = Fp::from(42);
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<3>, 3, 2>::init()
+            .hash([Fp::ONE, x, public], Fp::ZERO);
+        let circuit = MixedInputHashCircuit { x: Value::known(x) };
+
+        let prover = MockProver::run(k, &circuit, vec![vec![public, output]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+        assert!(MockProver::run(k - 1, &circuit, vec![vec![public, output]]).is_err());
+    }
+
+    #[test]
+    fn poseidon_hash_mixed_inputs() {
+        let x = Fp::from(7);
+        let public = Fp::from(42);
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<3>, 3, 2>::init()
+            .hash([Fp::ONE, x, public], Fp::ZERO);
+
+        let k = 8;
+        let circuit = MixedInputHashCircuit { x: Value::known(x) };
+        let prover = MockProver::run(k, &circuit, vec![vec![public, output]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let circuit = MixedInputHashCircuit {
+            x: Value::known(x + Fp::ONE),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![public, output]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes an `L`-word message in the [`ConstantLength<L>`] domain, and binds the
+    /// output to the first instance row.
+    ///
+    /// This is the smallest circuit using the [`Hash`] gadget: configure a [`Pow5Chip`]
+    /// with a constant-enabled fixed column, load the message, and hash it.
+    struct HashCircuit<
+        S: Spec<Fp, WIDTH, RATE>,
+        const WIDTH: usize,
+        const RATE: usize,
+        const L: usize,
+    > {
+        message: Value<[Fp; L]>,
+        _spec: PhantomData<S>,
+    }
+
+    impl<S: Spec<Fp, WIDTH, RATE>, const WIDTH: usize, const RATE: usize, const L: usize>
+        Circuit<Fp> for HashCircuit<S, WIDTH, RATE, L>
+    {
+        type Config = (Pow5Config<Fp, WIDTH, RATE>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                message: Value::unknown(),
+                _spec: PhantomData,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let state = [0; WIDTH].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; WIDTH].map(|_| meta.fixed_column());
+            let pad_fixed = [0; WIDTH].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            let pow5 = Pow5Chip::configure::<S>(meta, state, partial_sbox, rc_a, pad_fixed);
+            (pow5, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.clone());
+
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let message_word = |i: usize| {
+                        let value = self.message.map(|message_vals| message_vals[i]);
+                        region.assign_advice(
+                            || format!("load message_{}", i),
+                            config.state[0],
+                            i,
+                            || value,
+                        )
+                    };
+
+                    let message: Result<Vec<_>, Error> = (0..L).map(message_word).collect();
+                    Ok(message?.try_into().unwrap())
+                },
+            )?;
+
+            let hasher = Hash::<_, _, S, ConstantLength<L>, WIDTH, RATE>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = hasher.hash(layouter.namespace(|| "hash"), message)?;
+
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    fn check_hash_circuit<const L: usize>(k: u32) {
+        let message: [Fp; L] = core::array::from_fn(|i| Fp::from(i as u64 * 7 + 1));
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<L>, 3, 2>::init()
+            .hash(message, Fp::ZERO);
+
+        let circuit = HashCircuit::<P128Pow5T3<Fp>, 3, 2, L> {
+            message: Value::known(message),
+            _spec: PhantomData,
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![output]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let prover = MockProver::run(k, &circuit, vec![vec![output + Fp::ONE]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes a two-word message, and checks the bytes of the output against `expected`
+    /// while synthesizing.
+    struct HashBytesCircuit {
+        message: [Value<Fp>; 2],
+        expected: [u8; 32],
+    }
+
+    impl Circuit<Fp> for HashBytesCircuit {
+        type Config = (Pow5Config<Fp, 3, 2>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            HashBytesCircuit {
+                message: [Value::unknown(); 2],
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            HashCircuit::<P128Pow5T3<Fp>, 3, 2, 2>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (config, _): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.clone());
+            let message = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    let a = region.assign_advice(|| "a", config.state[0], 0, || self.message[0])?;
+                    let b = region.assign_advice(|| "b", config.state[1], 0, || self.message[1])?;
+                    Ok([a, b])
+                },
+            )?;
+
+            let hasher = Hash::<_, _, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            let output = StateWord::from(hasher.hash(layouter.namespace(|| "hash"), message)?);
+            output
+                .to_bytes()
+                .assert_if_known(|bytes| *bytes == self.expected);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn hash_output_bytes_match_reference() {
+        let message = [Fp::from(3), Fp::from(5)];
+        let output = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash(message, Fp::ZERO);
+
+        let circuit = HashBytesCircuit {
+            message: message.map(Value::known),
+            expected: output.to_repr(),
+        };
+        let prover = MockProver::run(7, &circuit, vec![vec![]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn poseidon_hash() {
+        check_hash_circuit::<2>(7);
+    }
+
+    #[test]
+    fn poseidon_hash_longer_input() {
+        check_hash_circuit::<3>(8);
+    }
+
+    #[test]
+    fn poseidon_hash_longer_input_custom() {
+        check_hash_circuit::<4>(8);
+    }
 
 //     #[test]
 //     fn hash_test_vectors() {
@@ -0,0 +1,668 @@
+//! A running-sum range check, used to bound words before they are absorbed.
+
+use std::marker::PhantomData;
+
+use ff::{Field, PrimeField};
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Value},
+    plonk::{Advice, Column, ConstraintSystem, Constraints, Error, Expression, Selector},
+    poly::Rotation,
+};
+use num_bigint::BigUint;
+use num_traits::Num;
+
+use super::utils::bool_check;
+use crate::base::primitives::reduce_index;
+
+/// Configuration for a [`RangeCheckChip`].
+#[derive(Clone, Debug)]
+pub struct RangeCheckConfig<F: PrimeField> {
+    z: Column<Advice>,
+    q_bit: Selector,
+    q_zero: Selector,
+    q_reduce: Selector,
+    _marker: PhantomData<F>,
+}
+
+/// Constrains a cell to a number of bits by decomposing it into a running sum.
+///
+/// Row `i` of the region holds `z_i`, where `z_0` is the checked value and
+/// `z_{i+1} = (z_i - b_i) / 2` for the `i`-th bit `b_i`. Each `z_i - 2 z_{i+1}` is
+/// constrained to be boolean and the final `z_bits` to be zero, which uses `bits + 1`
+/// rows.
+#[derive(Clone, Debug)]
+pub struct RangeCheckChip<F: PrimeField> {
+    config: RangeCheckConfig<F>,
+}
+
+impl<F: PrimeField> RangeCheckChip<F> {
+    /// Configures this chip for use in a circuit.
+    ///
+    /// # Side-effects
+    ///
+    /// `z` will be equality-enabled.
+    pub fn configure(meta: &mut ConstraintSystem<F>, z: Column<Advice>) -> RangeCheckConfig<F> {
+        meta.enable_equality(z);
+
+        let q_bit = meta.selector();
+        let q_zero = meta.selector();
+        let q_reduce = meta.selector();
+
+        meta.create_gate("range check bit", |meta| {
+            let q_bit = meta.query_selector(q_bit);
+            let z_cur = meta.query_advice(z, Rotation::cur());
+            let z_next = meta.query_advice(z, Rotation::next());
+
+            Constraints::with_selector(q_bit, Some(bool_check(z_cur - z_next * F::from(2))))
+        });
+
+        meta.create_gate("range check end", |meta| {
+            let q_zero = meta.query_selector(q_zero);
+            let z_cur = meta.query_advice(z, Rotation::cur());
+
+            Constraints::with_selector(q_zero, Some(z_cur))
+        });
+
+        // The rows of a `reduce` region, whose differences are range checked elsewhere.
+        meta.create_gate("reduce", |meta| {
+            let q_reduce = meta.query_selector(q_reduce);
+            let [value, quotient, remainder, modulus, remainder_gap, bound, quotient_gap] =
+                [0, 1, 2, 3, 4, 5, 6].map(|row| meta.query_advice(z, Rotation(row)));
+
+            Constraints::with_selector(
+                q_reduce,
+                [
+                    (
+                        "value = quotient * modulus + remainder",
+                        value - (quotient.clone() * modulus.clone() + remainder.clone()),
+                    ),
+                    (
+                        "remainder_gap = modulus - 1 - remainder",
+                        remainder_gap - (modulus - Expression::Constant(F::ONE) - remainder),
+                    ),
+                    ("quotient_gap = bound - quotient", quotient_gap - (bound - quotient)),
+                ],
+            )
+        });
+
+        RangeCheckConfig {
+            z,
+            q_bit,
+            q_zero,
+            q_reduce,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Constructs a [`RangeCheckChip`].
+    pub fn construct(config: RangeCheckConfig<F>) -> Self {
+        RangeCheckChip { config }
+    }
+
+    /// Assigns `2 * value + tag`, with `tag` constrained to be boolean, as
+    /// [`pack_tagged`] computes it off-circuit.
+    ///
+    /// This is the first row of a range check: `value` is the running sum after the
+    /// packed word's lowest bit, which is `tag`.
+    ///
+    /// [`pack_tagged`]: crate::base::primitives::pack_tagged
+    pub fn pack_tag(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+        tag: Value<bool>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = &self.config;
+
+        layouter.assign_region(
+            || "pack tag",
+            |mut region| {
+                config.q_bit.enable(&mut region, 0)?;
+                let packed = value
+                    .value()
+                    .zip(tag)
+                    .map(|(value, tag)| value.double() + F::from(u64::from(tag)));
+                let packed = region.assign_advice(|| "packed", config.z, 0, || packed)?;
+                value.copy_advice(|| "value", &mut region, config.z, 1)?;
+
+                Ok(packed)
+            },
+        )
+    }
+
+    /// Constrains `cell` to be less than `2^bits`.
+    pub fn range_check(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &AssignedCell<F, F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        assert!(
+            bits < F::NUM_BITS as usize,
+            "cannot range check to {} bits in a {}-bit field",
+            bits,
+            F::NUM_BITS
+        );
+        let config = &self.config;
+        let two_inv = F::from(2).invert().unwrap();
+
+        layouter.assign_region(
+            || format!("range check {} bits", bits),
+            |mut region| {
+                let mut z = cell.copy_advice(|| "z_0", &mut region, config.z, 0)?;
+                for i in 0..bits {
+                    config.q_bit.enable(&mut region, i)?;
+                    let next = z.value().map(|z| {
+                        let bit = F::from(u64::from(bool::from(z.is_odd())));
+                        (*z - bit) * two_inv
+                    });
+                    z = region.assign_advice(
+                        || format!("z_{}", i + 1),
+                        config.z,
+                        i + 1,
+                        || next,
+                    )?;
+                }
+                config.q_zero.enable(&mut region, bits)?;
+
+                Ok(())
+            },
+        )
+    }
+}
+
+impl<F: PrimeField<Repr = [u8; 32]>> RangeCheckChip<F> {
+    /// Reduces `value` modulo `modulus`, returning the remainder [`reduce_index`]
+    /// computes off-circuit.
+    ///
+    /// The region witnesses `value = quotient * modulus + remainder`, and range checks
+    /// `remainder` and `modulus - 1 - remainder` to the bits of `modulus - 1`, so that
+    /// the remainder is below `modulus`. The quotient is likewise checked to be at most
+    /// `floor((p - 1) / modulus) - 1`, so that the decomposition cannot wrap around the
+    /// field. The at most `modulus` values from `floor((p - 1) / modulus) * modulus` up
+    /// are therefore left without a decomposition, which a pseudorandom value, such as a
+    /// squeezed one, only hits with negligible probability.
+    ///
+    /// `modulus` and the quotient's bound are loaded as constants, so the circuit needs
+    /// a constant-enabled fixed column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is below 2.
+    pub fn reduce(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: &AssignedCell<F, F>,
+        modulus: u64,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        assert!(modulus >= 2, "cannot reduce modulo {}", modulus);
+        let config = &self.config;
+
+        let p = BigUint::from_str_radix(F::MODULUS.trim_start_matches("0x"), 16)
+            .expect("MODULUS is a hex string");
+        let bound = (&p - 1u32) / modulus - 1u32;
+        let quotient_bits = bound.bits() as usize;
+        // The quotient and its gap to the bound each fit in `quotient_bits`, so their sum
+        // must not reach `p + bound`, or it could wrap around to the bound.
+        assert!(
+            (BigUint::from(1u32) << (quotient_bits + 1)) < &p + &bound,
+            "the quotient bound for modulus {} is too close to the field size",
+            modulus
+        );
+        let bound = F::from_str_vartime(&bound.to_str_radix(10)).unwrap();
+        let remainder_bits = (u64::BITS - (modulus - 1).leading_zeros()) as usize;
+
+        let (quotient, remainder, quotient_gap, remainder_gap) = layouter.assign_region(
+            || format!("reduce mod {}", modulus),
+            |mut region| {
+                config.q_reduce.enable(&mut region, 0)?;
+                value.copy_advice(|| "value", &mut region, config.z, 0)?;
+
+                let remainder = value.value().map(|value| F::from(reduce_index(*value, modulus)));
+                // `value - remainder` is a multiple of `modulus`, so dividing by it in the
+                // field gives the integer quotient.
+                let modulus_inv = F::from(modulus).invert().unwrap();
+                let quotient = value
+                    .value()
+                    .zip(remainder)
+                    .map(|(value, remainder)| (*value - remainder) * modulus_inv);
+
+                let quotient = region.assign_advice(|| "quotient", config.z, 1, || quotient)?;
+                let remainder = region.assign_advice(|| "remainder", config.z, 2, || remainder)?;
+                region.assign_advice_from_constant(|| "modulus", config.z, 3, F::from(modulus))?;
+                let remainder_gap = region.assign_advice(
+                    || "remainder gap",
+                    config.z,
+                    4,
+                    || remainder.value().map(|remainder| F::from(modulus - 1) - remainder),
+                )?;
+                region.assign_advice_from_constant(|| "quotient bound", config.z, 5, bound)?;
+                let quotient_gap = region.assign_advice(
+                    || "quotient gap",
+                    config.z,
+                    6,
+                    || quotient.value().map(|quotient| bound - quotient),
+                )?;
+
+                Ok((quotient, remainder, quotient_gap, remainder_gap))
+            },
+        )?;
+
+        for (name, cell, bits) in [
+            ("remainder", &remainder, remainder_bits),
+            ("remainder gap", &remainder_gap, remainder_bits),
+            ("quotient", &quotient, quotient_bits),
+            ("quotient gap", &quotient_gap, quotient_bits),
+        ] {
+            self.range_check(layouter.namespace(|| name), cell, bits)?;
+        }
+
+        Ok(remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::{Field, PrimeField};
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::{RangeCheckChip, RangeCheckConfig};
+    use crate::base::primitives::{
+        self as poseidon, pack_tagged, reduce_index, u256_limbs, ConstantLength, Domain,
+    };
+    use crate::base::P128Pow5T3;
+    use crate::circuit::poseidon::Sponge;
+    use crate::circuit::pow5::{Pow5Chip, Pow5Config};
+
+    #[derive(Clone, Debug)]
+    struct BoundedAbsorbConfig {
+        pow5: Pow5Config<Fp, 3, 2>,
+        range_check: RangeCheckConfig<Fp>,
+        message: Column<Advice>,
+    }
+
+    /// Absorbs a two-word message, each word bounded to 64 bits.
+    struct BoundedAbsorbCircuit {
+        message: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for BoundedAbsorbCircuit {
+        type Config = BoundedAbsorbConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BoundedAbsorbCircuit {
+                message: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> BoundedAbsorbConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let message = meta.advice_column();
+            meta.enable_equality(message);
+            let z = meta.advice_column();
+
+            BoundedAbsorbConfig {
+                pow5: Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                range_check: RangeCheckChip::configure(meta, z),
+                message,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: BoundedAbsorbConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let cells = layouter.assign_region(
+                || "load message",
+                |mut region| {
+                    self.message
+                        .iter()
+                        .enumerate()
+                        .map(|(i, value)| {
+                            region.assign_advice(
+                                || format!("message_{}", i),
+                                config.message,
+                                i,
+                                || *value,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let range_check = RangeCheckChip::construct(config.range_check.clone());
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            for (i, cell) in cells.into_iter().enumerate() {
+                sponge.absorb_bounded(
+                    layouter.namespace(|| format!("absorb_{}", i)),
+                    &range_check,
+                    cell,
+                    64,
+                )?;
+            }
+            sponge.finish_absorbing(layouter.namespace(|| "finish absorbing"))?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn absorb_bounded_rejects_out_of_range() {
+        let k = 8;
+        let in_range = BoundedAbsorbCircuit {
+            message: [Value::known(Fp::from(u64::MAX)), Value::known(Fp::from(7))],
+        };
+        let prover = MockProver::run(k, &in_range, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let out_of_range = BoundedAbsorbCircuit {
+            message: [
+                Value::known(Fp::from(7)),
+                Value::known(Fp::from_u128(1 << 64)),
+            ],
+        };
+        let prover = MockProver::run(k, &out_of_range, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes a 256-bit word given as its two limbs, and binds the output to the
+    /// instance column.
+    struct U256HashCircuit {
+        limbs: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for U256HashCircuit {
+        type Config = (BoundedAbsorbConfig, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            U256HashCircuit {
+                limbs: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            (BoundedAbsorbCircuit::configure(meta), instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let [lo, hi] = layouter.assign_region(
+                || "load limbs",
+                |mut region| {
+                    let mut load = |i: usize| {
+                        region.assign_advice(
+                            || format!("limb_{}", i),
+                            config.message,
+                            i,
+                            || self.limbs[i],
+                        )
+                    };
+                    Ok([load(0)?, load(1)?])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let range_check = RangeCheckChip::construct(config.range_check.clone());
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_u256(layouter.namespace(|| "absorb u256"), &range_check, lo, hi)?;
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    #[test]
+    fn hash_u256_matches_reference() {
+        let k = 9;
+        // Larger than the modulus, so it cannot be absorbed as a single word.
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0x42;
+        let limbs = u256_limbs::<Fp>(bytes);
+        assert_eq!(limbs[0], Fp::from_u128(u128::MAX - 0xbd));
+        assert_eq!(limbs[1], Fp::from_u128(u128::MAX));
+
+        let expected = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash_u256(bytes, Fp::ZERO);
+        let circuit = U256HashCircuit {
+            limbs: limbs.map(Value::known),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![expected]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn hash_u256_rejects_out_of_range_limb() {
+        let k = 9;
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0x42;
+        let limbs = u256_limbs::<Fp>(bytes);
+
+        // The same word, reduced into the low limb. The instance is the hash of the
+        // limbs as given, so only the range check of the low limb can fail.
+        let reduced = limbs[0] + limbs[1] * Fp::from_u128(1 << 64).square();
+        let expected = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash([reduced, Fp::ZERO], Fp::ZERO);
+        let out_of_range = U256HashCircuit {
+            limbs: [Value::known(reduced), Value::known(Fp::ZERO)],
+        };
+        let prover = MockProver::run(k, &out_of_range, vec![vec![expected]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn hash_u256_rejects_wrong_output() {
+        let k = 9;
+        let mut bytes = [0xff; 32];
+        bytes[0] = 0x42;
+        let limbs = u256_limbs::<Fp>(bytes);
+
+        // In-range limbs bound to another word's hash, so only the output fails.
+        bytes[31] = 0;
+        let other = poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<2>, 3, 2>::init()
+            .hash_u256(bytes, Fp::ZERO);
+        let circuit = U256HashCircuit {
+            limbs: limbs.map(Value::known),
+        };
+        let prover = MockProver::run(k, &circuit, vec![vec![other]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Hashes a value packed with a boolean tag, and binds the output to the instance
+    /// column.
+    struct TaggedHashCircuit {
+        value: Value<Fp>,
+        tag: Value<bool>,
+    }
+
+    impl Circuit<Fp> for TaggedHashCircuit {
+        type Config = (BoundedAbsorbConfig, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            TaggedHashCircuit {
+                value: Value::unknown(),
+                tag: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            U256HashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let value = layouter.assign_region(
+                || "load value",
+                |mut region| region.assign_advice(|| "value", config.message, 0, || self.value),
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let range_check = RangeCheckChip::construct(config.range_check.clone());
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<1>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_tagged(
+                layouter.namespace(|| "absorb tagged"),
+                &range_check,
+                &value,
+                self.tag,
+            )?;
+            sponge.pad_block(Fp::ZERO);
+            let output = sponge
+                .finish_absorbing(layouter.namespace(|| "finish absorbing"))?
+                .squeeze(layouter.namespace(|| "squeeze"))?;
+            layouter.constrain_instance(output.cell(), instance, 0)
+        }
+    }
+
+    #[test]
+    fn hash_tagged_value_matches_reference() {
+        let value = Fp::from(42);
+        assert_eq!(pack_tagged(value, true), Fp::from(85));
+
+        let expected = [false, true].map(|tag| {
+            poseidon::Hash::<_, P128Pow5T3<Fp>, ConstantLength<1>, 3, 2>::init()
+                .hash([pack_tagged(value, tag)], Fp::ZERO)
+        });
+        assert_ne!(expected[0], expected[1]);
+
+        for (tag, expected) in [false, true].into_iter().zip(expected) {
+            let circuit = TaggedHashCircuit {
+                value: Value::known(value),
+                tag: Value::known(tag),
+            };
+            let prover = MockProver::run(8, &circuit, vec![vec![expected]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+
+        let circuit = TaggedHashCircuit {
+            value: Value::known(value),
+            tag: Value::known(true),
+        };
+        let prover = MockProver::run(8, &circuit, vec![vec![expected[0]]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// The moduli [`ShuffleIndexCircuit`] reduces its squeezed words by.
+    const MODULI: [u64; 3] = [2, 10, 1000];
+
+    /// Absorbs a two-word seed and squeezes an index for each of [`MODULI`], binding
+    /// them, in order, to the instance column.
+    struct ShuffleIndexCircuit {
+        seed: [Value<Fp>; 2],
+    }
+
+    impl Circuit<Fp> for ShuffleIndexCircuit {
+        type Config = (BoundedAbsorbConfig, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ShuffleIndexCircuit {
+                seed: [Value::unknown(); 2],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            U256HashCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let seed = layouter.assign_region(
+                || "load seed",
+                |mut region| {
+                    let first =
+                        region.assign_advice(|| "seed_0", config.message, 0, || self.seed[0])?;
+                    let second =
+                        region.assign_advice(|| "seed_1", config.message, 1, || self.seed[1])?;
+                    Ok([first, second])
+                },
+            )?;
+
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let range_check = RangeCheckChip::construct(config.range_check.clone());
+            let mut sponge = Sponge::<_, _, P128Pow5T3<Fp>, _, ConstantLength<2>, 3, 2>::new(
+                chip,
+                layouter.namespace(|| "init"),
+            )?;
+            sponge.absorb_cells(layouter.namespace(|| "absorb seed"), &seed)?;
+            let mut sponge = sponge.finish_absorbing(layouter.namespace(|| "finish absorbing"))?;
+            for (i, modulus) in MODULI.into_iter().enumerate() {
+                let index = sponge.squeeze_index(
+                    layouter.namespace(|| format!("index_{}", i)),
+                    &range_check,
+                    modulus,
+                )?;
+                layouter.constrain_instance(index.cell(), instance, i)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn squeezed_indices_match_reference() {
+        let seed = [Fp::from(7), Fp::from(11)];
+        let mut reference = poseidon::Sponge::<_, P128Pow5T3<Fp>, _, 3, 2>::new(
+            <ConstantLength<2> as Domain<Fp, 2>>::initial_capacity_element(),
+            0,
+        );
+        seed.into_iter().for_each(|word| reference.absorb(word));
+        let mut reference = reference.finish_absorbing();
+        let indices = MODULI.map(|modulus| reduce_index(reference.squeeze(), modulus));
+        assert!(indices.iter().zip(MODULI).all(|(index, modulus)| *index < modulus));
+
+        let circuit = ShuffleIndexCircuit {
+            seed: seed.map(Value::known),
+        };
+        let expected: Vec<_> = indices.iter().copied().map(Fp::from).collect();
+        let prover = MockProver::run(11, &circuit, vec![expected.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Any other index is rejected.
+        let mut wrong = expected;
+        wrong[2] = Fp::from((indices[2] + 1) % MODULI[2]);
+        let prover = MockProver::run(11, &circuit, vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
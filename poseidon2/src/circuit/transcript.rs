@@ -0,0 +1,225 @@
+//! An in-circuit Fiat-Shamir transcript built on the Poseidon sponge.
+
+use ff::FromUniformBytes;
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter},
+    plonk::Error,
+};
+
+use super::poseidon::{PaddedWord, PoseidonSpongeInstructions, Sponge};
+use crate::base::primitives::{Absorbing, Domain, Spec};
+
+/// A Fiat-Shamir transcript which keeps its sponge state across calls.
+///
+/// This is the in-circuit analog of [`crate::base::primitives::Transcript`]: before a
+/// challenge is squeezed the pending block is padded with a one followed by zeros, and
+/// the challenge is absorbed back as the first word of the next block.
+#[derive(Debug)]
+pub struct Transcript<
+    F: FromUniformBytes<64> + Ord,
+    PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+    S: Spec<F, T, RATE>,
+    D: Domain<F, RATE>,
+    const T: usize,
+    const RATE: usize,
+> {
+    // Only `None` while a challenge is being squeezed.
+    sponge: Option<Sponge<F, PoseidonChip, S, Absorbing<PaddedWord<F>, RATE>, D, T, RATE>>,
+}
+
+impl<
+        F: FromUniformBytes<64> + Ord,
+        PoseidonChip: PoseidonSpongeInstructions<F, S, D, T, RATE>,
+        S: Spec<F, T, RATE>,
+        D: Domain<F, RATE>,
+        const T: usize,
+        const RATE: usize,
+    > Transcript<F, PoseidonChip, S, D, T, RATE>
+{
+    /// Initializes an empty transcript.
+    pub fn new(chip: PoseidonChip, layouter: impl Layouter<F>) -> Result<Self, Error> {
+        Sponge::new(chip, layouter).map(|sponge| Transcript {
+            sponge: Some(sponge),
+        })
+    }
+
+    /// Absorbs the given prover message, in a namespace named by `label`.
+    pub fn absorb(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+        label: &str,
+        message: &[AssignedCell<F, F>],
+    ) -> Result<(), Error> {
+        let sponge = self.sponge.as_mut().expect("transcript sponge is present");
+        let mut layouter = layouter.namespace(|| label.to_string());
+        for (i, cell) in message.iter().enumerate() {
+            sponge.absorb(
+                layouter.namespace(|| format!("absorb_{}", i)),
+                PaddedWord::Message(cell.clone()),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Squeezes a challenge, which is absorbed back into the transcript.
+    pub fn squeeze_challenge(
+        &mut self,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let mut sponge = self.sponge.take().expect("transcript sponge is present");
+        // Absorbing the one permutes a full pending block first, as the reference does.
+        sponge.absorb(layouter.namespace(|| "pad"), PaddedWord::Padding(F::ONE))?;
+        sponge.pad_block(F::ZERO);
+
+        let mut sponge = sponge.finish_absorbing(layouter.namespace(|| "finish absorbing"))?;
+        let challenge = sponge.squeeze(layouter.namespace(|| "squeeze"))?;
+        self.sponge = Some(sponge.finish_squeezing(challenge.clone())?);
+
+        Ok(challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+    use halo2curves::bn256::Fr as Fp;
+
+    use super::Transcript;
+    use crate::base::primitives::{self, VariableLength};
+    use crate::base::P128Pow5T3;
+    use crate::circuit::pow5::{Pow5Chip, Pow5Config};
+
+    #[derive(Clone, Debug)]
+    struct TranscriptConfig {
+        pow5: Pow5Config<Fp, 3, 2>,
+        message: Column<Advice>,
+        instance: Column<Instance>,
+    }
+
+    /// Absorbs two messages, squeezing a challenge after each, and binds both
+    /// challenges to the instance column.
+    struct TranscriptCircuit {
+        messages: [Vec<Value<Fp>>; 2],
+    }
+
+    impl Circuit<Fp> for TranscriptCircuit {
+        type Config = TranscriptConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            TranscriptCircuit {
+                messages: self
+                    .messages
+                    .clone()
+                    .map(|message| vec![Value::unknown(); message.len()]),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> TranscriptConfig {
+            let state = [0; 3].map(|_| meta.advice_column());
+            let partial_sbox = meta.advice_column();
+            let rc_a = [0; 3].map(|_| meta.fixed_column());
+            let pad_fixed = [0; 3].map(|_| meta.fixed_column());
+
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            let message = meta.advice_column();
+            meta.enable_equality(message);
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+
+            TranscriptConfig {
+                pow5: Pow5Chip::configure::<P128Pow5T3<Fp>>(
+                    meta,
+                    state,
+                    partial_sbox,
+                    rc_a,
+                    pad_fixed,
+                ),
+                message,
+                instance,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: TranscriptConfig,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = Pow5Chip::construct(config.pow5.clone());
+            let mut transcript =
+                Transcript::<_, _, P128Pow5T3<Fp>, VariableLength, 3, 2>::new(
+                    chip,
+                    layouter.namespace(|| "init"),
+                )?;
+
+            for (round, message) in self.messages.iter().enumerate() {
+                let cells = layouter.assign_region(
+                    || format!("load message_{}", round),
+                    |mut region| {
+                        message
+                            .iter()
+                            .enumerate()
+                            .map(|(i, value)| {
+                                region.assign_advice(
+                                    || format!("message_{}", i),
+                                    config.message,
+                                    i,
+                                    || *value,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, Error>>()
+                    },
+                )?;
+
+                transcript.absorb(
+                    layouter.namespace(|| "transcript"),
+                    &format!("message_{}", round),
+                    &cells,
+                )?;
+                let challenge = transcript
+                    .squeeze_challenge(layouter.namespace(|| format!("challenge_{}", round)))?;
+                layouter.constrain_instance(challenge.cell(), config.instance, round)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transcript_matches_reference() {
+        let messages = [
+            vec![Fp::from(1), Fp::from(2), Fp::from(3)],
+            vec![Fp::from(4)],
+        ];
+
+        let mut reference =
+            primitives::Transcript::<_, P128Pow5T3<Fp>, VariableLength, 3, 2>::init();
+        let challenges = messages
+            .iter()
+            .map(|message| {
+                reference.absorb(message);
+                reference.squeeze_challenge()
+            })
+            .collect::<Vec<_>>();
+
+        let circuit = TranscriptCircuit {
+            messages: messages
+                .clone()
+                .map(|message| message.into_iter().map(Value::known).collect()),
+        };
+
+        let k = 9;
+        let prover = MockProver::run(k, &circuit, vec![challenges.clone()]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let wrong = vec![challenges[0], challenges[1] + Fp::ONE];
+        let prover = MockProver::run(k, &circuit, vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}